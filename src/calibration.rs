@@ -0,0 +1,67 @@
+use crate::messages::messages;
+use crossterm::style::Print;
+use crossterm::terminal::{Clear, ClearType};
+use crossterm::{cursor, QueueableCommand};
+use dialoguer::Select;
+use std::io;
+use std::io::Write;
+
+pub struct FontCalibration {
+    lower_bound: f32,
+    upper_bound: f32,
+    cell_aspect_ratio: f32,
+}
+
+impl FontCalibration {
+    const SQUARE_WIDTH: usize = 24;
+    const MAX_ROUNDS: usize = 8;
+    const MIN_RATIO: f32 = 0.2;
+    const MAX_RATIO: f32 = 1.5;
+
+    pub fn new(initial_ratio: f32) -> Self {
+        Self {
+            lower_bound: Self::MIN_RATIO,
+            upper_bound: Self::MAX_RATIO,
+            cell_aspect_ratio: initial_ratio.clamp(Self::MIN_RATIO, Self::MAX_RATIO),
+        }
+    }
+
+    fn print_test_square(&self) -> io::Result<()> {
+        let rows = ((Self::SQUARE_WIDTH as f32 * self.cell_aspect_ratio).round() as usize).max(1);
+        let row = "\u{2588}".repeat(Self::SQUARE_WIDTH);
+        let mut stdout = io::stdout();
+        stdout.queue(Clear(ClearType::All))?.queue(cursor::MoveTo(0, 0))?;
+        for _ in 0..rows {
+            stdout.queue(Print(&row))?.queue(Print('\n'))?;
+        }
+        stdout.flush()
+    }
+
+    pub fn run(mut self) -> io::Result<Option<f32>> {
+        let messages = messages();
+        let items = vec![
+            messages.calibration_square,
+            messages.calibration_too_tall,
+            messages.calibration_too_wide,
+            messages.calibration_cancel,
+        ];
+        for _ in 0..Self::MAX_ROUNDS {
+            self.print_test_square()?;
+            let selection = Select::new()
+                .with_prompt(messages.calibration_prompt)
+                .default(0)
+                .items(&items)
+                .interact()
+                .map_err(io::Error::other)?;
+            match selection {
+                0 => return Ok(Some(self.cell_aspect_ratio)),
+                1 => self.upper_bound = self.cell_aspect_ratio,
+                2 => self.lower_bound = self.cell_aspect_ratio,
+                3 => return Ok(None),
+                _ => unreachable!(),
+            }
+            self.cell_aspect_ratio = (self.lower_bound + self.upper_bound) / 2.0;
+        }
+        Ok(Some(self.cell_aspect_ratio))
+    }
+}
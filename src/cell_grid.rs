@@ -0,0 +1,80 @@
+use crate::sgr::{Colour, StyledChar};
+
+pub struct CellGrid {
+    cells: Vec<Vec<StyledChar>>,
+}
+
+impl CellGrid {
+    pub fn new(cells: Vec<Vec<StyledChar>>) -> Self {
+        Self { cells }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.first().map_or(0, Vec::len)
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn block_range(index: usize, target: usize, source: usize) -> (usize, usize) {
+        let start = index * source / target;
+        let end = ((index + 1) * source / target).max(start + 1);
+        (start, end)
+    }
+
+    fn average_colour(colours: &[(u8, u8, u8)]) -> Option<Colour> {
+        if colours.is_empty() {
+            return None;
+        }
+        let count = colours.len() as u32;
+        let (r, g, b) = colours.iter().fold((0u32, 0u32, 0u32), |(r, g, b), colour| {
+            (r + colour.0 as u32, g + colour.1 as u32, b + colour.2 as u32)
+        });
+        Some(Colour::Rgb((r / count) as u8, (g / count) as u8, (b / count) as u8))
+    }
+
+    fn most_common_char(block: &[StyledChar]) -> char {
+        let mut counts: Vec<(char, usize)> = Vec::new();
+        for styled_char in block {
+            match counts.iter_mut().find(|(ch, _)| *ch == styled_char.ch) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((styled_char.ch, 1)),
+            }
+        }
+        counts.iter().max_by_key(|(_, count)| *count).map_or(' ', |(ch, _)| *ch)
+    }
+
+    fn downsample_block(&self, rows: (usize, usize), columns: (usize, usize)) -> StyledChar {
+        let block: Vec<StyledChar> = self.cells[rows.0..rows.1]
+            .iter()
+            .flat_map(|row| row[columns.0..columns.1].iter().copied())
+            .collect();
+        let foregrounds: Vec<_> = block.iter().filter_map(|cell| cell.foreground.map(|colour| colour.rgb())).collect();
+        let backgrounds: Vec<_> = block.iter().filter_map(|cell| cell.background.map(|colour| colour.rgb())).collect();
+        StyledChar {
+            ch: Self::most_common_char(&block),
+            foreground: Self::average_colour(&foregrounds),
+            background: Self::average_colour(&backgrounds),
+        }
+    }
+
+    pub fn thumbnail(&self, width: usize, height: usize) -> Vec<Vec<StyledChar>> {
+        let width = width.clamp(1, self.width().max(1));
+        let height = height.clamp(1, self.height().max(1));
+        if self.width() == 0 || self.height() == 0 {
+            return Vec::new();
+        }
+        (0..height)
+            .map(|row| {
+                let rows = Self::block_range(row, height, self.height());
+                (0..width)
+                    .map(|column| {
+                        let columns = Self::block_range(column, width, self.width());
+                        self.downsample_block(rows, columns)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,147 @@
+use crate::messages::{fill, messages};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::{env, fmt, io};
+
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum ConfigError {
+    NoConfigDirError,
+    ParseError(usize),
+    IoError(io::Error),
+}
+
+impl From<io::Error> for ConfigError {
+    fn from(err: io::Error) -> Self {
+        ConfigError::IoError(err)
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::NoConfigDirError => write!(f, "{}", messages().no_config_dir),
+            ConfigError::ParseError(line) => write!(f, "{}", fill(messages().config_parse_error, &[line])),
+            ConfigError::IoError(err) => write!(f, "{}", fill(messages().config_io_error, &[err])),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+#[derive(Default)]
+pub struct Config {
+    path: PathBuf,
+    sections: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl Config {
+    const CONFIG_PATH_VARIABLE: &'static str = "COLOURFUL_WORDS_CONFIG";
+    const CONFIG_DIR_NAME: &'static str = "colourful_words";
+    const CONFIG_FILE_NAME: &'static str = "config.ini";
+    pub const GLOBAL_SECTION: &'static str = "";
+
+    pub fn load() -> Result<Self, ConfigError> {
+        let path = Self::config_path()?;
+        let sections = if path.is_file() {
+            Self::parse(&fs::read_to_string(&path)?)?
+        } else {
+            BTreeMap::new()
+        };
+        Ok(Self { path, sections })
+    }
+
+    pub fn is_first_run() -> bool {
+        Self::config_path().is_ok_and(|path| !path.exists())
+    }
+
+    fn config_path() -> Result<PathBuf, ConfigError> {
+        if let Some(path) = env::var_os(Self::CONFIG_PATH_VARIABLE) {
+            return Ok(PathBuf::from(path));
+        }
+        Ok(Self::config_dir()?.join(Self::CONFIG_FILE_NAME))
+    }
+
+    pub fn config_dir() -> Result<PathBuf, ConfigError> {
+        if let Some(path) = env::var_os(Self::CONFIG_PATH_VARIABLE) {
+            return Path::new(&path)
+                .parent()
+                .map(Path::to_path_buf)
+                .ok_or(ConfigError::NoConfigDirError);
+        }
+        let config_dir = env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+            .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+            .ok_or(ConfigError::NoConfigDirError)?;
+        Ok(config_dir.join(Self::CONFIG_DIR_NAME))
+    }
+
+    fn parse(content: &str) -> Result<BTreeMap<String, BTreeMap<String, String>>, ConfigError> {
+        let mut sections: BTreeMap<String, BTreeMap<String, String>> = BTreeMap::new();
+        let mut current_section = Self::GLOBAL_SECTION.to_string();
+        for (line_index, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(section) = line.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+                current_section = section.trim().to_string();
+                continue;
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or(ConfigError::ParseError(line_index + 1))?;
+            sections
+                .entry(current_section.clone())
+                .or_default()
+                .insert(key.trim().to_string(), value.trim().to_string());
+        }
+        Ok(sections)
+    }
+
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.sections
+            .get(section)
+            .and_then(|values| values.get(key))
+            .map(String::as_str)
+    }
+
+    pub fn set(&mut self, section: &str, key: &str, value: &str) {
+        self.sections
+            .entry(section.to_string())
+            .or_default()
+            .insert(key.to_string(), value.replace(['\n', '\r'], " "));
+    }
+
+    pub fn save(&self) -> Result<(), ConfigError> {
+        // A default config was never read from disk; writing it would overwrite the user's file.
+        if self.path.as_os_str().is_empty() {
+            return Err(ConfigError::NoConfigDirError);
+        }
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut writer = io::BufWriter::new(fs::File::create(&self.path)?);
+        for (section, values) in &self.sections {
+            if section != Self::GLOBAL_SECTION {
+                writeln!(writer, "[{}]", section)?;
+            }
+            for (key, value) in values {
+                writeln!(writer, "{} = {}", key, value)?;
+            }
+            writeln!(writer)?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn terminal_section() -> String {
+        let terminal = env::var("TERM_PROGRAM")
+            .or_else(|_| env::var("TERM"))
+            .unwrap_or_else(|_| "default".to_string());
+        format!("terminal:{}", terminal)
+    }
+}
@@ -1,108 +1,2119 @@
-use crate::downloader::ImageDownloader;
+use crate::image_source::ImageSource;
+use crate::graphics::GraphicsProtocol;
+use crate::input_queue::InputQueue;
+use crate::iterm2::ITerm2;
+use crate::kitty::Kitty;
 use crate::logger::Logger;
-use crate::printer::PrinterImageData;
+use crate::messages::{fill, messages};
+use crate::palette::Palette;
+use crate::printer::{AnimationFrame, PrinterImageData, RESERVED_ROWS};
+use crate::sgr::{Colour, StyledChar};
+use crate::sixel::Sixel;
+use crate::timings::{ImageTimings, TimingStage};
 use bytes::Bytes;
-use image::{GenericImageView, RgbImage};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::terminal;
+use image::codecs::gif::GifDecoder;
+use image::metadata::Orientation;
+use image::{AnimationDecoder, DynamicImage, GenericImageView, ImageDecoder, ImageError, ImageFormat, ImageReader, Rgb, RgbImage};
 use rayon::iter::ParallelIterator;
 use rayon::prelude::IntoParallelIterator;
+use std::env;
 use std::fmt;
-use std::fmt::Write;
+use std::io::{self, Cursor, IsTerminal};
 use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
 
 #[derive(Debug)]
 pub enum ConverterError {
-    ImageLoadingError,
+    ImageLoadingError { format: Option<ImageFormat>, cause: String },
+    InvalidWidthError,
+    CancelledError,
+    EmptyRampError,
+    UnsortedRampError(char, char),
+    WidthLimitError(u32, u32),
+    PixelLimitError(u64, u64),
+    CellLimitError(u64, u64),
+    InvalidCropError,
+    InvalidDuotoneError,
 }
 
 impl fmt::Display for ConverterError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            ConverterError::ImageLoadingError => write!(f, "Failed to load image from memory"),
+            ConverterError::ImageLoadingError { format: None, cause } => write!(f, "{}", fill(messages().image_format_unknown, &[cause])),
+            ConverterError::ImageLoadingError { format: Some(format), cause } => {
+                let format_name = format!("{:?}", format).to_uppercase();
+                write!(f, "{}", fill(messages().image_loading_error, &[&format_name, cause]))?;
+                // AVIF decoding links the native dav1d library, so it is left out of default builds.
+                if *format == ImageFormat::Avif && !cfg!(feature = "avif") {
+                    write!(f, " - {}", messages().avif_feature_hint)?;
+                }
+                Ok(())
+            }
+            ConverterError::InvalidWidthError => write!(f, "{}", messages().invalid_width),
+            ConverterError::CancelledError => write!(f, "{}", messages().conversion_cancelled),
+            ConverterError::EmptyRampError => write!(f, "{}", messages().ramp_empty),
+            ConverterError::UnsortedRampError(previous, next) => {
+                write!(f, "{}", fill(messages().ramp_unsorted, &[next, previous]))
+            }
+            ConverterError::WidthLimitError(width, max_width) => {
+                write!(f, "{}", fill(messages().width_limit_exceeded, &[width, max_width]))
+            }
+            ConverterError::PixelLimitError(pixels, max_pixels) => {
+                write!(f, "{}", fill(messages().pixel_limit_exceeded, &[pixels, max_pixels]))
+            }
+            ConverterError::CellLimitError(cells, max_cells) => {
+                write!(f, "{}", fill(messages().cell_limit_exceeded, &[cells, max_cells]))
+            }
+            ConverterError::InvalidCropError => write!(f, "{}", messages().invalid_crop),
+            ConverterError::InvalidDuotoneError => write!(f, "{}", fill(messages().invalid_duotone, &[&Duotone::EXAMPLE])),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Transform {
+    quarter_turns: u16,
+    flip_horizontal: bool,
+    flip_vertical: bool,
+}
+
+impl Transform {
+    pub const ROTATIONS: [u16; 4] = [0, 90, 180, 270];
+    const QUARTER_TURN: u16 = 90;
+
+    pub fn new(rotation: u16, flip_horizontal: bool, flip_vertical: bool) -> Self {
+        Self {
+            quarter_turns: rotation / Self::QUARTER_TURN % 4,
+            flip_horizontal,
+            flip_vertical,
+        }
+    }
+
+    pub fn rotation(&self) -> u16 {
+        self.quarter_turns * Self::QUARTER_TURN
+    }
+
+    pub fn is_flipped_horizontally(&self) -> bool {
+        self.flip_horizontal
+    }
+
+    pub fn is_flipped_vertically(&self) -> bool {
+        self.flip_vertical
+    }
+
+    pub fn rotated_clockwise(self) -> Self {
+        Self::new(self.rotation() + Self::QUARTER_TURN, self.flip_horizontal, self.flip_vertical)
+    }
+
+    pub fn flipped_horizontally(self) -> Self {
+        Self { flip_horizontal: !self.flip_horizontal, ..self }
+    }
+
+    pub fn flipped_vertically(self) -> Self {
+        Self { flip_vertical: !self.flip_vertical, ..self }
+    }
+
+    // Rotation comes first, so the flips always act on the picture as it is seen.
+    fn apply(&self, img: DynamicImage) -> DynamicImage {
+        let img = match self.quarter_turns {
+            1 => img.rotate90(),
+            2 => img.rotate180(),
+            3 => img.rotate270(),
+            _ => img,
+        };
+        let img = if self.flip_horizontal { img.fliph() } else { img };
+        if self.flip_vertical { img.flipv() } else { img }
+    }
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = Vec::new();
+        if self.quarter_turns != 0 {
+            parts.push(fill(messages().transform_rotated, &[&self.rotation()]));
+        }
+        if self.flip_horizontal {
+            parts.push(messages().transform_flipped_horizontally.to_string());
+        }
+        if self.flip_vertical {
+            parts.push(messages().transform_flipped_vertically.to_string());
+        }
+        if parts.is_empty() {
+            return write!(f, "{}", messages().transform_none);
+        }
+        write!(f, "{}", parts.join(", "))
+    }
+}
+
+// Stored as fractions of the frame so the same region fits images of any size.
+#[derive(Debug, Clone, Copy)]
+pub struct CropRegion {
+    left: f32,
+    top: f32,
+    width: f32,
+    height: f32,
+}
+
+impl CropRegion {
+    const MIN_ZOOM: f32 = 100.0;
+
+    fn from_centre(centre_x: f32, centre_y: f32, zoom: f32) -> Self {
+        let size = 1.0 / zoom;
+        Self {
+            left: (centre_x - size / 2.0).clamp(0.0, 1.0 - size),
+            top: (centre_y - size / 2.0).clamp(0.0, 1.0 - size),
+            width: size,
+            height: size,
+        }
+    }
+
+    fn apply(&self, img: &DynamicImage) -> DynamicImage {
+        let (image_width, image_height) = img.dimensions();
+        let scale = |fraction: f32, length: u32| (fraction * length as f32).round() as u32;
+        let x = scale(self.left, image_width).min(image_width.saturating_sub(1));
+        let y = scale(self.top, image_height).min(image_height.saturating_sub(1));
+        let width = scale(self.width, image_width).clamp(1, image_width - x);
+        let height = scale(self.height, image_height).clamp(1, image_height - y);
+        img.crop_imm(x, y, width, height)
+    }
+}
+
+impl FromStr for CropRegion {
+    type Err = ConverterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s
+            .split(',')
+            .map(|part| part.trim().trim_end_matches('%').parse::<f32>().map_err(|_| ConverterError::InvalidCropError))
+            .collect::<Result<Vec<_>, _>>()?;
+        if parts.iter().any(|part| !part.is_finite() || *part < 0.0) {
+            return Err(ConverterError::InvalidCropError);
+        }
+        let region = match parts.as_slice() {
+            [left, top, width, height] if *width > 0.0 && *height > 0.0 && left + width <= 100.0 && top + height <= 100.0 => Self {
+                left: left / 100.0,
+                top: top / 100.0,
+                width: width / 100.0,
+                height: height / 100.0,
+            },
+            [centre_x, centre_y, zoom] if *centre_x <= 100.0 && *centre_y <= 100.0 && *zoom >= Self::MIN_ZOOM => {
+                Self::from_centre(centre_x / 100.0, centre_y / 100.0, zoom / 100.0)
+            }
+            _ => return Err(ConverterError::InvalidCropError),
+        };
+        Ok(region)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum WidthSpec {
+    Fixed(u32),
+    TerminalPercentage(u8),
+    FitTerminal,
+}
+
+impl WidthSpec {
+    const FALLBACK_TERMINAL_SIZE: (u16, u16) = (100, 40);
+    const FIT_TERMINAL_NAME: &'static str = "auto";
+    pub const PRESETS: [(&'static str, u8); 3] = [("small", 33), ("medium", 66), ("full", 100)];
+
+    pub fn resolve(&self) -> u32 {
+        match self {
+            WidthSpec::Fixed(width) => *width,
+            WidthSpec::TerminalPercentage(percentage) => {
+                let (columns, _) = Self::terminal_size();
+                (columns as u32 * *percentage as u32 / 100).max(1)
+            }
+            WidthSpec::FitTerminal => (Self::terminal_size().0 as u32).max(1),
+        }
+    }
+
+    fn terminal_size() -> (u16, u16) {
+        terminal::size().unwrap_or(Self::FALLBACK_TERMINAL_SIZE)
+    }
+
+    // Leaves room for the rows the printer draws around the image.
+    fn available_rows() -> u32 {
+        (Self::terminal_size().1.saturating_sub(RESERVED_ROWS as u16) as u32).max(1)
+    }
+
+    pub fn check(&self, size_limits: &SizeLimits) -> Result<(), ConverterError> {
+        size_limits.check_width(self.resolve())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SizeLimits {
+    pub max_width: u32,
+    pub max_decoded_pixels: u64,
+    pub max_cells: u64,
+}
+
+impl Default for SizeLimits {
+    fn default() -> Self {
+        Self {
+            max_width: 1000,
+            max_decoded_pixels: 64_000_000,
+            max_cells: 500_000,
+        }
+    }
+}
+
+impl SizeLimits {
+    fn check_width(&self, width: u32) -> Result<(), ConverterError> {
+        if width > self.max_width {
+            return Err(ConverterError::WidthLimitError(width, self.max_width));
+        }
+        Ok(())
+    }
+
+    fn check_decoded_pixels(&self, (width, height): (u32, u32)) -> Result<(), ConverterError> {
+        let pixels = width as u64 * height as u64;
+        if pixels > self.max_decoded_pixels {
+            return Err(ConverterError::PixelLimitError(pixels, self.max_decoded_pixels));
+        }
+        Ok(())
+    }
+
+    fn check_cells(&self, columns: u32, rows: u32) -> Result<(), ConverterError> {
+        let cells = columns as u64 * rows as u64;
+        if cells > self.max_cells {
+            return Err(ConverterError::CellLimitError(cells, self.max_cells));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for WidthSpec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WidthSpec::Fixed(width) => write!(f, "{}", width),
+            WidthSpec::FitTerminal => write!(f, "{}", Self::FIT_TERMINAL_NAME),
+            WidthSpec::TerminalPercentage(percentage) => match Self::PRESETS.iter().find(|(_, preset)| preset == percentage) {
+                Some((name, _)) => write!(f, "{}", name),
+                None => write!(f, "{}%", percentage),
+            },
+        }
+    }
+}
+
+impl FromStr for WidthSpec {
+    type Err = ConverterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        if s == Self::FIT_TERMINAL_NAME {
+            return Ok(WidthSpec::FitTerminal);
+        }
+        if let Some((_, percentage)) = Self::PRESETS.iter().find(|(name, _)| *name == s) {
+            return Ok(WidthSpec::TerminalPercentage(*percentage));
+        }
+        if let Some(percentage) = s.strip_suffix('%') {
+            return match percentage.trim().parse::<u8>() {
+                Ok(percentage @ 1..=100) => Ok(WidthSpec::TerminalPercentage(percentage)),
+                _ => Err(ConverterError::InvalidWidthError),
+            };
+        }
+        match s.parse::<u32>() {
+            Ok(width) if width > 0 => Ok(WidthSpec::Fixed(width)),
+            _ => Err(ConverterError::InvalidWidthError),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CharacterRamp {
+    chars: Vec<char>,
+}
+
+impl Default for CharacterRamp {
+    fn default() -> Self {
+        Self {
+            chars: Self::DEFAULT_CHARS.to_vec(),
+        }
+    }
+}
+
+impl CharacterRamp {
+    const DEFAULT_CHARS: [char; 13] = [
+        '@', '#', 'S', '%', '&', '?', '*', '=', '+', '-', ':', ',', '.',
+    ];
+    const SHADING_CHARS: [char; 5] = ['\u{2588}', '\u{2593}', '\u{2592}', '\u{2591}', ' '];
+    const SPARSE_LEVELS: u32 = 4;
+
+    pub fn shading() -> Self {
+        Self {
+            chars: Self::SHADING_CHARS.to_vec(),
+        }
+    }
+
+    fn density_tier(ch: char) -> Option<u8> {
+        match ch {
+            '\u{2588}' | '\u{2593}' | '@' | '#' | '$' | '%' | '&' | '8' | '0' | 'B' | 'D' | 'G' | 'H' | 'M'
+            | 'N' | 'Q' | 'R' | 'S' | 'W' | 'g' | 'm' | 'w' => Some(4),
+            '\u{2592}' | '?' => Some(3),
+            '\u{2591}' | '*' | '=' | '+' | '!' | '|' | '/' | '\\' | '(' | ')' | '[' | ']' | '{' | '}' | '<'
+            | '>' | '^' | '~' | '"' | ';' => Some(2),
+            '-' | '_' | ':' | ',' | '.' | '\'' | '`' => Some(1),
+            ' ' | '\u{2800}' => Some(0),
+            ch if ch.is_ascii_alphanumeric() => Some(3),
+            _ => None,
+        }
+    }
+
+    pub fn char_for_brightness(&self, brightness: u32) -> char {
+        let ramp_length_m1 = (self.chars.len() - 1) as u32;
+        self.chars[((brightness * ramp_length_m1 + 127) / 255) as usize]
+    }
+
+    // Flat regions only get a few evenly spaced characters, so they read as calm areas.
+    fn sparse_char_for_brightness(&self, brightness: u32) -> char {
+        let levels_m1 = Self::SPARSE_LEVELS - 1;
+        let level = (brightness * levels_m1 + 127) / 255;
+        self.char_for_brightness(level * 255 / levels_m1)
+    }
+}
+
+impl FromStr for CharacterRamp {
+    type Err = ConverterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let chars: Vec<char> = s.chars().filter(|ch| !ch.is_control()).collect();
+        if chars.is_empty() {
+            return Err(ConverterError::EmptyRampError);
+        }
+        let mut previous: Option<(char, u8)> = None;
+        for &ch in &chars {
+            if let Some(tier) = Self::density_tier(ch) {
+                if let Some((previous_ch, previous_tier)) = previous {
+                    if tier > previous_tier {
+                        return Err(ConverterError::UnsortedRampError(previous_ch, ch));
+                    }
+                }
+                previous = Some((ch, tier));
+            }
+        }
+        Ok(Self { chars })
+    }
+}
+
+impl fmt::Display for CharacterRamp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.chars.iter().try_for_each(|ch| write!(f, "{}", ch))
+    }
+}
+
+struct CancellationWatcher {
+    finished: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl CancellationWatcher {
+    const POLL_INTERVAL: Duration = Duration::from_millis(25);
+
+    // Without a terminal on stdin there is no Esc to wait for, and polling would only fail over and over.
+    fn start(cancellation_requested: Arc<AtomicBool>) -> Self {
+        let finished = Arc::new(AtomicBool::new(false));
+        if !io::stdin().is_terminal() {
+            return Self { finished, handle: None };
+        }
+        let watcher_finished = finished.clone();
+        let handle = thread::spawn(move || {
+            while !watcher_finished.load(Ordering::Relaxed) {
+                match event::poll(Self::POLL_INTERVAL) {
+                    Ok(true) => {}
+                    Ok(false) => continue,
+                    // A terminal that went away cannot deliver Esc any more.
+                    Err(_) => return,
+                }
+                match event::read() {
+                    Ok(Event::Key(key_event)) if key_event.kind == KeyEventKind::Press && key_event.code == KeyCode::Esc => {
+                        cancellation_requested.store(true, Ordering::Relaxed);
+                    }
+                    Ok(other_event) => InputQueue::defer(other_event),
+                    Err(_) => return,
+                }
+            }
+        });
+        Self { finished, handle: Some(handle) }
+    }
+
+    fn stop(self) {
+        self.finished.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RenderStyle {
+    #[default]
+    Ascii,
+    Braille,
+    HalfBlock,
+    Quadrant,
+    Mosaic,
+    EdgeAware,
+    Sixel,
+    Kitty,
+    ITerm2,
+    Emoji,
+}
+
+impl RenderStyle {
+    pub const ALL: [RenderStyle; 10] = [
+        RenderStyle::Ascii,
+        RenderStyle::Braille,
+        RenderStyle::HalfBlock,
+        RenderStyle::Quadrant,
+        RenderStyle::Mosaic,
+        RenderStyle::EdgeAware,
+        RenderStyle::Sixel,
+        RenderStyle::Kitty,
+        RenderStyle::ITerm2,
+        RenderStyle::Emoji,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            RenderStyle::Ascii => "ascii",
+            RenderStyle::Braille => "braille",
+            RenderStyle::HalfBlock => "half-block",
+            RenderStyle::Quadrant => "quadrant",
+            RenderStyle::Mosaic => "mosaic",
+            RenderStyle::EdgeAware => "edges",
+            RenderStyle::Sixel => "sixel",
+            RenderStyle::Kitty => "kitty",
+            RenderStyle::ITerm2 => "iterm2",
+            RenderStyle::Emoji => "emoji",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<RenderStyle> {
+        Self::ALL.into_iter().find(|style| style.code() == code.trim())
+    }
+
+    pub fn resolve(self) -> RenderStyle {
+        match self {
+            RenderStyle::Sixel if !Sixel::is_supported() => RenderStyle::Ascii,
+            RenderStyle::Kitty if !Kitty::is_supported() => RenderStyle::Ascii,
+            RenderStyle::ITerm2 if !ITerm2::is_supported() => RenderStyle::Ascii,
+            style => style,
+        }
+    }
+
+    fn has_pixel_cells(&self) -> bool {
+        matches!(self, RenderStyle::Ascii | RenderStyle::EdgeAware | RenderStyle::Mosaic)
+    }
+
+    fn is_graphic(&self) -> bool {
+        matches!(self, RenderStyle::Sixel | RenderStyle::Kitty | RenderStyle::ITerm2)
+    }
+}
+
+impl fmt::Display for RenderStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RenderStyle::Ascii => write!(f, "{}", messages().render_style_ascii),
+            RenderStyle::Braille => write!(f, "{}", messages().render_style_braille),
+            RenderStyle::HalfBlock => write!(f, "{}", messages().render_style_half_block),
+            RenderStyle::Quadrant => write!(f, "{}", messages().render_style_quadrant),
+            RenderStyle::Mosaic => write!(f, "{}", messages().render_style_mosaic),
+            RenderStyle::EdgeAware => write!(f, "{}", messages().render_style_edge_aware),
+            RenderStyle::Sixel => write!(f, "{}", messages().render_style_sixel),
+            RenderStyle::Kitty => write!(f, "{}", messages().render_style_kitty),
+            RenderStyle::ITerm2 => write!(f, "{}", messages().render_style_iterm2),
+            RenderStyle::Emoji => write!(f, "{}", messages().render_style_emoji),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColourMode {
+    #[default]
+    Auto,
+    TrueColour,
+    Ansi256,
+    Ansi16,
+    Monochrome,
+}
+
+impl ColourMode {
+    pub const ALL: [ColourMode; 5] = [
+        ColourMode::Auto,
+        ColourMode::TrueColour,
+        ColourMode::Ansi256,
+        ColourMode::Ansi16,
+        ColourMode::Monochrome,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ColourMode::Auto => "auto",
+            ColourMode::TrueColour => "truecolour",
+            ColourMode::Ansi256 => "256",
+            ColourMode::Ansi16 => "16",
+            ColourMode::Monochrome => "monochrome",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<ColourMode> {
+        Self::ALL.into_iter().find(|mode| mode.code() == code.trim())
+    }
+
+    pub fn detect() -> ColourMode {
+        let colour_term = env::var("COLORTERM").unwrap_or_default().to_lowercase();
+        if colour_term == "truecolor" || colour_term == "24bit" {
+            return ColourMode::TrueColour;
+        }
+        match env::var("TERM") {
+            Ok(term) if term == "dumb" => ColourMode::Monochrome,
+            Ok(term) if term.contains("256color") => ColourMode::Ansi256,
+            Ok(term) if !term.is_empty() => ColourMode::Ansi16,
+            _ => ColourMode::TrueColour,
+        }
+    }
+
+    pub fn resolve(self) -> ColourMode {
+        match self {
+            ColourMode::Auto => Self::detect(),
+            mode => mode,
+        }
+    }
+
+    fn is_reduced_palette(self) -> bool {
+        matches!(self, ColourMode::Ansi256 | ColourMode::Ansi16)
+    }
+
+    fn snap(self, colour: [u8; 3]) -> [u8; 3] {
+        self.quantize(colour).map_or(colour, |quantized| {
+            let (r, g, b) = quantized.rgb();
+            [r, g, b]
+        })
+    }
+
+    fn quantize(self, [r, g, b]: [u8; 3]) -> Option<Colour> {
+        match self {
+            ColourMode::Auto => self.resolve().quantize([r, g, b]),
+            ColourMode::TrueColour => Some(Colour::Rgb(r, g, b)),
+            ColourMode::Ansi256 => Some(Colour::ansi_256(r, g, b)),
+            ColourMode::Ansi16 => Some(Colour::ansi_16(r, g, b)),
+            ColourMode::Monochrome => None,
+        }
+    }
+}
+
+impl fmt::Display for ColourMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ColourMode::Auto => write!(f, "{}", fill(messages().colour_mode_auto, &[&Self::detect()])),
+            ColourMode::TrueColour => write!(f, "{}", messages().colour_mode_true_colour),
+            ColourMode::Ansi256 => write!(f, "{}", messages().colour_mode_ansi_256),
+            ColourMode::Ansi16 => write!(f, "{}", messages().colour_mode_ansi_16),
+            ColourMode::Monochrome => write!(f, "{}", messages().colour_mode_monochrome),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Dithering {
+    #[default]
+    Off,
+    FloydSteinberg,
+    Bayer4,
+    Bayer8,
+}
+
+impl Dithering {
+    pub const ALL: [Dithering; 4] = [Dithering::Off, Dithering::FloydSteinberg, Dithering::Bayer4, Dithering::Bayer8];
+
+    fn bayer_size(&self) -> Option<u32> {
+        match self {
+            Dithering::Bayer4 => Some(4),
+            Dithering::Bayer8 => Some(8),
+            _ => None,
+        }
+    }
+
+    // The threshold matrix entry is built by interleaving the bits of y and x ^ y, centred on zero.
+    fn bayer_offset(size: u32, x: u32, y: u32) -> f32 {
+        let bits = size.trailing_zeros();
+        let (x, y) = (x % size, y % size);
+        let rank = (0..bits).fold(0, |rank, bit| {
+            let shift = 2 * (bits - 1 - bit);
+            rank | (((x ^ y) >> bit) & 1) << (shift + 1) | ((y >> bit) & 1) << shift
+        });
+        (rank as f32 + 0.5) / (size * size) as f32 - 0.5
+    }
+}
+
+impl fmt::Display for Dithering {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Dithering::Off => write!(f, "{}", messages().dithering_off),
+            Dithering::FloydSteinberg => write!(f, "{}", messages().dithering_floyd_steinberg),
+            Dithering::Bayer4 | Dithering::Bayer8 => {
+                let size = self.bayer_size().unwrap_or_default();
+                write!(f, "{}", fill(messages().dithering_bayer, &[&size, &size]))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Luminance {
+    #[default]
+    Rec709,
+    Rec601,
+    Average,
+}
+
+impl Luminance {
+    pub const ALL: [Luminance; 3] = [Luminance::Rec709, Luminance::Rec601, Luminance::Average];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Luminance::Rec709 => "rec709",
+            Luminance::Rec601 => "rec601",
+            Luminance::Average => "average",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Luminance> {
+        Self::ALL.into_iter().find(|luminance| luminance.code() == code.trim())
+    }
+
+    pub fn of(&self, pixel: &Rgb<u8>) -> u32 {
+        let [r, g, b] = pixel.0.map(|channel| channel as u32);
+        match self {
+            Luminance::Rec709 => (2126 * r + 7152 * g + 722 * b) / 10000,
+            Luminance::Rec601 => (299 * r + 587 * g + 114 * b) / 1000,
+            Luminance::Average => (r + g + b) / 3,
+        }
+    }
+}
+
+impl fmt::Display for Luminance {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Luminance::Rec709 => write!(f, "{}", messages().luminance_rec709),
+            Luminance::Rec601 => write!(f, "{}", messages().luminance_rec601),
+            Luminance::Average => write!(f, "{}", messages().luminance_average),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Inversion {
+    #[default]
+    Off,
+    Characters,
+    CharactersAndColours,
+}
+
+impl Inversion {
+    pub const ALL: [Inversion; 3] = [Inversion::Off, Inversion::Characters, Inversion::CharactersAndColours];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Inversion::Off => "off",
+            Inversion::Characters => "characters",
+            Inversion::CharactersAndColours => "all",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Inversion> {
+        Self::ALL.into_iter().find(|inversion| inversion.code() == code.trim())
+    }
+
+    fn inverts_characters(&self) -> bool {
+        *self != Inversion::Off
+    }
+
+    fn inverts_colours(&self) -> bool {
+        *self == Inversion::CharactersAndColours
+    }
+}
+
+impl fmt::Display for Inversion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Inversion::Off => write!(f, "{}", messages().inversion_off),
+            Inversion::Characters => write!(f, "{}", messages().inversion_characters),
+            Inversion::CharactersAndColours => write!(f, "{}", messages().inversion_all),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AlphaMode {
+    #[default]
+    Background,
+    Checkerboard,
+    Transparent,
+}
+
+impl AlphaMode {
+    pub const ALL: [AlphaMode; 3] = [AlphaMode::Background, AlphaMode::Checkerboard, AlphaMode::Transparent];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            AlphaMode::Background => "background",
+            AlphaMode::Checkerboard => "checkerboard",
+            AlphaMode::Transparent => "transparent",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<AlphaMode> {
+        Self::ALL.into_iter().find(|alpha_mode| alpha_mode.code() == code.trim())
+    }
+}
+
+impl fmt::Display for AlphaMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlphaMode::Background => write!(f, "{}", messages().alpha_background),
+            AlphaMode::Checkerboard => write!(f, "{}", messages().alpha_checkerboard),
+            AlphaMode::Transparent => write!(f, "{}", messages().alpha_transparent),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Caption {
+    #[default]
+    Off,
+    ImageName,
+    Text(String),
+}
+
+impl Caption {
+    fn text<'a>(&'a self, image_name: &'a str) -> Option<&'a str> {
+        match self {
+            Caption::Off => None,
+            Caption::ImageName => Some(image_name),
+            Caption::Text(text) => Some(text),
+        }
+    }
+}
+
+impl fmt::Display for Caption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Caption::Off => write!(f, "{}", messages().caption_off),
+            Caption::ImageName => write!(f, "{}", messages().caption_image_name),
+            Caption::Text(text) => write!(f, "\"{}\"", text),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Effect {
+    Sepia,
+    Invert,
+    Posterize,
+    HueRotate,
+}
+
+impl Effect {
+    pub const ALL: [Effect; 4] = [Effect::Sepia, Effect::Invert, Effect::Posterize, Effect::HueRotate];
+    const POSTERIZE_LEVELS: f32 = 4.0;
+    const HUE_ROTATION_DEGREES: f32 = 120.0;
+    const SEPIA_MATRIX: [[f32; 3]; 3] = [
+        [0.393, 0.769, 0.189],
+        [0.349, 0.686, 0.168],
+        [0.272, 0.534, 0.131],
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Effect::Sepia => "sepia",
+            Effect::Invert => "invert",
+            Effect::Posterize => "posterize",
+            Effect::HueRotate => "hue-rotate",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Effect> {
+        Self::ALL.into_iter().find(|effect| effect.code() == code.trim())
+    }
+
+    // Same luminance-preserving rotation as the CSS hue-rotate() filter.
+    fn hue_rotation_matrix() -> [[f32; 3]; 3] {
+        let (sin, cos) = Self::HUE_ROTATION_DEGREES.to_radians().sin_cos();
+        [
+            [0.213 + cos * 0.787 - sin * 0.213, 0.715 - cos * 0.715 - sin * 0.715, 0.072 - cos * 0.072 + sin * 0.928],
+            [0.213 - cos * 0.213 + sin * 0.143, 0.715 + cos * 0.285 + sin * 0.140, 0.072 - cos * 0.072 - sin * 0.283],
+            [0.213 - cos * 0.213 - sin * 0.787, 0.715 - cos * 0.715 + sin * 0.715, 0.072 + cos * 0.928 + sin * 0.072],
+        ]
+    }
+
+    fn apply_matrix(image: &mut RgbImage, matrix: [[f32; 3]; 3]) {
+        for pixel in image.pixels_mut() {
+            let [r, g, b] = pixel.0.map(|channel| channel as f32);
+            pixel.0 = matrix.map(|row| (row[0] * r + row[1] * g + row[2] * b).round().clamp(0.0, 255.0) as u8);
+        }
+    }
+
+    fn apply(&self, image: &mut RgbImage) {
+        match self {
+            Effect::Sepia => Self::apply_matrix(image, Self::SEPIA_MATRIX),
+            Effect::Invert => image::imageops::invert(image),
+            Effect::Posterize => {
+                let steps = Self::POSTERIZE_LEVELS - 1.0;
+                for pixel in image.pixels_mut() {
+                    pixel.0 = pixel.0.map(|channel| ((channel as f32 * steps / 255.0).round() * 255.0 / steps).round() as u8);
+                }
+            }
+            Effect::HueRotate => Self::apply_matrix(image, Self::hue_rotation_matrix()),
+        }
+    }
+}
+
+impl fmt::Display for Effect {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Effect::Sepia => write!(f, "{}", messages().effect_sepia),
+            Effect::Invert => write!(f, "{}", messages().effect_invert),
+            Effect::Posterize => write!(f, "{}", messages().effect_posterize),
+            Effect::HueRotate => write!(f, "{}", messages().effect_hue_rotate),
+        }
+    }
+}
+
+// Effects run in the order they were chosen, each one on the output of the previous.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EffectChain {
+    effects: Vec<Effect>,
+}
+
+impl EffectChain {
+    pub fn new(effects: Vec<Effect>) -> Self {
+        Self { effects }
+    }
+
+    pub fn effects(&self) -> &[Effect] {
+        &self.effects
+    }
+
+    pub fn code(&self) -> String {
+        self.effects.iter().map(Effect::code).collect::<Vec<_>>().join(",")
+    }
+
+    pub fn from_code(code: &str) -> Option<EffectChain> {
+        let effects = code
+            .split(',')
+            .filter(|code| !code.trim().is_empty())
+            .map(Effect::from_code)
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self::new(effects))
+    }
+
+    fn apply(&self, mut image: RgbImage) -> RgbImage {
+        for effect in &self.effects {
+            effect.apply(&mut image);
+        }
+        image
+    }
+}
+
+impl fmt::Display for EffectChain {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.effects.is_empty() {
+            return write!(f, "{}", messages().effects_none);
+        }
+        let names: Vec<String> = self.effects.iter().map(Effect::to_string).collect();
+        write!(f, "{}", names.join(" \u{2192} "))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Duotone {
+    shadows: Colour,
+    highlights: Colour,
+}
+
+impl Duotone {
+    pub const EXAMPLE: &'static str = "#1b2a4a,#ffb000";
+
+    pub fn code(&self) -> String {
+        format!("{},{}", self.shadows.hex(), self.highlights.hex())
+    }
+
+    // Brightness picks a point on the line between the two colours, the source hues are dropped.
+    fn apply(&self, mut img: RgbImage, luminance: Luminance) -> RgbImage {
+        let (shadows, highlights) = (self.shadows.rgb(), self.highlights.rgb());
+        let (shadows, highlights) = ([shadows.0, shadows.1, shadows.2], [highlights.0, highlights.1, highlights.2]);
+        for pixel in img.pixels_mut() {
+            let t = luminance.of(pixel) as f32 / 255.0;
+            for (channel, (dark, light)) in pixel.0.iter_mut().zip(shadows.into_iter().zip(highlights)) {
+                *channel = (dark as f32 + (light as f32 - dark as f32) * t).round() as u8;
+            }
         }
+        img
     }
 }
 
-pub struct Converter {
-    image_iterator: ImageDownloader,
-    image_width: u32,
+impl FromStr for Duotone {
+    type Err = ConverterError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let colours: Vec<Colour> = s
+            .split(',')
+            .map(|part| Colour::from_hex(part).ok_or(ConverterError::InvalidDuotoneError))
+            .collect::<Result<_, _>>()?;
+        match colours[..] {
+            [shadows, highlights] => Ok(Self { shadows, highlights }),
+            _ => Err(ConverterError::InvalidDuotoneError),
+        }
+    }
 }
 
-impl Converter {
-    const ASCII_CHARS: [char; 13] = [
-        '@', '#', 'S', '%', '&', '?', '*', '=', '+', '-', ':', ',', '.',
+impl fmt::Display for Duotone {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} \u{2192} {}", self.shadows.hex(), self.highlights.hex())
+    }
+}
+
+#[derive(Clone)]
+pub struct ConverterOptions {
+    pub character_ramp: CharacterRamp,
+    pub render_style: RenderStyle,
+    pub colour_mode: ColourMode,
+    pub palette: Palette,
+    pub detail_threshold: Option<u32>,
+    pub dithering: Dithering,
+    pub auto_levels: bool,
+    pub double_width: bool,
+    pub luminance: Luminance,
+    pub size_limits: SizeLimits,
+    pub saturation: f32,
+    pub inversion: Inversion,
+    pub effects: EffectChain,
+    pub duotone: Option<Duotone>,
+    pub alpha_mode: AlphaMode,
+    pub alpha_background: Colour,
+    pub transform: Transform,
+    pub crop: Option<CropRegion>,
+    pub caption: Caption,
+    pub cell_aspect_ratio: f32,
+}
+
+impl Default for ConverterOptions {
+    fn default() -> Self {
+        Self {
+            character_ramp: CharacterRamp::default(),
+            render_style: RenderStyle::default(),
+            colour_mode: ColourMode::default(),
+            palette: Palette::default(),
+            detail_threshold: None,
+            dithering: Dithering::default(),
+            auto_levels: false,
+            double_width: false,
+            luminance: Luminance::default(),
+            size_limits: SizeLimits::default(),
+            saturation: Self::DEFAULT_SATURATION,
+            inversion: Inversion::default(),
+            effects: EffectChain::default(),
+            duotone: None,
+            alpha_mode: AlphaMode::default(),
+            alpha_background: Self::DEFAULT_ALPHA_BACKGROUND,
+            transform: Transform::default(),
+            crop: None,
+            caption: Caption::default(),
+            cell_aspect_ratio: Self::DEFAULT_CELL_ASPECT_RATIO,
+        }
+    }
+}
+
+impl ConverterOptions {
+    pub const DEFAULT_SATURATION: f32 = 1.0;
+    pub const MAX_SATURATION: f32 = 4.0;
+    pub const DEFAULT_DETAIL_THRESHOLD: u32 = 200;
+    // Brightness spans 0-255, so no neighbourhood can vary by more than this.
+    pub const MAX_DETAIL_THRESHOLD: u32 = 128 * 128;
+    pub const DEFAULT_CELL_ASPECT_RATIO: f32 = 0.5;
+    pub const DEFAULT_ALPHA_BACKGROUND: Colour = Colour::Rgb(0, 0, 0);
+}
+
+#[derive(Clone, Copy)]
+struct FrameLayout {
+    render_style: RenderStyle,
+    colour_mode: ColourMode,
+    pixel_size: (u32, u32),
+    cell_size: (u32, u32),
+    cells_per_pixel: u32,
+}
+
+struct ConvertedFrame {
+    image_array: Vec<Vec<StyledChar>>,
+    graphic: Option<String>,
+}
+
+// Everything but the shared name, so a conversion can be handed back from another thread.
+struct ConvertedImage {
+    image_array: Vec<Vec<StyledChar>>,
+    graphic: Option<String>,
+    frames: Vec<AnimationFrame>,
+    timings: ImageTimings,
+    source: Bytes,
+}
+
+impl ConvertedImage {
+    fn into_image_data(self, image_name: Rc<String>) -> PrinterImageData {
+        PrinterImageData::new(image_name, self.image_array)
+            .with_source(self.source)
+            .with_graphic(self.graphic)
+            .with_frames(self.frames)
+            .with_timings(self.timings)
+    }
+}
+
+// The next image converts on its own thread while the current one is on screen.
+struct PendingConversion {
+    image_name: Rc<String>,
+    image_bytes: Bytes,
+    download_time: Option<Duration>,
+    cancellation_requested: Arc<AtomicBool>,
+    handle: JoinHandle<Result<ConvertedImage, ConverterError>>,
+}
+
+pub struct Converter<S: ImageSource = Box<dyn ImageSource>> {
+    image_iterator: S,
+    image_width: WidthSpec,
+    options: ConverterOptions,
+    cancellation_requested: Arc<AtomicBool>,
+    prefetched: Option<PendingConversion>,
+}
+
+impl<S: ImageSource> Converter<S> {
+    const BRAILLE_BASE: u32 = 0x2800;
+    const BRAILLE_CELL_WIDTH: u32 = 2;
+    const BRAILLE_CELL_HEIGHT: u32 = 4;
+    const BRAILLE_DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+    const UPPER_HALF_BLOCK: char = '\u{2580}';
+    const LOWER_HALF_BLOCK: char = '\u{2584}';
+    const FULL_BLOCK: char = '\u{2588}';
+    const HALF_BLOCK_CELL_HEIGHT: u32 = 2;
+    const QUADRANT_CELL_SIZE: u32 = 2;
+    // Indexed by the covered quadrants: bit 0 top left, 1 top right, 2 bottom left, 3 bottom right.
+    const QUADRANT_CHARS: [char; 16] = [
+        ' ', '\u{2598}', '\u{259D}', '\u{2580}', '\u{2596}', '\u{258C}', '\u{259E}', '\u{259B}',
+        '\u{2597}', '\u{259A}', '\u{2590}', '\u{259C}', '\u{2584}', '\u{2599}', '\u{259F}', '\u{2588}',
+    ];
+    const FALLBACK_CELL_PIXEL_SIZE: (u32, u32) = (10, 20);
+    const MIN_FRAME_DELAY: Duration = Duration::from_millis(20);
+    const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+    const MOSAIC_CHAR: char = ' ';
+    const EMOJI_CELL_WIDTH: u32 = 2;
+    const DOUBLE_WIDTH_CELLS: u32 = 2;
+    const FULLWIDTH_OFFSET: u32 = 0xFEE0;
+    const IDEOGRAPHIC_SPACE: char = '\u{3000}';
+    // Approximate colours of the squares as most emoji fonts draw them.
+    const EMOJI_SQUARES: [(char, [u8; 3]); 9] = [
+        ('\u{1F7E5}', [221, 46, 68]),
+        ('\u{1F7E7}', [244, 144, 12]),
+        ('\u{1F7E8}', [253, 203, 88]),
+        ('\u{1F7E9}', [120, 177, 89]),
+        ('\u{1F7E6}', [85, 172, 238]),
+        ('\u{1F7EA}', [170, 142, 214]),
+        ('\u{1F7EB}', [193, 105, 79]),
+        ('\u{2B1B}', [49, 55, 61]),
+        ('\u{2B1C}', [230, 231, 232]),
+    ];
+    const EDGE_THRESHOLD: f32 = 192.0;
+    const LEVELS_CLIP_FRACTION: f32 = 0.005;
+    const CHECKER_DIVISIONS: u32 = 16;
+    const CHECKER_LIGHT: [u8; 3] = [0x99, 0x99, 0x99];
+    const CHECKER_DARK: [u8; 3] = [0x66, 0x66, 0x66];
+    const ORDERED_COLOUR_SPREAD: f32 = 64.0;
+    const FLOYD_STEINBERG_WEIGHTS: [(i64, i64, f32); 4] = [
+        (1, 0, 7.0 / 16.0),
+        (-1, 1, 3.0 / 16.0),
+        (0, 1, 5.0 / 16.0),
+        (1, 1, 1.0 / 16.0),
     ];
 
-    pub fn new(image_iterator: ImageDownloader, image_width: u32) -> Self {
+    pub fn new(image_iterator: S, image_width: WidthSpec, options: ConverterOptions) -> Self {
         Self {
             image_iterator,
             image_width,
+            options,
+            cancellation_requested: Arc::new(AtomicBool::new(false)),
+            prefetched: None,
         }
     }
 
-    fn convert_image(
-        image_width: u32,
-        image_name: Rc<String>,
-        image_bytes: Bytes,
-    ) -> Result<PrinterImageData, ConverterError> {
-        let img =
-            image::load_from_memory(&image_bytes).map_err(|_| ConverterError::ImageLoadingError)?;
-        let resized: RgbImage = {
-            let (original_width, original_height) = img.dimensions();
-            let height = original_height * image_width / original_width;
-            let height = height.max(1);
-            img.resize_exact(image_width, height, image::imageops::FilterType::CatmullRom)
-                .to_rgb8()
+    pub fn effects(&self) -> &EffectChain {
+        &self.options.effects
+    }
+
+    pub fn set_effects(&mut self, effects: EffectChain) {
+        self.options.effects = effects;
+        self.restart_prefetch();
+    }
+
+    pub fn image_width(&self) -> WidthSpec {
+        self.image_width
+    }
+
+    pub fn set_image_width(&mut self, image_width: WidthSpec) {
+        self.image_width = image_width;
+        self.restart_prefetch();
+    }
+
+    pub fn transform(&self) -> Transform {
+        self.options.transform
+    }
+
+    pub fn set_transform(&mut self, transform: Transform) {
+        self.options.transform = transform;
+        self.restart_prefetch();
+    }
+
+    // Converts an already shown source image again, picking up option changes made since.
+    pub fn reconvert(&self, image_name: Rc<String>, image_bytes: Bytes) -> Option<PrinterImageData> {
+        match self.convert_watched(image_name.clone(), image_bytes, ImageTimings::default()) {
+            Ok(printer_image_data) => Some(printer_image_data),
+            Err(ConverterError::CancelledError) => None,
+            Err(e) => {
+                Logger::log_error(&fill(messages().conversion_failed, &[&image_name, &e]));
+                None
+            }
+        }
+    }
+
+    fn convert_watched(&self, image_name: Rc<String>, image_bytes: Bytes, timings: ImageTimings) -> Result<PrinterImageData, ConverterError> {
+        self.cancellation_requested.store(false, Ordering::Relaxed);
+        let watcher = CancellationWatcher::start(self.cancellation_requested.clone());
+        let conversion_result = Self::convert_image(
+            self.image_width,
+            &self.options,
+            &image_name,
+            image_bytes,
+            timings,
+            &self.cancellation_requested,
+        );
+        watcher.stop();
+        conversion_result.map(|converted| converted.into_image_data(image_name))
+    }
+
+    fn start_conversion(&self, image_name: Rc<String>, image_bytes: Bytes, download_time: Option<Duration>) -> PendingConversion {
+        let cancellation_requested = Arc::new(AtomicBool::new(false));
+        let (image_width, options, name, bytes, cancelled) =
+            (self.image_width, self.options.clone(), image_name.to_string(), image_bytes.clone(), cancellation_requested.clone());
+        let handle = thread::spawn(move || {
+            let mut timings = ImageTimings::default();
+            if let Some(download_time) = download_time {
+                timings.record(TimingStage::Download, download_time);
+            }
+            Self::convert_image(image_width, &options, &name, bytes, timings, &cancelled)
+        });
+        PendingConversion { image_name, image_bytes, download_time, cancellation_requested, handle }
+    }
+
+    fn fetch_and_start(&mut self) -> Option<PendingConversion> {
+        let (image_name, image_bytes) = self.image_iterator.next()?;
+        let download_time = self.image_iterator.last_fetch_time();
+        Some(self.start_conversion(image_name, image_bytes, download_time))
+    }
+
+    // A prefetched image converted with outdated options is abandoned and started over.
+    fn restart_prefetch(&mut self) {
+        if let Some(pending) = self.prefetched.take() {
+            pending.cancellation_requested.store(true, Ordering::Relaxed);
+            self.prefetched = Some(self.start_conversion(pending.image_name, pending.image_bytes, pending.download_time));
+        }
+    }
+
+    // Esc only reaches the conversion being waited on, a prefetched one keeps going.
+    fn finish_conversion(&self, pending: PendingConversion) -> Result<PrinterImageData, ConverterError> {
+        let watcher = CancellationWatcher::start(pending.cancellation_requested.clone());
+        let conversion_result = pending.handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+        watcher.stop();
+        if pending.cancellation_requested.load(Ordering::Relaxed) {
+            self.cancellation_requested.store(true, Ordering::Relaxed);
+        }
+        conversion_result.map(|converted| converted.into_image_data(pending.image_name))
+    }
+
+    // Called while an image is on screen. Starts converting the following image as soon as the source has it,
+    // without ever waiting on the source, so its conversion overlaps with viewing.
+    pub fn prefetch(&mut self) {
+        if self.prefetched.is_none() && !self.image_iterator.is_live() && self.image_iterator.is_next_ready() {
+            self.prefetched = self.fetch_and_start();
+        }
+    }
+
+    pub fn take_cancellation(&self) -> bool {
+        self.cancellation_requested.swap(false, Ordering::Relaxed)
+    }
+
+    pub fn source_report(&self) -> Option<String> {
+        self.image_iterator.session_report()
+    }
+
+    fn diffuse_error<const N: usize>(buffer: &mut [[f32; N]], width: u32, height: u32, x: u32, y: u32, error: [f32; N]) {
+        for (dx, dy, weight) in Self::FLOYD_STEINBERG_WEIGHTS {
+            let (nx, ny) = (x as i64 + dx, y as i64 + dy);
+            if nx < 0 || nx >= width as i64 || ny >= height as i64 {
+                continue;
+            }
+            let neighbour = &mut buffer[(ny * width as i64 + nx) as usize];
+            for (value, channel_error) in neighbour.iter_mut().zip(error) {
+                *value += channel_error * weight;
+            }
+        }
+    }
+
+    fn dither_colours(resized: &RgbImage, dithering: Dithering, snap: impl Fn([u8; 3]) -> [u8; 3]) -> RgbImage {
+        if let Some(size) = dithering.bayer_size() {
+            let mut dithered = resized.clone();
+            for (x, y, pixel) in dithered.enumerate_pixels_mut() {
+                let offset = Dithering::bayer_offset(size, x, y) * Self::ORDERED_COLOUR_SPREAD;
+                *pixel = Rgb(snap(pixel.0.map(|channel| (channel as f32 + offset).clamp(0.0, 255.0).round() as u8)));
+            }
+            return dithered;
+        }
+        let (width, height) = resized.dimensions();
+        let mut buffer: Vec<[f32; 3]> = resized.pixels().map(|pixel| pixel.0.map(|channel| channel as f32)).collect();
+        let mut dithered = RgbImage::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                let current = buffer[(y * width + x) as usize];
+                let original = current.map(|channel| channel.clamp(0.0, 255.0).round() as u8);
+                let [r, g, b] = snap(original);
+                dithered.put_pixel(x, y, Rgb([r, g, b]));
+                let error = [current[0] - r as f32, current[1] - g as f32, current[2] - b as f32];
+                Self::diffuse_error(&mut buffer, width, height, x, y, error);
+            }
+        }
+        dithered
+    }
+
+    fn dither_brightness(resized: &RgbImage, dithering: Dithering, character_ramp: &CharacterRamp, luminance: Luminance) -> Vec<u32> {
+        let (width, height) = resized.dimensions();
+        let levels_m1 = (character_ramp.chars.len() - 1).max(1) as f32;
+        // Ordered dithering nudges each pixel by up to half a ramp step before snapping.
+        if let Some(size) = dithering.bayer_size() {
+            return resized
+                .enumerate_pixels()
+                .map(|(x, y, pixel)| {
+                    let shifted = luminance.of(pixel) as f32 + Dithering::bayer_offset(size, x, y) * 255.0 / levels_m1;
+                    let level = (shifted.clamp(0.0, 255.0) * levels_m1 / 255.0).round();
+                    (level * 255.0 / levels_m1).round() as u32
+                })
+                .collect();
+        }
+        let mut buffer: Vec<[f32; 1]> = resized.pixels().map(|pixel| [luminance.of(pixel) as f32]).collect();
+        let mut dithered = Vec::with_capacity(buffer.len());
+        for y in 0..height {
+            for x in 0..width {
+                let current = buffer[(y * width + x) as usize][0].clamp(0.0, 255.0);
+                let level = (current * levels_m1 / 255.0).round();
+                let snapped = level * 255.0 / levels_m1;
+                dithered.push(snapped.round() as u32);
+                Self::diffuse_error(&mut buffer, width, height, x, y, [current - snapped]);
+            }
+        }
+        dithered
+    }
+
+    fn mean_brightness(resized: &RgbImage, luminance: Luminance) -> u32 {
+        let pixel_count = (resized.width() * resized.height()).max(1) as u64;
+        (resized.pixels().map(|pixel| luminance.of(pixel) as u64).sum::<u64>() / pixel_count) as u32
+    }
+
+    fn cell(colour_mode: ColourMode, colour: [u8; 3], ch: char) -> StyledChar {
+        StyledChar { ch, foreground: colour_mode.quantize(colour), background: None }
+    }
+
+    fn background_cell(colour_mode: ColourMode, colour: [u8; 3]) -> StyledChar {
+        StyledChar { ch: Self::MOSAIC_CHAR, foreground: None, background: colour_mode.quantize(colour) }
+    }
+
+    fn edge_glyph(gx: f32, gy: f32) -> char {
+        let angle = gy.atan2(gx).to_degrees().rem_euclid(180.0);
+        match angle {
+            a if !(22.5..157.5).contains(&a) => '|',
+            a if a < 67.5 => '/',
+            a if a < 112.5 => if gy > 0.0 { '_' } else { '\u{2014}' },
+            _ => '\\',
+        }
+    }
+
+    // Brightness variance over each pixel's 3x3 neighbourhood, clipped at the image border.
+    fn local_variance(resized: &RgbImage, luminance: Luminance) -> Vec<u32> {
+        let (width, height) = resized.dimensions();
+        let brightness: Vec<i64> = resized.pixels().map(|pixel| luminance.of(pixel) as i64).collect();
+        let mut variance = Vec::with_capacity(brightness.len());
+        for y in 0..height {
+            for x in 0..width {
+                let (mut count, mut sum, mut sum_of_squares) = (0i64, 0i64, 0i64);
+                for ny in y.saturating_sub(1)..(y + 2).min(height) {
+                    for nx in x.saturating_sub(1)..(x + 2).min(width) {
+                        let value = brightness[(ny * width + nx) as usize];
+                        count += 1;
+                        sum += value;
+                        sum_of_squares += value * value;
+                    }
+                }
+                variance.push(((sum_of_squares * count - sum * sum) / (count * count)) as u32);
+            }
+        }
+        variance
+    }
+
+    fn apply_edge_glyphs(resized: &RgbImage, luminance: Luminance, glyph_map: &mut [char]) {
+        let (width, height) = resized.dimensions();
+        if width < 3 || height < 3 {
+            return;
+        }
+        let brightness = |x: u32, y: u32| luminance.of(resized.get_pixel(x, y)) as f32;
+        let mut gradients = vec![(0.0f32, 0.0f32); (width * height) as usize];
+        for y in 1..height - 1 {
+            for x in 1..width - 1 {
+                let gx = brightness(x + 1, y - 1) + 2.0 * brightness(x + 1, y) + brightness(x + 1, y + 1)
+                    - brightness(x - 1, y - 1) - 2.0 * brightness(x - 1, y) - brightness(x - 1, y + 1);
+                let gy = brightness(x - 1, y + 1) + 2.0 * brightness(x, y + 1) + brightness(x + 1, y + 1)
+                    - brightness(x - 1, y - 1) - 2.0 * brightness(x, y - 1) - brightness(x + 1, y - 1);
+                gradients[(y * width + x) as usize] = (gx, gy);
+            }
+        }
+        let magnitude = |x: u32, y: u32| {
+            let (gx, gy) = gradients[(y * width + x) as usize];
+            gx.hypot(gy)
         };
+        for y in 1..height - 1 {
+            for x in 1..width - 1 {
+                let (gx, gy) = gradients[(y * width + x) as usize];
+                let current = gx.hypot(gy);
+                if current < Self::EDGE_THRESHOLD {
+                    continue;
+                }
+                // Only the strongest pixel across the edge gets a glyph, keeping outlines one cell thick.
+                let (dx, dy) = (gx / current, gy / current);
+                let (ahead_x, ahead_y) = ((x as f32 + dx).round() as u32, (y as f32 + dy).round() as u32);
+                let (behind_x, behind_y) = ((x as f32 - dx).round() as u32, (y as f32 - dy).round() as u32);
+                if current >= magnitude(ahead_x, ahead_y) && current > magnitude(behind_x, behind_y) {
+                    glyph_map[(y * width + x) as usize] = Self::edge_glyph(gx, gy);
+                }
+            }
+        }
+    }
+
+    fn colorize_ascii(
+        resized: &RgbImage,
+        glyph_map: &[char],
+        colour_mode: ColourMode,
+        cancellation_requested: &AtomicBool,
+    ) -> Option<Vec<Vec<StyledChar>>> {
         let width = resized.width();
-        let height = resized.height();
-        let ascii_length_m1 = (Self::ASCII_CHARS.len() - 1) as u32;
-        let converted_image: Vec<Vec<String>> = (0..height)
+        (0..resized.height())
             .into_par_iter()
             .map(|y| {
-                let mut image_row = vec![String::with_capacity(32); width as usize];
+                if cancellation_requested.load(Ordering::Relaxed) {
+                    return None;
+                }
+                let image_row = (0..width)
+                    .map(|x| Self::cell(colour_mode, resized.get_pixel(x, y).0, glyph_map[(y * width + x) as usize]))
+                    .collect();
+                Some(image_row)
+            })
+            .collect()
+    }
+
+    fn colorize_braille(
+        resized: &RgbImage,
+        luminance: Luminance,
+        invert_shapes: bool,
+        colour_mode: ColourMode,
+        cancellation_requested: &AtomicBool,
+    ) -> Option<Vec<Vec<StyledChar>>> {
+        let columns = resized.width() / Self::BRAILLE_CELL_WIDTH;
+        let rows = resized.height() / Self::BRAILLE_CELL_HEIGHT;
+        let threshold = Self::mean_brightness(resized, luminance);
+        (0..rows)
+            .into_par_iter()
+            .map(|row| {
+                if cancellation_requested.load(Ordering::Relaxed) {
+                    return None;
+                }
+                let mut image_row = Vec::with_capacity(columns as usize);
+                for column in 0..columns {
+                    let mut dots = 0u32;
+                    let mut colour_sum = [0u32; 3];
+                    for (dy, dot_row) in Self::BRAILLE_DOTS.iter().enumerate() {
+                        for (dx, dot) in dot_row.iter().enumerate() {
+                            let pixel = resized.get_pixel(
+                                column * Self::BRAILLE_CELL_WIDTH + dx as u32,
+                                row * Self::BRAILLE_CELL_HEIGHT + dy as u32,
+                            );
+                            if (luminance.of(pixel) < threshold) != invert_shapes {
+                                dots |= *dot as u32;
+                            }
+                            for (sum, channel) in colour_sum.iter_mut().zip(pixel.0) {
+                                *sum += channel as u32;
+                            }
+                        }
+                    }
+                    let cell_pixels = Self::BRAILLE_CELL_WIDTH * Self::BRAILLE_CELL_HEIGHT;
+                    let colour = colour_sum.map(|sum| (sum / cell_pixels) as u8);
+                    let ch = char::from_u32(Self::BRAILLE_BASE + dots).expect("Braille patterns are valid chars");
+                    image_row.push(Self::cell(colour_mode, colour, ch));
+                }
+                Some(image_row)
+            })
+            .collect()
+    }
+
+    fn colorize_half_block(
+        resized: &RgbImage,
+        luminance: Luminance,
+        invert_shapes: bool,
+        colour_mode: ColourMode,
+        cancellation_requested: &AtomicBool,
+    ) -> Option<Vec<Vec<StyledChar>>> {
+        let width = resized.width();
+        let rows = resized.height() / Self::HALF_BLOCK_CELL_HEIGHT;
+        let threshold = Self::mean_brightness(resized, luminance);
+        (0..rows)
+            .into_par_iter()
+            .map(|row| {
+                if cancellation_requested.load(Ordering::Relaxed) {
+                    return None;
+                }
+                let mut image_row = Vec::with_capacity(width as usize);
                 for x in 0..width {
-                    let pixel = resized.get_pixel(x, y);
-                    let [r, g, b] = pixel.0;
-                    let brightness = (r as u32 + g as u32 + b as u32) / 3;
-                    let char_index = ((brightness * ascii_length_m1) + 127) / 255;
-                    write!(
-                        &mut image_row[x as usize],
-                        "\x1B[38;2;{};{};{}m{}\x1B[0m",
-                        r,
-                        g,
-                        b,
-                        Self::ASCII_CHARS[char_index as usize]
-                    )
-                    .expect("Writing to String should not fail");
-                }
-                image_row
+                    let top = resized.get_pixel(x, row * Self::HALF_BLOCK_CELL_HEIGHT);
+                    let bottom = resized.get_pixel(x, row * Self::HALF_BLOCK_CELL_HEIGHT + 1);
+                    if colour_mode == ColourMode::Monochrome {
+                        let ch = match ((luminance.of(top) < threshold) != invert_shapes, (luminance.of(bottom) < threshold) != invert_shapes) {
+                            (true, true) => Self::FULL_BLOCK,
+                            (true, false) => Self::UPPER_HALF_BLOCK,
+                            (false, true) => Self::LOWER_HALF_BLOCK,
+                            (false, false) => Self::MOSAIC_CHAR,
+                        };
+                        image_row.push(StyledChar::plain(ch));
+                        continue;
+                    }
+                    image_row.push(StyledChar {
+                        ch: Self::UPPER_HALF_BLOCK,
+                        foreground: colour_mode.quantize(top.0),
+                        background: colour_mode.quantize(bottom.0),
+                    });
+                }
+                Some(image_row)
+            })
+            .collect()
+    }
+
+    fn quadrant_pixels(resized: &RgbImage, column: u32, row: u32) -> [Rgb<u8>; 4] {
+        let (x, y) = (column * Self::QUADRANT_CELL_SIZE, row * Self::QUADRANT_CELL_SIZE);
+        [(x, y), (x + 1, y), (x, y + 1), (x + 1, y + 1)].map(|(x, y)| *resized.get_pixel(x, y))
+    }
+
+    fn mean_colour(pixels: impl Iterator<Item = Rgb<u8>>) -> [u8; 3] {
+        let (mut sum, mut count) = ([0u32; 3], 0u32);
+        for pixel in pixels {
+            for (total, channel) in sum.iter_mut().zip(pixel.0) {
+                *total += channel as u32;
+            }
+            count += 1;
+        }
+        sum.map(|total| (total / count.max(1)) as u8)
+    }
+
+    // Every split of the four pixels into foreground and background is tried, keeping the one with the least error.
+    fn best_quadrant_split(pixels: &[Rgb<u8>; 4]) -> (usize, [u8; 3], [u8; 3]) {
+        let in_mask = |mask: usize, index: usize| mask & (1 << index) != 0;
+        (1..Self::QUADRANT_CHARS.len())
+            .map(|mask| {
+                let foreground = Self::mean_colour(pixels.iter().enumerate().filter(|(index, _)| in_mask(mask, *index)).map(|(_, pixel)| *pixel));
+                let background = Self::mean_colour(pixels.iter().enumerate().filter(|(index, _)| !in_mask(mask, *index)).map(|(_, pixel)| *pixel));
+                let error: u32 = pixels
+                    .iter()
+                    .enumerate()
+                    .map(|(index, pixel)| {
+                        let target = if in_mask(mask, index) { foreground } else { background };
+                        pixel.0.iter().zip(target).map(|(a, b)| (a.abs_diff(b) as u32).pow(2)).sum::<u32>()
+                    })
+                    .sum();
+                (error, mask, foreground, background)
+            })
+            .min_by_key(|(error, ..)| *error)
+            .map(|(_, mask, foreground, background)| (mask, foreground, background))
+            .expect("There are quadrant masks")
+    }
+
+    fn colorize_quadrant(
+        resized: &RgbImage,
+        luminance: Luminance,
+        invert_shapes: bool,
+        colour_mode: ColourMode,
+        cancellation_requested: &AtomicBool,
+    ) -> Option<Vec<Vec<StyledChar>>> {
+        let columns = resized.width() / Self::QUADRANT_CELL_SIZE;
+        let rows = resized.height() / Self::QUADRANT_CELL_SIZE;
+        let threshold = Self::mean_brightness(resized, luminance);
+        (0..rows)
+            .into_par_iter()
+            .map(|row| {
+                if cancellation_requested.load(Ordering::Relaxed) {
+                    return None;
+                }
+                let mut image_row = Vec::with_capacity(columns as usize);
+                for column in 0..columns {
+                    let pixels = Self::quadrant_pixels(resized, column, row);
+                    if colour_mode == ColourMode::Monochrome {
+                        let mask = pixels
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, pixel)| (luminance.of(pixel) < threshold) != invert_shapes)
+                            .fold(0, |mask, (index, _)| mask | 1 << index);
+                        image_row.push(StyledChar::plain(Self::QUADRANT_CHARS[mask]));
+                        continue;
+                    }
+                    let (mask, foreground, background) = Self::best_quadrant_split(&pixels);
+                    let background = (mask != Self::QUADRANT_CHARS.len() - 1).then(|| colour_mode.quantize(background)).flatten();
+                    image_row.push(StyledChar {
+                        ch: Self::QUADRANT_CHARS[mask],
+                        foreground: colour_mode.quantize(foreground),
+                        background,
+                    });
+                }
+                Some(image_row)
+            })
+            .collect()
+    }
+
+    fn colorize_mosaic(
+        resized: &RgbImage,
+        colour_mode: ColourMode,
+        cancellation_requested: &AtomicBool,
+    ) -> Option<Vec<Vec<StyledChar>>> {
+        let width = resized.width();
+        (0..resized.height())
+            .into_par_iter()
+            .map(|y| {
+                if cancellation_requested.load(Ordering::Relaxed) {
+                    return None;
+                }
+                let image_row = (0..width)
+                    .map(|x| Self::background_cell(colour_mode, resized.get_pixel(x, y).0))
+                    .collect();
+                Some(image_row)
+            })
+            .collect()
+    }
+
+    fn nearest_emoji_square(colour: [u8; 3]) -> char {
+        let distance = |square: &[u8; 3]| -> u32 {
+            square.iter().zip(colour).map(|(a, b)| (a.abs_diff(b) as u32).pow(2)).sum()
+        };
+        Self::EMOJI_SQUARES
+            .iter()
+            .min_by_key(|(_, square)| distance(square))
+            .map(|(ch, _)| *ch)
+            .expect("There are emoji squares")
+    }
+
+    fn colorize_emoji(resized: &RgbImage, cancellation_requested: &AtomicBool) -> Option<Vec<Vec<StyledChar>>> {
+        let width = resized.width();
+        (0..resized.height())
+            .into_par_iter()
+            .map(|y| {
+                if cancellation_requested.load(Ordering::Relaxed) {
+                    return None;
+                }
+                let image_row = (0..width)
+                    .map(|x| StyledChar::plain(Self::nearest_emoji_square(resized.get_pixel(x, y).0)))
+                    .collect();
+                Some(image_row)
             })
+            .collect()
+    }
+
+    // Wide cells need wide glyphs, so ASCII is swapped for its fullwidth forms.
+    fn caption_char(ch: char, cell_width: usize) -> char {
+        match ch {
+            ' ' if cell_width > 1 => Self::IDEOGRAPHIC_SPACE,
+            '!'..='~' if cell_width > 1 => char::from_u32(ch as u32 + Self::FULLWIDTH_OFFSET).unwrap_or(ch),
+            _ => ch,
+        }
+    }
+
+    // The caption sits centred on the bottom row, over the average colour of the cells it covers.
+    fn burn_caption(image_array: &mut [Vec<StyledChar>], caption: &str, colour_mode: ColourMode) {
+        let Some(row) = image_array.last_mut() else {
+            return;
+        };
+        let cell_width = row.first().map_or(1, StyledChar::width);
+        let text: Vec<char> = format!(" {} ", caption.trim())
+            .chars()
+            .filter(|ch| !ch.is_control())
+            .map(|ch| Self::caption_char(ch, cell_width))
+            .take(row.len())
             .collect();
-        Ok(PrinterImageData::new(image_name, converted_image))
+        let start = (row.len() - text.len()) / 2;
+        let covered = &row[start..start + text.len()];
+        let colours: Vec<(u8, u8, u8)> = covered
+            .iter()
+            .filter_map(|cell| cell.background.or(cell.foreground).map(|colour| colour.rgb()))
+            .collect();
+        let count = colours.len().max(1) as u32;
+        let (r, g, b) = colours.iter().fold((0u32, 0u32, 0u32), |(r, g, b), colour| {
+            (r + colour.0 as u32, g + colour.1 as u32, b + colour.2 as u32)
+        });
+        let average = [(r / count) as u8, (g / count) as u8, (b / count) as u8];
+        let contrast = if Luminance::default().of(&Rgb(average)) < 128 { [255, 255, 255] } else { [0, 0, 0] };
+        for (cell, ch) in row[start..].iter_mut().zip(text) {
+            *cell = StyledChar { ch, foreground: colour_mode.quantize(contrast), background: colour_mode.quantize(average) };
+        }
+    }
+
+    fn decode_image(image_bytes: &[u8], size_limits: &SizeLimits) -> Result<DynamicImage, ConverterError> {
+        let reader = ImageReader::new(Cursor::new(image_bytes))
+            .with_guessed_format()
+            .map_err(|e| ConverterError::ImageLoadingError { format: None, cause: e.to_string() })?;
+        let format = reader.format();
+        let loading_error = |e: ImageError| ConverterError::ImageLoadingError { format, cause: e.to_string() };
+        let mut decoder = reader.into_decoder().map_err(loading_error)?;
+        size_limits.check_decoded_pixels(decoder.dimensions())?;
+        // A broken EXIF block should not cost the whole picture, it is just shown unrotated.
+        let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+        let mut img = DynamicImage::from_decoder(decoder).map_err(loading_error)?;
+        img.apply_orientation(orientation);
+        Ok(img)
+    }
+
+    // pixel_aspect_ratio is the width to height ratio of a single sampled pixel once printed.
+    fn scaled_height(original_height: u32, width: u32, original_width: u32, pixel_aspect_ratio: f32) -> u32 {
+        let height = original_height as f64 * width as f64 / original_width.max(1) as f64;
+        (height * pixel_aspect_ratio as f64).round().min(u32::MAX as f64) as u32
+    }
+
+    fn target_dimensions(
+        render_style: RenderStyle,
+        image_width: u32,
+        cell_aspect_ratio: f32,
+        (original_width, original_height): (u32, u32),
+    ) -> ((u32, u32), (u32, u32)) {
+        match render_style {
+            RenderStyle::Ascii | RenderStyle::Mosaic | RenderStyle::EdgeAware | RenderStyle::Sixel | RenderStyle::Kitty | RenderStyle::ITerm2 => {
+                let height = Self::scaled_height(original_height, image_width, original_width, cell_aspect_ratio).max(1);
+                ((image_width, height), (image_width, height))
+            }
+            RenderStyle::Braille => {
+                let width = image_width.saturating_mul(Self::BRAILLE_CELL_WIDTH);
+                let pixel_aspect_ratio = cell_aspect_ratio * Self::BRAILLE_CELL_HEIGHT as f32 / Self::BRAILLE_CELL_WIDTH as f32;
+                let height = Self::scaled_height(original_height, width, original_width, pixel_aspect_ratio);
+                let rows = height.div_ceil(Self::BRAILLE_CELL_HEIGHT).max(1);
+                ((width, rows.saturating_mul(Self::BRAILLE_CELL_HEIGHT)), (image_width, rows))
+            }
+            RenderStyle::Emoji => {
+                // Each emoji spans two columns, which makes it roughly square.
+                let columns = (image_width / Self::EMOJI_CELL_WIDTH).max(1);
+                let pixel_aspect_ratio = cell_aspect_ratio * Self::EMOJI_CELL_WIDTH as f32;
+                let height = Self::scaled_height(original_height, columns, original_width, pixel_aspect_ratio).max(1);
+                ((columns, height), (columns, height))
+            }
+            RenderStyle::Quadrant => {
+                // Square cells of pixels keep the calibrated ratio for each pixel.
+                let width = image_width.saturating_mul(Self::QUADRANT_CELL_SIZE);
+                let height = Self::scaled_height(original_height, width, original_width, cell_aspect_ratio);
+                let rows = height.div_ceil(Self::QUADRANT_CELL_SIZE).max(1);
+                ((width, rows.saturating_mul(Self::QUADRANT_CELL_SIZE)), (image_width, rows))
+            }
+            RenderStyle::HalfBlock => {
+                let pixel_aspect_ratio = cell_aspect_ratio * Self::HALF_BLOCK_CELL_HEIGHT as f32;
+                let height = Self::scaled_height(original_height, image_width, original_width, pixel_aspect_ratio);
+                let rows = height.div_ceil(Self::HALF_BLOCK_CELL_HEIGHT).max(1);
+                ((image_width, rows.saturating_mul(Self::HALF_BLOCK_CELL_HEIGHT)), (image_width, rows))
+            }
+        }
+    }
+
+    fn cell_pixel_size() -> (u32, u32) {
+        match terminal::window_size() {
+            Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => {
+                ((size.width / size.columns) as u32, (size.height / size.rows) as u32)
+            }
+            _ => Self::FALLBACK_CELL_PIXEL_SIZE,
+        }
+    }
+
+    // Pixel graphics are laid out by the real cell size in pixels rather than the calibrated ratio.
+    fn effective_cell_aspect_ratio(render_style: RenderStyle, cell_aspect_ratio: f32) -> f32 {
+        if render_style.is_graphic() {
+            let (cell_width, cell_height) = Self::cell_pixel_size();
+            return cell_width as f32 / cell_height as f32;
+        }
+        cell_aspect_ratio
+    }
+
+    fn fit_width(render_style: RenderStyle, cell_aspect_ratio: f32, columns: u32, available_rows: u32, dimensions: (u32, u32)) -> u32 {
+        let (_, (_, rows)) = Self::target_dimensions(render_style, columns, cell_aspect_ratio, dimensions);
+        if rows <= available_rows {
+            return columns;
+        }
+        (columns as u64 * available_rows as u64 / rows as u64).max(1) as u32
+    }
+
+    // The darkest and brightest half percent are clipped so a few stray pixels do not pin the range.
+    fn brightness_levels(resized: &RgbImage, luminance: Luminance) -> Option<(u32, u32)> {
+        let mut histogram = [0u32; 256];
+        for pixel in resized.pixels() {
+            histogram[luminance.of(pixel).min(255) as usize] += 1;
+        }
+        let clipped = (resized.pixels().len() as f32 * Self::LEVELS_CLIP_FRACTION) as u32;
+        let low = Self::clipped_level(&histogram, clipped, 0..histogram.len())?;
+        let high = Self::clipped_level(&histogram, clipped, (0..histogram.len()).rev())?;
+        (high > low && (low, high) != (0, 255)).then_some((low, high))
+    }
+
+    fn clipped_level(histogram: &[u32], clipped: u32, mut levels: impl Iterator<Item = usize>) -> Option<u32> {
+        let mut seen = 0;
+        levels
+            .find(|level| {
+                seen += histogram[*level];
+                seen > clipped
+            })
+            .map(|level| level as u32)
+    }
+
+    fn stretch_levels(image: &mut RgbImage, (low, high): (u32, u32)) {
+        let scale = 255.0 / (high - low) as f32;
+        for pixel in image.pixels_mut() {
+            pixel.0 = pixel.0.map(|channel| ((channel as f32 - low as f32) * scale).round().clamp(0.0, 255.0) as u8);
+        }
+    }
+
+    // The backdrop is drawn at the source resolution, so the checkerboard scales with the image.
+    fn flatten_alpha(img: &DynamicImage, alpha_mode: AlphaMode, background: Colour) -> DynamicImage {
+        let rgba = img.to_rgba8();
+        let (width, height) = rgba.dimensions();
+        let square = (width.max(height) / Self::CHECKER_DIVISIONS).max(1);
+        let (r, g, b) = background.rgb();
+        let mut flattened = RgbImage::new(width, height);
+        for (x, y, pixel) in rgba.enumerate_pixels() {
+            let backdrop = match alpha_mode {
+                AlphaMode::Checkerboard if (x / square + y / square) % 2 == 0 => Self::CHECKER_LIGHT,
+                AlphaMode::Checkerboard => Self::CHECKER_DARK,
+                AlphaMode::Background | AlphaMode::Transparent => [r, g, b],
+            };
+            let alpha = pixel.0[3] as u32;
+            let blend = |index: usize| ((pixel.0[index] as u32 * alpha + backdrop[index] as u32 * (255 - alpha) + 127) / 255) as u8;
+            flattened.put_pixel(x, y, Rgb([blend(0), blend(1), blend(2)]));
+        }
+        DynamicImage::ImageRgb8(flattened)
+    }
+
+    // Cells made only of fully transparent pixels become plain spaces showing the terminal background.
+    fn clear_transparent_cells(image_array: &mut [Vec<StyledChar>], transparent: &[bool], render_style: RenderStyle, pixel_width: u32) {
+        let (cell_width, cell_height) = match render_style {
+            RenderStyle::Braille => (Self::BRAILLE_CELL_WIDTH, Self::BRAILLE_CELL_HEIGHT),
+            RenderStyle::HalfBlock => (1, Self::HALF_BLOCK_CELL_HEIGHT),
+            RenderStyle::Quadrant => (Self::QUADRANT_CELL_SIZE, Self::QUADRANT_CELL_SIZE),
+            _ => (1, 1),
+        };
+        let is_transparent = |x: u32, y: u32| transparent.get((y * pixel_width + x) as usize).copied().unwrap_or(false);
+        for (row, image_row) in image_array.iter_mut().enumerate() {
+            for (column, cell) in image_row.iter_mut().enumerate() {
+                let (x, y) = (column as u32 * cell_width, row as u32 * cell_height);
+                let transparent_pixels: Vec<bool> = (0..cell_height)
+                    .flat_map(|dy| (0..cell_width).map(move |dx| (x + dx, y + dy)))
+                    .map(|(x, y)| is_transparent(x, y))
+                    .collect();
+                if transparent_pixels.iter().all(|transparent| *transparent) {
+                    *cell = StyledChar::plain(Self::MOSAIC_CHAR);
+                } else if render_style == RenderStyle::HalfBlock && cell.background.is_some() {
+                    // A half block keeps only its opaque half, drawn in the foreground colour.
+                    match transparent_pixels.as_slice() {
+                        [true, false] => *cell = StyledChar { ch: Self::LOWER_HALF_BLOCK, foreground: cell.background, background: None },
+                        [false, true] => *cell = StyledChar { ch: Self::UPPER_HALF_BLOCK, foreground: cell.foreground, background: None },
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+
+    fn boost_saturation(image: &RgbImage, factor: f32) -> RgbImage {
+        let mut boosted = image.clone();
+        for pixel in boosted.pixels_mut() {
+            let max = *pixel.0.iter().max().unwrap() as f32;
+            let min = *pixel.0.iter().min().unwrap() as f32;
+            if max == min {
+                continue;
+            }
+            // Scaling every channel's distance from the maximum keeps hue and value, changing only HSV saturation.
+            let saturation = (max - min) / max;
+            let scale = (saturation * factor).min(1.0) / saturation;
+            for channel in pixel.0.iter_mut() {
+                *channel = (max - (max - *channel as f32) * scale).round().clamp(0.0, 255.0) as u8;
+            }
+        }
+        boosted
+    }
+
+    fn convert_image(
+        width_spec: WidthSpec,
+        options: &ConverterOptions,
+        image_name: &str,
+        image_bytes: Bytes,
+        mut timings: ImageTimings,
+        cancellation_requested: &AtomicBool,
+    ) -> Result<ConvertedImage, ConverterError> {
+        let image_width = width_spec.resolve();
+        options.size_limits.check_width(image_width)?;
+        let img = timings.measure(TimingStage::Decode, || Self::decode_image(&image_bytes, &options.size_limits))?;
+        let img = Self::prepare_frame(img, options);
+        let render_style = options.render_style.resolve();
+        // In double width mode every pixel is sampled as if it were one cell twice as wide.
+        let cells_per_pixel = if options.double_width && render_style.has_pixel_cells() { Self::DOUBLE_WIDTH_CELLS } else { 1 };
+        let cell_aspect_ratio = Self::effective_cell_aspect_ratio(render_style, options.cell_aspect_ratio) * cells_per_pixel as f32;
+        let image_width = (image_width / cells_per_pixel).max(1);
+        let image_width = match width_spec {
+            WidthSpec::FitTerminal => Self::fit_width(render_style, cell_aspect_ratio, image_width, WidthSpec::available_rows(), img.dimensions()),
+            _ => image_width,
+        };
+        let ((width, height), (columns, rows)) = Self::target_dimensions(render_style, image_width, cell_aspect_ratio, img.dimensions());
+        let columns = columns * cells_per_pixel;
+        options.size_limits.check_cells(columns, rows)?;
+        let layout = FrameLayout {
+            render_style,
+            colour_mode: options.colour_mode.resolve(),
+            pixel_size: (width, height),
+            cell_size: (columns, rows),
+            cells_per_pixel,
+        };
+        let caption = options.caption.text(image_name);
+        let mut converted = Self::convert_frame(&img, &layout, options, &mut timings, cancellation_requested)?;
+        if let Some(caption) = caption {
+            Self::burn_caption(&mut converted.image_array, caption, layout.colour_mode);
+        }
+        let frames = if !render_style.is_graphic() && image::guess_format(&image_bytes).is_ok_and(|format| format == ImageFormat::Gif) {
+            let animation = timings.measure(TimingStage::Decode, || Self::decode_animation(&image_bytes, &options.size_limits, columns as u64 * rows as u64));
+            let mut frames = Vec::with_capacity(animation.len());
+            for (index, (frame, delay)) in animation.into_iter().enumerate() {
+                let image_array = match index {
+                    0 => converted.image_array.clone(),
+                    _ => {
+                        let mut image_array = Self::convert_frame(&Self::prepare_frame(frame, options), &layout, options, &mut timings, cancellation_requested)?.image_array;
+                        if let Some(caption) = caption {
+                            Self::burn_caption(&mut image_array, caption, layout.colour_mode);
+                        }
+                        image_array
+                    }
+                };
+                frames.push(AnimationFrame { image_array, delay });
+            }
+            frames
+        } else {
+            Vec::new()
+        };
+        Ok(ConvertedImage {
+            image_array: converted.image_array,
+            graphic: converted.graphic,
+            frames,
+            timings,
+            source: image_bytes,
+        })
+    }
+
+    // The crop is given on the picture as seen, so it follows the rotation and flips.
+    fn prepare_frame(img: DynamicImage, options: &ConverterOptions) -> DynamicImage {
+        let img = options.transform.apply(img);
+        match options.crop {
+            Some(crop) => crop.apply(&img),
+            None => img,
+        }
+    }
+
+    // Frames are composited onto the full canvas and stop early once the pixel or cell budget is spent.
+    fn decode_animation(image_bytes: &[u8], size_limits: &SizeLimits, cells_per_frame: u64) -> Vec<(DynamicImage, Duration)> {
+        let decoder = match GifDecoder::new(Cursor::new(image_bytes)) {
+            Ok(decoder) => decoder,
+            Err(_) => return Vec::new(),
+        };
+        let (width, height) = decoder.dimensions();
+        let max_frames = (size_limits.max_decoded_pixels / (width as u64 * height as u64).max(1))
+            .min(size_limits.max_cells / cells_per_frame.max(1))
+            .max(1) as usize;
+        decoder
+            .into_frames()
+            .take(max_frames)
+            .map_while(Result::ok)
+            .map(|frame| {
+                // Like browsers, near-zero delays fall back to a sensible default.
+                let delay = match Duration::from(frame.delay()) {
+                    delay if delay < Self::MIN_FRAME_DELAY => Self::DEFAULT_FRAME_DELAY,
+                    delay => delay,
+                };
+                (DynamicImage::ImageRgba8(frame.into_buffer()), delay)
+            })
+            .collect()
+    }
+
+    fn convert_frame(
+        img: &DynamicImage,
+        layout: &FrameLayout,
+        options: &ConverterOptions,
+        timings: &mut ImageTimings,
+        cancellation_requested: &AtomicBool,
+    ) -> Result<ConvertedFrame, ConverterError> {
+        let FrameLayout { render_style, colour_mode, pixel_size: (width, height), cell_size: (columns, rows), cells_per_pixel } = *layout;
+        if cancellation_requested.load(Ordering::Relaxed) {
+            return Err(ConverterError::CancelledError);
+        }
+        let has_alpha = img.color().has_alpha();
+        let (resized, graphic_image, transparent) = timings.measure(TimingStage::Resize, || {
+            let flattened = has_alpha.then(|| Self::flatten_alpha(img, options.alpha_mode, options.alpha_background));
+            let source = flattened.as_ref().unwrap_or(img);
+            let resized = source.resize_exact(width, height, image::imageops::FilterType::CatmullRom)
+                .to_rgb8();
+            let graphic_image = render_style.is_graphic().then(|| {
+                let (cell_width, cell_height) = Self::cell_pixel_size();
+                source.resize_exact(columns.saturating_mul(cell_width), rows.saturating_mul(cell_height), image::imageops::FilterType::CatmullRom)
+                    .to_rgb8()
+            });
+            let transparent: Option<Vec<bool>> = (has_alpha && options.alpha_mode == AlphaMode::Transparent).then(|| {
+                img.resize_exact(width, height, image::imageops::FilterType::CatmullRom)
+                    .to_rgba8()
+                    .pixels()
+                    .map(|pixel| pixel.0[3] == 0)
+                    .collect()
+            });
+            (resized, graphic_image, transparent)
+        });
+        if cancellation_requested.load(Ordering::Relaxed) {
+            return Err(ConverterError::CancelledError);
+        }
+        let converted_image = timings.measure(TimingStage::Colorize, || {
+            // Effects edit the picture itself, so the glyphs follow the filtered colours.
+            let resized = options.effects.apply(resized);
+            let mut graphic_image = graphic_image.map(|graphic_image| options.effects.apply(graphic_image));
+            let mut resized = resized;
+            if let Some(levels) = options.auto_levels.then(|| Self::brightness_levels(&resized, options.luminance)).flatten() {
+                Self::stretch_levels(&mut resized, levels);
+                if let Some(graphic_image) = graphic_image.as_mut() {
+                    Self::stretch_levels(graphic_image, levels);
+                }
+            }
+            if let Some(duotone) = options.duotone {
+                resized = duotone.apply(resized, options.luminance);
+                graphic_image = graphic_image.map(|graphic_image| duotone.apply(graphic_image, options.luminance));
+            }
+            let brightness_map: Vec<u32> = if options.dithering != Dithering::Off {
+                Self::dither_brightness(&resized, options.dithering, &options.character_ramp, options.luminance)
+            } else {
+                resized.pixels().map(|pixel| options.luminance.of(pixel)).collect()
+            };
+            let flat_regions: Option<Vec<bool>> = options.detail_threshold.map(|threshold| {
+                Self::local_variance(&resized, options.luminance).into_iter().map(|variance| variance < threshold).collect()
+            });
+            let mut glyph_map: Vec<char> = brightness_map
+                .iter()
+                .map(|brightness| if options.inversion.inverts_characters() { 255 - *brightness } else { *brightness })
+                .enumerate()
+                .map(|(index, brightness)| match &flat_regions {
+                    Some(flat_regions) if flat_regions[index] => options.character_ramp.sparse_char_for_brightness(brightness),
+                    _ => options.character_ramp.char_for_brightness(brightness),
+                })
+                .collect();
+            if render_style == RenderStyle::EdgeAware {
+                Self::apply_edge_glyphs(&resized, options.luminance, &mut glyph_map);
+            }
+            let invert_colours = options.inversion.inverts_colours() && colour_mode != ColourMode::Monochrome;
+            let resized = if invert_colours {
+                let mut inverted = resized;
+                image::imageops::invert(&mut inverted);
+                inverted
+            } else {
+                resized
+            };
+            // Shapes are taken from the colour image, so inverted colours already flip them once.
+            let invert_shapes = options.inversion.inverts_characters() != invert_colours;
+            let resized = if options.saturation != ConverterOptions::DEFAULT_SATURATION && colour_mode != ColourMode::Monochrome {
+                Self::boost_saturation(&resized, options.saturation)
+            } else {
+                resized
+            };
+            // A theme palette is coarser than any colour mode, so it takes over the dithering.
+            let resized = match (options.palette.is_active(), options.dithering) {
+                (true, Dithering::Off) => options.palette.apply(resized),
+                (true, dithering) => Self::dither_colours(&resized, dithering, |colour| options.palette.nearest(colour)),
+                (false, Dithering::Off) => resized,
+                (false, dithering) if colour_mode.is_reduced_palette() => Self::dither_colours(&resized, dithering, |colour| colour_mode.snap(colour)),
+                _ => resized,
+            };
+            // Graphic styles keep a cell version of the image for saving and copying.
+            let converted_image = match (render_style, colour_mode) {
+                (RenderStyle::Ascii | RenderStyle::EdgeAware | RenderStyle::Kitty | RenderStyle::ITerm2, _) | (RenderStyle::Mosaic | RenderStyle::Sixel, ColourMode::Monochrome) => {
+                    Self::colorize_ascii(&resized, &glyph_map, colour_mode, cancellation_requested)
+                }
+                (RenderStyle::Braille, _) => Self::colorize_braille(&resized, options.luminance, invert_shapes, colour_mode, cancellation_requested),
+                (RenderStyle::HalfBlock, _) => Self::colorize_half_block(&resized, options.luminance, invert_shapes, colour_mode, cancellation_requested),
+                (RenderStyle::Quadrant, _) => Self::colorize_quadrant(&resized, options.luminance, invert_shapes, colour_mode, cancellation_requested),
+                (RenderStyle::Mosaic | RenderStyle::Sixel, _) => Self::colorize_mosaic(&resized, colour_mode, cancellation_requested),
+                (RenderStyle::Emoji, _) => Self::colorize_emoji(&resized, cancellation_requested),
+            };
+            let graphic = graphic_image.map(|graphic_image| options.palette.apply(graphic_image)).and_then(|graphic_image| match render_style {
+                RenderStyle::Sixel => Sixel::encode(&graphic_image, columns, rows).ok(),
+                RenderStyle::Kitty => Kitty::encode(&graphic_image, columns, rows).ok(),
+                RenderStyle::ITerm2 => ITerm2::encode(&graphic_image, columns, rows).ok(),
+                _ => None,
+            });
+            converted_image.map(|converted_image| (converted_image, graphic))
+        });
+        let (mut image_array, graphic) = converted_image.ok_or(ConverterError::CancelledError)?;
+        if let Some(transparent) = transparent {
+            Self::clear_transparent_cells(&mut image_array, &transparent, render_style, width);
+        }
+        if cells_per_pixel > 1 {
+            for row in image_array.iter_mut() {
+                *row = row.iter().flat_map(|cell| std::iter::repeat_n(*cell, cells_per_pixel as usize)).collect();
+            }
+        }
+        Ok(ConvertedFrame { image_array, graphic })
+    }
+}
+
+impl<S: ImageSource> Drop for Converter<S> {
+    fn drop(&mut self) {
+        if let Some(pending) = self.prefetched.take() {
+            pending.cancellation_requested.store(true, Ordering::Relaxed);
+        }
     }
 }
 
-impl Iterator for Converter {
+impl<S: ImageSource> Iterator for Converter<S> {
     type Item = PrinterImageData;
 
+    // Only waits on this image; the following one is fetched by `prefetch` once its download is in.
     fn next(&mut self) -> Option<Self::Item> {
+        self.cancellation_requested.store(false, Ordering::Relaxed);
         loop {
-            match self.image_iterator.next() {
-                Some(image_data_result) => {
-                    let (image_name, image_bytes) = image_data_result;
-                    match Self::convert_image(self.image_width, image_name.clone(), image_bytes) {
-                        Ok(printer_image_data) => return Some(printer_image_data),
-                        Err(e) => {
-                            Logger::log_error(format!(
-                                "Failed to convert image '{}': {}",
-                                image_name, e
-                            ).as_str());
-                        }
-                    }
+            let pending = match self.prefetched.take() {
+                Some(pending) => pending,
+                None => self.fetch_and_start()?,
+            };
+            let image_name = pending.image_name.clone();
+            match self.finish_conversion(pending) {
+                Ok(printer_image_data) => return Some(printer_image_data),
+                Err(ConverterError::CancelledError) => return None,
+                Err(e) => {
+                    Logger::log_error(&fill(messages().conversion_failed, &[&image_name, &e]));
                 }
-                None => return None,
             }
         }
-
     }
 }
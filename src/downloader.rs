@@ -1,82 +1,339 @@
+use crate::image_source::ImageSource;
 use crate::logger::Logger;
+use crate::messages::{fill, messages};
 use headless_chrome::{Browser, LaunchOptionsBuilder};
-use reqwest::blocking;
+use reqwest::header::{CONTENT_RANGE, RANGE};
+use reqwest::{blocking, StatusCode};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::io::Read;
 use std::rc::Rc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
 
 #[derive(Debug)]
 pub enum DownloaderError {
-    ConnectionError,
-    NoResultsError,
-    BrowserError,
-    SearcherError,
+    BrowserError(String),
+    ConnectionError { url: String, cause: String },
+    SearcherError { url: String, cause: String },
+    SelectorError { url: String, selector: String, cause: String },
+    NoResultsError { keyword: String },
+    RequestError { url: String, cause: reqwest::Error },
+    StatusError { url: String, status: StatusCode },
+    BodyError { url: String, cause: io::Error },
 }
 
 impl fmt::Display for DownloaderError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            DownloaderError::ConnectionError => write!(f, "Failed to connect to the internet"),
-            DownloaderError::NoResultsError => write!(f, "No results found for the given keyword"),
-            DownloaderError::BrowserError => write!(f, "Failed to initialize browser"),
-            DownloaderError::SearcherError => write!(f, "Failed to search for given keyword"),
+            DownloaderError::BrowserError(cause) => write!(f, "{}", fill(messages().browser_error, &[cause])),
+            DownloaderError::ConnectionError { url, cause } => write!(f, "{}", fill(messages().connection_error, &[url, cause])),
+            DownloaderError::SearcherError { url, cause } => write!(f, "{}", fill(messages().searcher_error, &[url, cause])),
+            DownloaderError::SelectorError { url, selector, cause } => write!(f, "{}", fill(messages().selector_error, &[selector, url, cause])),
+            DownloaderError::NoResultsError { keyword } => write!(f, "{}", fill(messages().no_results, &[keyword])),
+            DownloaderError::RequestError { url, cause } => write!(f, "{}", fill(messages().request_error, &[url, cause])),
+            DownloaderError::StatusError { url, status } => write!(f, "{}", fill(messages().status_error, &[url, status])),
+            DownloaderError::BodyError { url, cause } => write!(f, "{}", fill(messages().body_error, &[url, cause])),
         }
     }
 }
 
-impl std::error::Error for DownloaderError {}
+impl std::error::Error for DownloaderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DownloaderError::RequestError { cause, .. } => Some(cause),
+            DownloaderError::BodyError { cause, .. } => Some(cause),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HostMatch {
+    Permitted,
+    Denied,
+    NotAllowed,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HostFilterStatistics {
+    permitted: usize,
+    denied: usize,
+    not_allowed: usize,
+}
+
+impl HostFilterStatistics {
+    fn record(&mut self, host_match: HostMatch) {
+        match host_match {
+            HostMatch::Permitted => self.permitted += 1,
+            HostMatch::Denied => self.denied += 1,
+            HostMatch::NotAllowed => self.not_allowed += 1,
+        }
+    }
+}
+
+impl fmt::Display for HostFilterStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", fill(messages().host_statistics, &[&self.permitted, &self.denied, &self.not_allowed]))
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct HostFilter {
+    allowed_hosts: Vec<String>,
+    denied_hosts: Vec<String>,
+}
+
+impl HostFilter {
+    pub fn set_allowed_hosts(&mut self, hosts: &str) {
+        self.allowed_hosts = Self::parse_hosts(hosts);
+    }
+
+    pub fn set_denied_hosts(&mut self, hosts: &str) {
+        self.denied_hosts = Self::parse_hosts(hosts);
+    }
+
+    pub fn allowed_hosts(&self) -> &[String] {
+        &self.allowed_hosts
+    }
+
+    pub fn denied_hosts(&self) -> &[String] {
+        &self.denied_hosts
+    }
+
+    fn parse_hosts(hosts: &str) -> Vec<String> {
+        hosts
+            .split(',')
+            .map(|host| host.trim().to_lowercase())
+            .filter(|host| !host.is_empty())
+            .collect()
+    }
+
+    fn matches(host: &str, pattern: &str) -> bool {
+        host == pattern || host.ends_with(format!(".{}", pattern).as_str())
+    }
+
+    pub fn check(&self, url: &str) -> HostMatch {
+        let host = match reqwest::Url::parse(url) {
+            Ok(parsed_url) => match parsed_url.host_str() {
+                Some(host) => host.to_lowercase(),
+                None => return HostMatch::NotAllowed,
+            },
+            Err(_) => return HostMatch::NotAllowed,
+        };
+        if self.denied_hosts.iter().any(|pattern| Self::matches(&host, pattern)) {
+            return HostMatch::Denied;
+        }
+        if self.allowed_hosts.is_empty()
+            || self.allowed_hosts.iter().any(|pattern| Self::matches(&host, pattern))
+        {
+            HostMatch::Permitted
+        } else {
+            HostMatch::NotAllowed
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct SearchOptions {
+    search_url_template: String,
+    image_selector: String,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            search_url_template: Self::DEFAULT_SEARCH_URL_TEMPLATE.to_string(),
+            image_selector: Self::DEFAULT_IMAGE_SELECTOR.to_string(),
+        }
+    }
+}
+
+impl SearchOptions {
+    const KEYWORD_PLACEHOLDER: &'static str = "{keyword}";
+    const DEFAULT_SEARCH_URL_TEMPLATE: &'static str = "https://www.bing.com/images/search?q={keyword}";
+    const DEFAULT_IMAGE_SELECTOR: &'static str = "img.mimg";
+
+    pub fn set_search_url_template(&mut self, template: &str) {
+        self.search_url_template = match template.trim() {
+            "" => Self::DEFAULT_SEARCH_URL_TEMPLATE.to_string(),
+            template => template.to_string(),
+        };
+    }
+
+    pub fn set_image_selector(&mut self, selector: &str) {
+        self.image_selector = match selector.trim() {
+            "" => Self::DEFAULT_IMAGE_SELECTOR.to_string(),
+            selector => selector.to_string(),
+        };
+    }
+
+    pub fn search_url_template(&self) -> &str {
+        &self.search_url_template
+    }
+
+    pub fn image_selector(&self) -> &str {
+        &self.image_selector
+    }
+
+    fn search_url(&self, keyword: &str) -> String {
+        if self.search_url_template.contains(Self::KEYWORD_PLACEHOLDER) {
+            self.search_url_template.replace(Self::KEYWORD_PLACEHOLDER, keyword)
+        } else {
+            format!("{}{}", self.search_url_template, keyword)
+        }
+    }
+}
+
+// The download after the one being shown runs on its own thread, so it overlaps with viewing and converting.
+struct PendingDownload {
+    url: String,
+    handle: JoinHandle<(Result<bytes::Bytes, DownloaderError>, Duration)>,
+}
 
 pub struct ImageDownloader {
     urls: Vec<String>,
     index: usize,
     client: blocking::Client,
     keyword: Rc<String>,   
+    last_download_time: Option<Duration>,
+    seen_image_hashes: HashSet<u64>,
+    host_filter: HostFilter,
+    host_statistics: HostFilterStatistics,
+    pending_download: Option<PendingDownload>,
 }
 
 impl ImageDownloader {
 
-    const BING_SEARCH_URL_PREFIX: &'static str = "https://www.bing.com/images/search?q=";
-    const DEFAULT_IMAGE_SELECTOR: &'static str = "img.mimg";
+    const MAX_RESUME_ATTEMPTS: usize = 3;
 
-    pub fn new(keyword: String) -> Result<Self, DownloaderError> {
-        let urls = Self::get_urls(keyword.as_str(), Self::DEFAULT_IMAGE_SELECTOR)?;
+    pub fn new(keyword: String, host_filter: &HostFilter, search_options: &SearchOptions) -> Result<Self, DownloaderError> {
+        let urls = Self::get_urls(keyword.as_str(), search_options)?;
+        if !urls.iter().any(|url| host_filter.check(url) == HostMatch::Permitted) {
+            return Err(DownloaderError::NoResultsError { keyword });
+        }
         Ok(Self {
             urls,
             index: 0,
             client: blocking::Client::default(),
             keyword: Rc::new(keyword),
+            last_download_time: None,
+            seen_image_hashes: HashSet::new(),
+            host_filter: host_filter.clone(),
+            host_statistics: HostFilterStatistics::default(),
+            pending_download: None,
         })
     }
 
-    fn get_search_url(keyword: &str) -> String {
-        format!("{}{}", Self::BING_SEARCH_URL_PREFIX, keyword)
+    fn is_resumed_response(res: &blocking::Response, received_bytes: usize) -> bool {
+        res.status() == StatusCode::PARTIAL_CONTENT
+            && res
+                .headers()
+                .get(CONTENT_RANGE)
+                .and_then(|range| range.to_str().ok())
+                .is_some_and(|range| range.starts_with(format!("bytes {}-", received_bytes).as_str()))
+    }
+
+    fn download(client: &blocking::Client, url: &str) -> Result<bytes::Bytes, DownloaderError> {
+        let mut received: Vec<u8> = Vec::new();
+        let mut resume_attempts = 0;
+        loop {
+            let mut request = client.get(url);
+            if !received.is_empty() {
+                request = request.header(RANGE, format!("bytes={}-", received.len()));
+            }
+            let mut res = request
+                .send()
+                .map_err(|cause| DownloaderError::RequestError { url: url.to_string(), cause })?;
+            if !res.status().is_success() {
+                return Err(DownloaderError::StatusError { url: url.to_string(), status: res.status() });
+            }
+            if !Self::is_resumed_response(&res, received.len()) {
+                received.clear();
+            }
+            match res.read_to_end(&mut received) {
+                Ok(_) => return Ok(bytes::Bytes::from(received)),
+                Err(cause) if resume_attempts < Self::MAX_RESUME_ATTEMPTS => {
+                    resume_attempts += 1;
+                    Logger::log_info(&fill(messages().download_resuming, &[&url, &received.len(), &cause]));
+                }
+                Err(cause) => return Err(DownloaderError::BodyError { url: url.to_string(), cause }),
+            }
+        }
+    }
+
+    // Skips the hosts the filter rejects and starts downloading the first permitted URL left.
+    fn start_next_download(&mut self) -> Option<PendingDownload> {
+        while self.index < self.urls.len() {
+            let url = self.urls[self.index].clone();
+            self.index += 1;
+            let host_match = self.host_filter.check(&url);
+            self.host_statistics.record(host_match);
+            if host_match != HostMatch::Permitted {
+                continue;
+            }
+            let (client, thread_url) = (self.client.clone(), url.clone());
+            let handle = thread::spawn(move || {
+                let download_start = Instant::now();
+                let result = Self::download(&client, &thread_url);
+                (result, download_start.elapsed())
+            });
+            return Some(PendingDownload { url, handle });
+        }
+        None
+    }
+
+    fn hash_bytes(bytes: &[u8]) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        bytes.hash(&mut hasher);
+        hasher.finish()
     }
 
-    fn get_urls(keyword: &str, selector: &str) -> Result<Vec<String>, DownloaderError> {
+    fn launch_browser() -> Result<Browser, DownloaderError> {
         let launch_options = LaunchOptionsBuilder::default().headless(true).build()
-            .map_err(|_| DownloaderError::BrowserError)?;
-        let browser = Browser::new(launch_options)
-            .map_err(|_| DownloaderError::BrowserError)?;
+            .map_err(|e| DownloaderError::BrowserError(e.to_string()))?;
+        Browser::new(launch_options).map_err(|e| DownloaderError::BrowserError(format!("{:#}", e)))
+    }
+
+    pub fn check_browser() -> Result<(), DownloaderError> {
+        Self::launch_browser().map(|_| ())
+    }
+
+    fn get_urls(keyword: &str, search_options: &SearchOptions) -> Result<Vec<String>, DownloaderError> {
+        let browser = Self::launch_browser()?;
         let tab = browser
             .new_tab()
-            .map_err(|_| DownloaderError::BrowserError)?;
-        tab.navigate_to(Self::get_search_url(keyword).as_str())
-            .map_err(|_| DownloaderError::ConnectionError)?;
+            .map_err(|e| DownloaderError::BrowserError(format!("{:#}", e)))?;
+        let search_url = search_options.search_url(keyword);
+        let selector = search_options.image_selector();
+        tab.navigate_to(search_url.as_str())
+            .map_err(|e| DownloaderError::ConnectionError { url: search_url.clone(), cause: format!("{:#}", e) })?;
         tab.wait_until_navigated()
-            .map_err(|_| DownloaderError::SearcherError)?;
+            .map_err(|e| DownloaderError::SearcherError { url: search_url.clone(), cause: format!("{:#}", e) })?;
         let images = tab
             .wait_for_elements(selector)
-            .map_err(|_| DownloaderError::NoResultsError)?;
+            .map_err(|e| DownloaderError::SelectorError {
+                url: search_url.clone(),
+                selector: selector.to_string(),
+                cause: format!("{:#}", e),
+            })?;
         let mut results: Vec<String> = Vec::new();
+        let mut seen_urls: HashSet<String> = HashSet::new();
         for img in images {
             if let Some(attr) = img.attributes {
                 if let Some(src_attr) = attr.iter().find(|elem| elem.starts_with("https://")) {
-                    results.push(src_attr.to_string());
+                    if seen_urls.insert(src_attr.to_string()) {
+                        results.push(src_attr.to_string());
+                    }
                 }
             }
         }
         if results.is_empty() {
-            return Err(DownloaderError::NoResultsError);
+            return Err(DownloaderError::NoResultsError { keyword: keyword.to_string() });
         }
         Ok(results)
     }
@@ -85,28 +342,41 @@ impl ImageDownloader {
 impl Iterator for ImageDownloader {
     type Item = (Rc<String>, bytes::Bytes);
 
+    // The following download is started before waiting on this one, keeping one image ahead on the network.
     fn next(&mut self) -> Option<Self::Item> {
-        while self.index < self.urls.len() {
-            let url = &self.urls[self.index];
-            self.index += 1;
-            match self.client.get(url).send() {
-                Ok(res) => {
-                    if res.status().is_success() {
-                        match res.bytes() {
-                            Ok(bytes) => return Some((self.keyword.clone(), bytes)),
-                            Err(e) => {
-                                Logger::log_error(format!("Failed to read bytes from {}: {}", url, e).as_str());
-                            }
-                        }
-                    } else {
-                        Logger::log_error(format!("Request to {} failed with status: {}", url, res.status()).as_str());
+        loop {
+            let pending = match self.pending_download.take() {
+                Some(pending) => pending,
+                None => self.start_next_download()?,
+            };
+            self.pending_download = self.start_next_download();
+            let (result, download_time) = pending.handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+            match result {
+                Ok(bytes) => {
+                    if !self.seen_image_hashes.insert(Self::hash_bytes(&bytes)) {
+                        Logger::log_info(&fill(messages().duplicate_image, &[&pending.url]));
+                        continue;
                     }
+                    self.last_download_time = Some(download_time);
+                    return Some((self.keyword.clone(), bytes));
                 }
-                Err(e) => {
-                    Logger::log_error(format!("Failed to send request to {}: {}", url, e).as_str());
-                }
+                Err(e) => Logger::log_error(e.to_string().as_str()),
             }
         }
-        None
+    }
+}
+
+impl ImageSource for ImageDownloader {
+    fn last_fetch_time(&self) -> Option<Duration> {
+        self.last_download_time
+    }
+
+    fn session_report(&self) -> Option<String> {
+        Some(self.host_statistics.to_string())
+    }
+
+    // With no download pending the URLs have run out, and there is nothing to wait for either.
+    fn is_next_ready(&self) -> bool {
+        self.pending_download.as_ref().is_none_or(|pending| pending.handle.is_finished())
     }
 }
@@ -0,0 +1,241 @@
+use crate::graphics;
+use crate::messages::messages;
+use crate::sgr::{self, StyledChar};
+use ab_glyph::{point, Font, FontRef, PxScale, ScaleFont};
+use image::{ImageError, Rgb, RgbImage};
+use std::fmt;
+use std::fmt::Write;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    #[default]
+    Ansi,
+    Html,
+    Svg,
+    Png,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 4] = [ExportFormat::Ansi, ExportFormat::Html, ExportFormat::Svg, ExportFormat::Png];
+    // Cells without a colour of their own are drawn like a dark terminal would.
+    const DEFAULT_FOREGROUND: (u8, u8, u8) = (0xCC, 0xCC, 0xCC);
+    const DEFAULT_BACKGROUND: (u8, u8, u8) = (0x00, 0x00, 0x00);
+    // SVG user units per cell; monospace glyphs are roughly half as wide as they are tall.
+    const SVG_CELL_WIDTH: usize = 10;
+    const SVG_CELL_HEIGHT: usize = 20;
+    // Bundled so the picture looks the same whatever fonts the machine has installed.
+    const PNG_FONT: &'static [u8] = include_bytes!("../assets/fonts/DejaVuSansMono.ttf");
+    const PNG_FONT_SIZE: f32 = 16.0;
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Ansi => "ans",
+            ExportFormat::Html => "html",
+            ExportFormat::Svg => "svg",
+            ExportFormat::Png => "png",
+        }
+    }
+
+    pub fn render(&self, image_name: &str, image_rows: &[&Vec<StyledChar>]) -> Result<Vec<u8>, ImageError> {
+        match self {
+            ExportFormat::Ansi => Ok(Self::render_ansi(image_rows).into_bytes()),
+            ExportFormat::Html => Ok(Self::render_html(image_name, image_rows).into_bytes()),
+            ExportFormat::Svg => Ok(Self::render_svg(image_name, image_rows).into_bytes()),
+            ExportFormat::Png => graphics::encode_png(&Self::rasterize(image_rows)),
+        }
+    }
+
+    // Plain escape sequences, one row per line, so the file can be `cat`-ed without this tool.
+    fn render_ansi(image_rows: &[&Vec<StyledChar>]) -> String {
+        let mut result = String::new();
+        for row in image_rows {
+            result.push_str(&sgr::render(row));
+            result.push('\n');
+        }
+        result
+    }
+
+    // One span per run of equally coloured cells keeps the page small for photos with flat areas.
+    fn render_html(image_name: &str, image_rows: &[&Vec<StyledChar>]) -> String {
+        let mut result = String::new();
+        write!(
+            result,
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n\
+             <body style=\"margin: 0; background: {};\">\n\
+             <pre style=\"margin: 0; font-family: monospace; line-height: 1; color: {}; background: {};\">",
+            Self::escape_html(image_name),
+            Self::css_colour(Self::DEFAULT_BACKGROUND),
+            Self::css_colour(Self::DEFAULT_FOREGROUND),
+            Self::css_colour(Self::DEFAULT_BACKGROUND),
+        ).expect("Writing to String should not fail");
+        for row in image_rows {
+            for run in row.chunk_by(|a, b| a.foreground == b.foreground && a.background == b.background) {
+                let text: String = run.iter().map(|styled_char| styled_char.ch).collect();
+                let text = Self::escape_html(&text);
+                match Self::css_style(&run[0]) {
+                    Some(style) => write!(result, "<span style=\"{}\">{}</span>", style, text),
+                    None => write!(result, "{}", text),
+                }.expect("Writing to String should not fail");
+            }
+            result.push('\n');
+        }
+        result.push_str("</pre>\n</body>\n</html>\n");
+        result
+    }
+
+    // Runs of equal background become one rect and runs of equal foreground one text element, stretched with
+    // textLength to exactly the cells they cover so the grid holds whatever font the viewer substitutes.
+    fn render_svg(image_name: &str, image_rows: &[&Vec<StyledChar>]) -> String {
+        let columns = image_rows.iter().map(|row| row.len()).max().unwrap_or(0);
+        let width = columns * Self::SVG_CELL_WIDTH;
+        let height = image_rows.len() * Self::SVG_CELL_HEIGHT;
+        let mut result = String::new();
+        write!(
+            result,
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+             <svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" viewBox=\"0 0 {width} {height}\" \
+             font-family=\"monospace\" font-size=\"{}\" xml:space=\"preserve\">\n\
+             <title>{}</title>\n\
+             <rect width=\"100%\" height=\"100%\" fill=\"{}\"/>\n",
+            Self::SVG_CELL_HEIGHT * 4 / 5,
+            Self::escape_html(image_name),
+            Self::css_colour(Self::DEFAULT_BACKGROUND),
+        ).expect("Writing to String should not fail");
+        for (row_index, row) in image_rows.iter().enumerate() {
+            let y = row_index * Self::SVG_CELL_HEIGHT;
+            let mut column = 0;
+            for run in row.chunk_by(|a, b| a.background == b.background) {
+                if let Some(colour) = run[0].background {
+                    writeln!(
+                        result,
+                        "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+                        column * Self::SVG_CELL_WIDTH,
+                        y,
+                        run.len() * Self::SVG_CELL_WIDTH,
+                        Self::SVG_CELL_HEIGHT,
+                        Self::css_colour(colour.rgb()),
+                    ).expect("Writing to String should not fail");
+                }
+                column += run.len();
+            }
+            let baseline = y + Self::SVG_CELL_HEIGHT * 4 / 5;
+            let mut column = 0;
+            for run in row.chunk_by(|a, b| a.foreground == b.foreground) {
+                let text: String = run.iter().map(|styled_char| styled_char.ch).collect();
+                if !text.trim().is_empty() {
+                    writeln!(
+                        result,
+                        "<text x=\"{}\" y=\"{}\" textLength=\"{}\" lengthAdjust=\"spacingAndGlyphs\" fill=\"{}\">{}</text>",
+                        column * Self::SVG_CELL_WIDTH,
+                        baseline,
+                        run.len() * Self::SVG_CELL_WIDTH,
+                        Self::css_colour(run[0].foreground.map_or(Self::DEFAULT_FOREGROUND, |colour| colour.rgb())),
+                        Self::escape_html(&text),
+                    ).expect("Writing to String should not fail");
+                }
+                column += run.len();
+            }
+        }
+        result.push_str("</svg>\n");
+        result
+    }
+
+    // Every cell is filled with its background, then its glyph's coverage blends the foreground over it.
+    fn rasterize(image_rows: &[&Vec<StyledChar>]) -> RgbImage {
+        let font = FontRef::try_from_slice(Self::PNG_FONT).expect("The bundled font is valid");
+        let scale = PxScale::from(Self::PNG_FONT_SIZE);
+        let scaled_font = font.as_scaled(scale);
+        let cell_width = scaled_font.h_advance(font.glyph_id('M')).ceil() as u32;
+        let cell_height = scaled_font.height().ceil() as u32;
+        let columns = image_rows.iter().map(|row| row.len()).max().unwrap_or(0) as u32;
+        let mut image = RgbImage::from_pixel(
+            columns * cell_width,
+            image_rows.len() as u32 * cell_height,
+            Self::rgb_pixel(Self::DEFAULT_BACKGROUND),
+        );
+        for (row_index, row) in image_rows.iter().enumerate() {
+            let top = row_index as u32 * cell_height;
+            for (column_index, styled_char) in row.iter().enumerate() {
+                let left = column_index as u32 * cell_width;
+                let background = styled_char.background.map_or(Self::DEFAULT_BACKGROUND, |colour| colour.rgb());
+                for y in top..top + cell_height {
+                    for x in left..left + cell_width {
+                        image.put_pixel(x, y, Self::rgb_pixel(background));
+                    }
+                }
+                let glyph = font.glyph_id(styled_char.ch)
+                    .with_scale_and_position(scale, point(left as f32, top as f32 + scaled_font.ascent()));
+                let Some(outline) = font.outline_glyph(glyph) else {
+                    continue;
+                };
+                let foreground = styled_char.foreground.map_or(Self::DEFAULT_FOREGROUND, |colour| colour.rgb());
+                let bounds = outline.px_bounds();
+                outline.draw(|x, y, coverage| {
+                    let x = bounds.min.x as i32 + x as i32;
+                    let y = bounds.min.y as i32 + y as i32;
+                    let inside_cell = (left as i32..(left + cell_width) as i32).contains(&x)
+                        && (top as i32..(top + cell_height) as i32).contains(&y);
+                    if inside_cell {
+                        let pixel = image.get_pixel_mut(x as u32, y as u32);
+                        *pixel = Self::blend(*pixel, foreground, coverage);
+                    }
+                });
+            }
+        }
+        image
+    }
+
+    fn rgb_pixel((r, g, b): (u8, u8, u8)) -> Rgb<u8> {
+        Rgb([r, g, b])
+    }
+
+    fn blend(pixel: Rgb<u8>, (r, g, b): (u8, u8, u8), coverage: f32) -> Rgb<u8> {
+        let coverage = coverage.clamp(0.0, 1.0);
+        let mix = |under: u8, over: u8| (under as f32 + (over as f32 - under as f32) * coverage).round() as u8;
+        Rgb([mix(pixel[0], r), mix(pixel[1], g), mix(pixel[2], b)])
+    }
+
+    fn css_style(styled_char: &StyledChar) -> Option<String> {
+        let mut declarations = Vec::with_capacity(2);
+        if let Some(colour) = styled_char.foreground {
+            declarations.push(format!("color: {}", Self::css_colour(colour.rgb())));
+        }
+        if let Some(colour) = styled_char.background {
+            declarations.push(format!("background: {}", Self::css_colour(colour.rgb())));
+        }
+        if declarations.is_empty() {
+            None
+        } else {
+            Some(declarations.join("; "))
+        }
+    }
+
+    fn css_colour((r, g, b): (u8, u8, u8)) -> String {
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+
+    fn escape_html(text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        for ch in text.chars() {
+            match ch {
+                '&' => result.push_str("&amp;"),
+                '<' => result.push_str("&lt;"),
+                '>' => result.push_str("&gt;"),
+                '"' => result.push_str("&quot;"),
+                _ => result.push(ch),
+            }
+        }
+        result
+    }
+}
+
+impl fmt::Display for ExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExportFormat::Ansi => write!(f, "{}", messages().export_format_ansi),
+            ExportFormat::Html => write!(f, "{}", messages().export_format_html),
+            ExportFormat::Svg => write!(f, "{}", messages().export_format_svg),
+            ExportFormat::Png => write!(f, "{}", messages().export_format_png),
+        }
+    }
+}
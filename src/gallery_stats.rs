@@ -0,0 +1,127 @@
+use crate::image_storage::{ImageLoadIterator, ImageStorage, StorageError};
+use crate::messages::{fill, messages};
+use crate::sgr::{Colour, StyledChar};
+use std::fmt;
+use std::path::Path;
+use std::time::SystemTime;
+
+struct GalleryFile {
+    name: String,
+    group: String,
+    size: u64,
+    modified: SystemTime,
+}
+
+pub struct GalleryStatistics {
+    files: Vec<GalleryFile>,
+    groups: Vec<(String, usize)>,
+}
+
+impl GalleryStatistics {
+    const BAR_CHAR: char = '\u{2588}';
+    const MAX_BAR_WIDTH: usize = 30;
+    const BAR_COLOURS: [(u8, u8, u8); 6] = [
+        (239, 71, 111),
+        (255, 209, 102),
+        (6, 214, 160),
+        (17, 138, 178),
+        (155, 93, 229),
+        (255, 146, 76),
+    ];
+    const SIZE_UNITS: [&'static str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+    pub fn collect(load_path: &str) -> Result<Self, StorageError> {
+        let path = Path::new(load_path);
+        if !path.is_dir() {
+            return Err(StorageError::NotADirError);
+        }
+        let mut files = Vec::new();
+        for entry in path.read_dir().map_err(|_| StorageError::OpeningDirError)?.flatten() {
+            let file_path = entry.path();
+            let metadata = match entry.metadata() {
+                Ok(metadata) if metadata.is_file() => metadata,
+                _ => continue,
+            };
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let group = match file_path.extension().and_then(|extension| extension.to_str()) {
+                Some(ImageStorage::IMAGE_EXTENSION) => Self::keyword_of(&file_path),
+                Some(ImageLoadIterator::ANSI_EXTENSION) => messages().gallery_ansi_group.to_string(),
+                _ => continue,
+            };
+            files.push(GalleryFile {
+                name,
+                group,
+                size: metadata.len(),
+                modified: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+            });
+        }
+        let mut groups: Vec<(String, usize)> = Vec::new();
+        for file in &files {
+            match groups.iter_mut().find(|(group, _)| *group == file.group) {
+                Some((_, count)) => *count += 1,
+                None => groups.push((file.group.clone(), 1)),
+            }
+        }
+        groups.sort_by(|(name_a, count_a), (name_b, count_b)| count_b.cmp(count_a).then(name_a.cmp(name_b)));
+        Ok(Self { files, groups })
+    }
+
+    fn keyword_of(file_path: &Path) -> String {
+        let stem = file_path.file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        match stem.split_once('_') {
+            Some((_, keyword)) if !keyword.is_empty() => keyword.to_string(),
+            _ => stem,
+        }
+    }
+
+    fn format_size(size: u64) -> String {
+        let mut value = size as f64;
+        let mut unit = 0;
+        while value >= 1024.0 && unit < Self::SIZE_UNITS.len() - 1 {
+            value /= 1024.0;
+            unit += 1;
+        }
+        if unit == 0 {
+            format!("{} {}", size, Self::SIZE_UNITS[0])
+        } else {
+            format!("{:.1} {}", value, Self::SIZE_UNITS[unit])
+        }
+    }
+
+    fn bar(length: usize, (r, g, b): (u8, u8, u8)) -> String {
+        let styled_char = StyledChar {
+            ch: Self::BAR_CHAR,
+            foreground: Some(Colour::Rgb(r, g, b)),
+            background: None,
+        };
+        (0..length).map(|_| styled_char.to_string()).collect()
+    }
+}
+
+impl fmt::Display for GalleryStatistics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let text = messages();
+        writeln!(f, "{}", text.gallery_title)?;
+        writeln!(f, "{}", fill(text.gallery_image_count, &[&self.files.len()]))?;
+        let total_size: u64 = self.files.iter().map(|file| file.size).sum();
+        writeln!(f, "{}", fill(text.gallery_disk_usage, &[&Self::format_size(total_size)]))?;
+        if let Some(largest) = self.files.iter().max_by_key(|file| file.size) {
+            writeln!(f, "{}", fill(text.gallery_largest, &[&largest.name, &Self::format_size(largest.size)]))?;
+        }
+        if let Some(oldest) = self.files.iter().min_by_key(|file| file.modified) {
+            writeln!(f, "{}", fill(text.gallery_oldest, &[&oldest.name]))?;
+        }
+        let max_count = self.groups.first().map_or(0, |(_, count)| *count);
+        if max_count == 0 {
+            return Ok(());
+        }
+        writeln!(f, "{}", text.gallery_by_keyword)?;
+        let label_width = self.groups.iter().map(|(group, _)| group.chars().count()).max().unwrap_or(0);
+        for (index, (group, count)) in self.groups.iter().enumerate() {
+            let length = (count * Self::MAX_BAR_WIDTH).div_ceil(max_count);
+            let colour = Self::BAR_COLOURS[index % Self::BAR_COLOURS.len()];
+            writeln!(f, "  {:<width$}  {} {}", group, Self::bar(length, colour), count, width = label_width)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use image::{ImageError, ImageFormat, RgbImage};
+use std::env;
+use std::io::Cursor;
+
+pub trait GraphicsProtocol {
+    const OVERRIDE_VARIABLE: &'static str;
+
+    fn is_detected() -> bool;
+
+    fn encode(image: &RgbImage, columns: u32, rows: u32) -> Result<String, ImageError>;
+
+    // The override wins over detection: "0" always falls back to ASCII, anything else forces graphics.
+    fn is_supported() -> bool {
+        match env::var_os(Self::OVERRIDE_VARIABLE).filter(|value| !value.is_empty()) {
+            Some(value) => value != "0",
+            None => Self::is_detected(),
+        }
+    }
+}
+
+pub fn encode_png(image: &RgbImage) -> Result<Vec<u8>, ImageError> {
+    let mut png = Vec::new();
+    image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)?;
+    Ok(png)
+}
@@ -0,0 +1,51 @@
+use crate::config::{Config, ConfigError};
+use std::fs;
+use std::path::PathBuf;
+
+pub struct KeywordHistory {
+    path: PathBuf,
+    keywords: Vec<String>,
+}
+
+impl KeywordHistory {
+    const HISTORY_FILE_NAME: &'static str = "keyword_history.txt";
+    const MAX_KEYWORDS: usize = 10;
+
+    pub fn load() -> Result<Self, ConfigError> {
+        let path = Config::config_dir()?.join(Self::HISTORY_FILE_NAME);
+        let keywords = if path.is_file() {
+            fs::read_to_string(&path)?
+                .lines()
+                .map(str::trim)
+                .filter(|keyword| !keyword.is_empty())
+                .take(Self::MAX_KEYWORDS)
+                .map(str::to_string)
+                .collect()
+        } else {
+            Vec::new()
+        };
+        Ok(Self { path, keywords })
+    }
+
+    pub fn keywords(&self) -> &[String] {
+        &self.keywords
+    }
+
+    pub fn record(&mut self, keyword: &str) {
+        let keyword = keyword.trim();
+        if keyword.is_empty() {
+            return;
+        }
+        self.keywords.retain(|existing| existing != keyword);
+        self.keywords.insert(0, keyword.to_string());
+        self.keywords.truncate(Self::MAX_KEYWORDS);
+    }
+
+    pub fn save(&self) -> Result<(), ConfigError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, self.keywords.join("\n"))?;
+        Ok(())
+    }
+}
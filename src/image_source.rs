@@ -0,0 +1,49 @@
+use bytes::Bytes;
+use image::{DynamicImage, ImageFormat};
+use std::io::Cursor;
+use std::rc::Rc;
+use std::time::Duration;
+
+pub trait ImageSource: Iterator<Item = (Rc<String>, Bytes)> {
+    fn last_fetch_time(&self) -> Option<Duration> {
+        None
+    }
+
+    fn session_report(&self) -> Option<String> {
+        None
+    }
+
+    // Live sources capture on demand, so fetching ahead would show a stale picture.
+    fn is_live(&self) -> bool {
+        false
+    }
+
+    // Whether the next image can be taken without waiting, e.g. on the network.
+    fn is_next_ready(&self) -> bool {
+        true
+    }
+}
+
+impl<S: ImageSource + ?Sized> ImageSource for Box<S> {
+    fn last_fetch_time(&self) -> Option<Duration> {
+        (**self).last_fetch_time()
+    }
+
+    fn session_report(&self) -> Option<String> {
+        (**self).session_report()
+    }
+
+    fn is_live(&self) -> bool {
+        (**self).is_live()
+    }
+
+    fn is_next_ready(&self) -> bool {
+        (**self).is_next_ready()
+    }
+}
+
+pub fn encode_frame(frame: DynamicImage) -> Option<Bytes> {
+    let mut encoded = Cursor::new(Vec::new());
+    frame.write_to(&mut encoded, ImageFormat::Bmp).ok()?;
+    Some(Bytes::from(encoded.into_inner()))
+}
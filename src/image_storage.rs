@@ -1,6 +1,11 @@
+use crate::export::ExportFormat;
 use crate::logger::Logger;
+use crate::messages::{fill, messages};
 use crate::printer::PrinterImageData;
-use std::fs::{File, ReadDir};
+use crate::sgr;
+use crate::sgr::StyledChar;
+use rand::seq::SliceRandom;
+use std::fs::{self, File};
 use std::io::{BufRead, BufWriter, Write};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
@@ -13,6 +18,8 @@ pub enum StorageError {
     SavePathError,
     SaveError,
     LoadError(String),
+    DeleteError(String),
+    FavouriteError(String),
     NotADirError,
     OpeningDirError,
     IoError(io::Error)
@@ -28,12 +35,14 @@ impl From<io::Error> for StorageError {
 impl fmt::Display for StorageError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            StorageError::SavePathError => write!(f, "Given save path is not a valid directory"),
-            StorageError::SaveError => write!(f, "Could not save image to the given save directory"),
-            StorageError::LoadError(image_name) => write!(f, "Image {image_name} couldn't be loaded"),
-            StorageError::NotADirError => write!(f, "Given path is not a directory - it may be a file instead"),
-            StorageError::OpeningDirError => write!(f, "Failed to open the given directory"),
-            StorageError::IoError(err) => write!(f, "IO error: {}", err),
+            StorageError::SavePathError => write!(f, "{}", messages().save_path_error),
+            StorageError::SaveError => write!(f, "{}", messages().save_error),
+            StorageError::LoadError(image_name) => write!(f, "{}", fill(messages().load_error, &[image_name])),
+            StorageError::DeleteError(image_name) => write!(f, "{}", fill(messages().delete_error, &[image_name])),
+            StorageError::FavouriteError(image_name) => write!(f, "{}", fill(messages().favourite_error, &[image_name])),
+            StorageError::NotADirError => write!(f, "{}", messages().not_a_dir_error),
+            StorageError::OpeningDirError => write!(f, "{}", messages().opening_dir_error),
+            StorageError::IoError(err) => write!(f, "{}", fill(messages().storage_io_error, &[err])),
         }
     }
 }
@@ -44,8 +53,14 @@ pub struct ImageStorage {
 
 impl ImageStorage {
 
-    const IMAGE_EXTENSION: &'static str = "cwi";
+    pub const IMAGE_EXTENSION: &'static str = "cwi";
     const CELL_SEPARATOR: &'static str = " ";
+    const CELL_SEPARATOR_CHAR: char = ' ';
+    const METADATA_PREFIX: &'static str = "#!cwi ";
+    const NOTE_KEY: &'static str = "note=";
+    const TEMPORARY_EXTENSION: &'static str = "cwi.tmp";
+    const FAVOURITE_KEY: &'static str = "favourite";
+    const FORBIDDEN_NAME_CHARS: [char; 9] = ['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
 
     pub fn new(save_path: String) -> Result<Self, StorageError> {
         let path = Path::new(&save_path);
@@ -57,27 +72,93 @@ impl ImageStorage {
         })
     }
 
-    fn get_image_name(image_name: &str) -> String {
+    // Names typed by the user must not escape the save directory or trip up the file system.
+    fn get_image_name(image_name: &str, extension: &str) -> String {
+        let image_name: String = image_name
+            .chars()
+            .map(|ch| if Self::FORBIDDEN_NAME_CHARS.contains(&ch) || ch.is_control() { '_' } else { ch })
+            .collect();
         format!("{}_{}.{}", SystemTime::now().duration_since(std::time::UNIX_EPOCH).expect("This will always be correct")
-            .as_secs(),  image_name, Self::IMAGE_EXTENSION)
+            .as_secs(),  image_name, extension)
     }
 
-    pub fn save_image(&self, image_name: &str,image_array: &Vec<Vec<String>>) -> Result<String, StorageError> {
-        let path = Path::new(&self.save_path);
-        let new_image_name = Self::get_image_name(image_name);
-        let mut path = path.join(new_image_name.as_str());
+    fn new_image_path(&self, image_name: &str, extension: &str) -> (String, PathBuf) {
+        let save_dir = Path::new(&self.save_path);
+        let mut new_image_name = Self::get_image_name(image_name, extension);
+        let mut path = save_dir.join(new_image_name.as_str());
         while path.exists() {
             sleep(std::time::Duration::from_millis(200));
-            path = path.join(Self::get_image_name(image_name));
+            new_image_name = Self::get_image_name(image_name, extension);
+            path = save_dir.join(new_image_name.as_str());
         }
+        (new_image_name, path)
+    }
+
+    pub fn save_image(&self, image_name: &str,image_array: &[Vec<StyledChar>], note: Option<&str>, favourite: bool) -> Result<String, StorageError> {
+        let (new_image_name, path) = self.new_image_path(image_name, Self::IMAGE_EXTENSION);
         let mut writer = BufWriter::new(File::create::<&Path>(path.as_ref()).map_err(|_| StorageError::SaveError)?);
+        if let Some(note) = note {
+            writeln!(writer, "{}{}{}", Self::METADATA_PREFIX, Self::NOTE_KEY, note).map_err(|_| StorageError::SaveError)?;
+        }
+        if favourite {
+            writeln!(writer, "{}{}", Self::METADATA_PREFIX, Self::FAVOURITE_KEY).map_err(|_| StorageError::SaveError)?;
+        }
         for row in image_array {
-            writeln!(writer, "{}", row.join(Self::CELL_SEPARATOR)).map_err(|_| StorageError::SaveError)?;
+            let cells: Vec<String> = row.iter().map(ToString::to_string).collect();
+            writeln!(writer, "{}", cells.join(Self::CELL_SEPARATOR)).map_err(|_| StorageError::SaveError)?;
         }
         writer.flush()?;
         Ok(new_image_name)
     }
 
+    pub fn export_image(&self, image_name: &str, image_rows: &[&Vec<StyledChar>], format: ExportFormat) -> Result<String, StorageError> {
+        let (new_image_name, path) = self.new_image_path(image_name, format.extension());
+        let contents = format.render(image_name, image_rows).map_err(|_| StorageError::SaveError)?;
+        fs::write(path, contents).map_err(|_| StorageError::SaveError)?;
+        Ok(new_image_name)
+    }
+
+    // Only files this tool can load are ever deleted, whatever path it is handed.
+    pub fn delete_image(&self, image_path: &Path) -> Result<(), StorageError> {
+        let path_string = image_path.to_string_lossy().into_owned();
+        match image_path.extension().and_then(|extension| extension.to_str()) {
+            Some(Self::IMAGE_EXTENSION) | Some(ImageLoadIterator::ANSI_EXTENSION) if image_path.is_file() => {
+                fs::remove_file(image_path).map_err(|_| StorageError::DeleteError(path_string))
+            }
+            _ => Err(StorageError::DeleteError(path_string)),
+        }
+    }
+
+    // The mark lives in the image's own header, so only .cwi files can carry it; the rest of the file is kept as is.
+    pub fn set_favourite(&self, image_path: &Path, favourite: bool) -> Result<(), StorageError> {
+        let path_string = image_path.to_string_lossy().into_owned();
+        if image_path.extension().and_then(|extension| extension.to_str()) != Some(Self::IMAGE_EXTENSION) {
+            return Err(StorageError::FavouriteError(path_string));
+        }
+        let favourite_line = format!("{}{}", Self::METADATA_PREFIX, Self::FAVOURITE_KEY);
+        let content = fs::read_to_string(image_path).map_err(|_| StorageError::FavouriteError(path_string.clone()))?;
+        let mut lines: Vec<&str> = content.lines().filter(|line| *line != favourite_line).collect();
+        if favourite {
+            lines.insert(0, &favourite_line);
+        }
+        // Written next to the original and moved over it, so a failed write never leaves the art half gone.
+        let temporary_path = image_path.with_extension(Self::TEMPORARY_EXTENSION);
+        let written = Self::write_lines(&temporary_path, &lines).and_then(|_| fs::rename(&temporary_path, image_path));
+        if written.is_err() {
+            let _ = fs::remove_file(&temporary_path);
+            return Err(StorageError::FavouriteError(path_string));
+        }
+        Ok(())
+    }
+
+    fn write_lines(path: &Path, lines: &[&str]) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        for line in lines {
+            writeln!(writer, "{}", line)?;
+        }
+        writer.flush()
+    }
+
     pub fn to_load_iterator(&self, load_path: &str) -> Result<ImageLoadIterator, StorageError> {
         ImageLoadIterator::new(load_path)
     }
@@ -85,23 +166,61 @@ impl ImageStorage {
 }
 
 pub struct ImageLoadIterator{
-    dir_iter: ReadDir
+    // The directory is listed up front, so the number of images is known; kept reversed to pop the next one off the end.
+    paths: Vec<PathBuf>,
 }
 
 impl ImageLoadIterator {
+    pub const ANSI_EXTENSION: &'static str = "ans";
+    const ANSI_EOF_MARKER: char = '\x1A';
+
     fn new(load_path: &str) -> Result<Self, StorageError> {
         let path = Path::new(&load_path);
         if !path.is_dir() {
             return Err(StorageError::NotADirError);
         }
+        let mut paths = Vec::new();
+        for entry in path.read_dir().map_err(|_| StorageError::OpeningDirError)? {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    Logger::log_error(&fill(messages().file_read_failed, &[&e]));
+                    continue;
+                },
+            };
+            let full_path = entry.path();
+            if !full_path.is_file() { 
+                continue;
+            }
+            match full_path.extension().and_then(|extension| extension.to_str()) {
+                Some(ImageStorage::IMAGE_EXTENSION) | Some(ImageLoadIterator::ANSI_EXTENSION) => paths.push(full_path),
+                _ => continue,
+            }
+        }
+        paths.reverse();
         Ok(Self{
-            dir_iter: path.read_dir().map_err(|_| StorageError::OpeningDirError)?
+            paths,
         })
     }
 
+    pub fn shuffle_remaining(&mut self) -> usize {
+        self.paths.shuffle(&mut rand::rng());
+        self.paths.len()
+    }
+
+    pub fn load_file(image_path: &Path) -> Result<PrinterImageData, StorageError> {
+        let image_data = match image_path.extension().and_then(|extension| extension.to_str()) {
+            Some(ImageStorage::IMAGE_EXTENSION) => Self::load_image(image_path.to_path_buf()),
+            Some(Self::ANSI_EXTENSION) => Self::load_ansi_image(image_path.to_path_buf()),
+            _ => Err(StorageError::LoadError(image_path.to_string_lossy().into_owned())),
+        }?;
+        Ok(image_data.with_path(image_path.to_path_buf()))
+    }
+
     pub fn wrap_into_valid(self) -> ValidImageLoadIterator {
         ValidImageLoadIterator {
             iterator: self,
+            favourites_only: false,
         }
     }
 
@@ -111,33 +230,82 @@ impl ImageLoadIterator {
         Ok(io::BufReader::new(file).lines())
     }
 
+    fn parse_row(line: &str) -> Option<Vec<StyledChar>> {
+        let styled_chars = sgr::parse(line).ok()?;
+        if styled_chars.len() % 2 == 0 {
+            return None;
+        }
+        let mut row = Vec::with_capacity(styled_chars.len() / 2 + 1);
+        for (index, styled_char) in styled_chars.iter().enumerate() {
+            if index % 2 == 0 {
+                row.push(*styled_char);
+            } else if *styled_char != StyledChar::plain(ImageStorage::CELL_SEPARATOR_CHAR) {
+                return None;
+            }
+        }
+        Some(row)
+    }
+
     fn load_image(image_path: PathBuf) -> Result<PrinterImageData, StorageError> {
         let mut lines = Self::read_lines(&image_path)?;
         let path_string = image_path.to_string_lossy().to_string();
         let load_error = || StorageError::LoadError(path_string.clone());
-        let first_line: Vec<_> = lines.next().ok_or(load_error())??
-            .split(ImageStorage::CELL_SEPARATOR)
-            .map(str::to_string)
-            .collect();
+        let mut note = None;
+        let mut favourite = false;
+        let mut first_line = lines.next().ok_or(load_error())??;
+        while let Some(metadata) = first_line.strip_prefix(ImageStorage::METADATA_PREFIX) {
+            if let Some(value) = metadata.strip_prefix(ImageStorage::NOTE_KEY) {
+                note = Some(value.to_string());
+            } else if metadata == ImageStorage::FAVOURITE_KEY {
+                favourite = true;
+            }
+            first_line = lines.next().ok_or(load_error())??;
+        }
+        let first_line = Self::parse_row(&first_line).ok_or_else(load_error)?;
         let expected_length: usize = first_line.len();
         let mut result = vec![first_line];
 
         let remaining_lines: Vec<_> = lines
             .map(|line| {
-                let current_line: Vec<String> = line?.split(ImageStorage::CELL_SEPARATOR)
-                    .map(str::to_string)
-                    .collect();
-
-                if current_line.len() != expected_length {
-                    Err(load_error())
-                } else {
-                    Ok(current_line)
+                match Self::parse_row(&line?) {
+                    Some(current_line) if current_line.len() == expected_length => Ok(current_line),
+                    _ => Err(load_error()),
                 }
             })
             .collect::<Result<Vec<_>, _>>()?;
         result.reserve(remaining_lines.len());
         result.extend(remaining_lines);
         let image_file_name = Rc::new(image_path.file_name().ok_or_else(load_error)?.to_string_lossy().into_owned());
+        Ok(PrinterImageData::new(
+            image_file_name,
+            result,
+        ).with_note(note).with_favourite(favourite))
+    }
+
+    fn load_ansi_image(image_path: PathBuf) -> Result<PrinterImageData, StorageError> {
+        let path_string = image_path.to_string_lossy().to_string();
+        let load_error = || StorageError::LoadError(path_string.clone());
+        let file_content = fs::read(&image_path)?;
+        let text = String::from_utf8_lossy(&file_content);
+        // Anything past the EOF marker is a SAUCE metadata record, not art.
+        let art = text.split(Self::ANSI_EOF_MARKER).next().unwrap_or_default();
+        let mut result = art
+            .lines()
+            .map(|line| {
+                sgr::parse(line).map_err(|_| load_error())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        while result.last().is_some_and(Vec::is_empty) {
+            result.pop();
+        }
+        let width = result.iter().map(Vec::len).max().unwrap_or(0);
+        if width == 0 {
+            return Err(load_error());
+        }
+        for row in result.iter_mut() {
+            row.resize(width, StyledChar::plain(ImageStorage::CELL_SEPARATOR_CHAR));
+        }
+        let image_file_name = Rc::new(image_path.file_name().ok_or_else(load_error)?.to_string_lossy().into_owned());
         Ok(PrinterImageData::new(
             image_file_name,
             result,
@@ -149,30 +317,28 @@ impl Iterator for ImageLoadIterator {
     type Item = Result<PrinterImageData, StorageError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        while let Some(entry) = self.dir_iter.next() {
-            let entry = match entry {
-                Ok(entry) => entry,
-                Err(e) => {
-                    Logger::log_error(format!("Failed to read file: {}", e).as_str());
-                    continue;
-                },
-            };
-            let full_path = entry.path();
-            if !full_path.is_file() { 
-                continue;
-            }
-            let extension = full_path.extension();
-            if extension.is_none() || extension.unwrap() != ImageStorage::IMAGE_EXTENSION {
-                continue;
-            }
-            return Some(ImageLoadIterator::load_image(full_path));
-        }
-        None
+        self.paths.pop().map(|path| ImageLoadIterator::load_file(&path))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.paths.len(), Some(self.paths.len()))
     }
 }
 
 pub struct ValidImageLoadIterator{
     iterator: ImageLoadIterator,
+    favourites_only: bool,
+}
+
+impl ValidImageLoadIterator {
+    pub fn favourites_only(mut self) -> Self {
+        self.favourites_only = true;
+        self
+    }
+
+    pub fn shuffle_remaining(&mut self) -> usize {
+        self.iterator.shuffle_remaining()
+    }
 }
 
 impl Iterator for ValidImageLoadIterator{
@@ -181,11 +347,17 @@ impl Iterator for ValidImageLoadIterator{
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             match self.iterator.next() {
+                Some(Ok(image)) if self.favourites_only && !image.is_favourite() => continue,
                 Some(Ok(image)) => return Some(image),
-                Some(Err(err)) => Logger::log_error(format!("Skipping problematic file during load: {}", err).as_str()),
+                Some(Err(err)) => Logger::log_error(&fill(messages().skipping_file, &[&err])),
                 None => return None
             }
         }
     }
 
+    // Files that fail to load, or are filtered out, are skipped, so only the upper bound is known.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iterator.size_hint().1)
+    }
+
 }
@@ -0,0 +1,34 @@
+use crossterm::event::{self, Event};
+use std::collections::VecDeque;
+use std::io;
+use std::sync::Mutex;
+use std::time::Duration;
+
+// Keys pressed while a conversion only listens for Esc are kept here, so the menu still gets them afterwards.
+static DEFERRED_EVENTS: Mutex<VecDeque<Event>> = Mutex::new(VecDeque::new());
+
+pub struct InputQueue;
+
+impl InputQueue {
+    pub fn defer(event: Event) {
+        DEFERRED_EVENTS.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).push_back(event);
+    }
+
+    fn take_deferred() -> Option<Event> {
+        DEFERRED_EVENTS.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).pop_front()
+    }
+
+    pub fn poll(timeout: Duration) -> io::Result<bool> {
+        if !DEFERRED_EVENTS.lock().unwrap_or_else(|poisoned| poisoned.into_inner()).is_empty() {
+            return Ok(true);
+        }
+        event::poll(timeout)
+    }
+
+    pub fn read() -> io::Result<Event> {
+        match Self::take_deferred() {
+            Some(event) => Ok(event),
+            None => event::read(),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::graphics::{self, GraphicsProtocol};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use image::{ImageError, RgbImage};
+use std::env;
+
+pub struct ITerm2;
+
+impl GraphicsProtocol for ITerm2 {
+    const OVERRIDE_VARIABLE: &'static str = "COLOURFUL_WORDS_ITERM2";
+
+    fn is_detected() -> bool {
+        env::var("TERM_PROGRAM").is_ok_and(|program| program == "iTerm.app")
+            || env::var("LC_TERMINAL").is_ok_and(|terminal| terminal == "iTerm2")
+    }
+
+    fn encode(image: &RgbImage, columns: u32, rows: u32) -> Result<String, ImageError> {
+        let png = graphics::encode_png(image)?;
+        Ok(format!(
+            "\x1B]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=0:{}\x07",
+            png.len(),
+            columns,
+            rows,
+            STANDARD.encode(&png)
+        ))
+    }
+}
@@ -0,0 +1,43 @@
+use crate::graphics::{self, GraphicsProtocol};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use image::{ImageError, RgbImage};
+use std::env;
+use std::fmt::Write;
+
+pub struct Kitty;
+
+impl Kitty {
+    const SUPPORTING_TERM_PROGRAMS: [&'static str; 2] = ["WezTerm", "ghostty"];
+    const CHUNK_SIZE: usize = 4096;
+    const END: &'static str = "\x1B\\";
+}
+
+impl GraphicsProtocol for Kitty {
+    const OVERRIDE_VARIABLE: &'static str = "COLOURFUL_WORDS_KITTY";
+
+    fn is_detected() -> bool {
+        env::var_os("KITTY_WINDOW_ID").is_some()
+            || env::var("TERM").is_ok_and(|term| term.contains("kitty") || term.contains("ghostty"))
+            || env::var("TERM_PROGRAM").is_ok_and(|program| Self::SUPPORTING_TERM_PROGRAMS.contains(&program.as_str()))
+    }
+
+    fn encode(image: &RgbImage, columns: u32, rows: u32) -> Result<String, ImageError> {
+        let payload = STANDARD.encode(graphics::encode_png(image)?);
+        let chunk_count = payload.len().div_ceil(Self::CHUNK_SIZE);
+        let mut graphic = String::with_capacity(payload.len() + chunk_count * 16 + 32);
+        for (index, chunk) in payload.as_bytes().chunks(Self::CHUNK_SIZE).enumerate() {
+            let more_chunks = (index + 1 < chunk_count) as u8;
+            // q=2 keeps the terminal from answering, which would otherwise show up as key presses.
+            if index == 0 {
+                write!(graphic, "\x1B_Ga=T,f=100,q=2,c={},r={},m={};", columns, rows, more_chunks)
+            } else {
+                write!(graphic, "\x1B_Gm={};", more_chunks)
+            }
+            .expect("Writing to String should not fail");
+            graphic.push_str(std::str::from_utf8(chunk).expect("Base64 output is ASCII"));
+            graphic.push_str(Self::END);
+        }
+        Ok(graphic)
+    }
+}
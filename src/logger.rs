@@ -1,3 +1,4 @@
+use crate::messages::messages;
 use crossterm::style::{Color, Print, ResetColor, SetForegroundColor};
 use crossterm::QueueableCommand;
 use std::io::Write;
@@ -18,21 +19,21 @@ impl Logger {
     }
 
     pub fn log_info(info: &str) {
-        let message = format!("INFO: {}\n", info);
+        let message = format!("{}: {}\n", messages().log_info_prefix, info);
         if Self::log_with_colour(Color::Yellow, message.as_str()).is_err() {
             Self::log_without_color(message)
         }
     }
 
     pub fn log_success(success: &str) {
-        let message = format!("Success: {}\n", success);
+        let message = format!("{}: {}\n", messages().log_success_prefix, success);
         if Self::log_with_colour(Color::Green, message.as_str()).is_err() {
             Self::log_without_color(message)
         }
     }
 
     pub fn log_error(error: &str) {
-        let message = format!("ERROR: {}\n", error);
+        let message = format!("{}: {}\n", messages().log_error_prefix, error);
         if Self::log_with_colour(Color::Red, message.as_str()).is_err() {
             Self::log_without_color(message)
         }
@@ -1,28 +1,91 @@
+mod calibration;
+mod cell_grid;
+mod config;
 mod converter;
 mod downloader;
+mod export;
+mod gallery_stats;
+mod graphics;
+mod history;
+mod image_source;
 mod image_storage;
+mod input_queue;
+mod iterm2;
+mod kitty;
 mod logger;
+mod messages;
+mod mock_source;
+mod osc52;
+mod palette;
 mod printer;
+mod replay;
+#[cfg(feature = "screen-capture")]
+mod screen_capture;
+mod sgr;
+mod sixel;
+mod sound;
+mod suggestions;
+mod terminal_guard;
+mod timings;
+#[cfg(feature = "webcam")]
+mod webcam;
 
-use crate::converter::Converter;
-use crate::downloader::ImageDownloader;
-use crate::image_storage::{ImageStorage, ValidImageLoadIterator};
+use crate::calibration::FontCalibration;
+use crate::config::Config;
+use crate::converter::{AlphaMode, Caption, CharacterRamp, ColourMode, Converter, ConverterOptions, CropRegion, Dithering, Duotone, Effect, EffectChain, Inversion, Luminance, RenderStyle, SizeLimits, Transform, WidthSpec};
+use crate::downloader::{HostFilter, ImageDownloader, SearchOptions};
+use crate::export::ExportFormat;
+use crate::gallery_stats::GalleryStatistics;
+use crate::history::KeywordHistory;
+use crate::image_source::ImageSource;
+use crate::image_storage::{ImageLoadIterator, ImageStorage, ValidImageLoadIterator};
+use crate::input_queue::InputQueue;
 use crate::logger::Logger;
-use crate::printer::{Printer, PrinterError, PrinterImageData};
-use crossterm::event;
+use crate::messages::{fill, messages, Language};
+use crate::mock_source::MockSource;
+use crate::palette::Palette;
+use crate::printer::{ClearMode, Printer, PrinterError, PrinterImageData, PrinterOptions, RevealStyle, ToastKind};
+use crate::replay::Replay;
+use crate::sgr::Colour;
+#[cfg(feature = "screen-capture")]
+use crate::screen_capture::{CaptureRegion, ScreenCapture};
+use crate::sound::{SoundEvent, SoundFeedback};
+use crate::suggestions::KeywordSuggestions;
+use crate::terminal_guard::TerminalGuard;
+#[cfg(feature = "webcam")]
+use crate::webcam::Webcam;
 use crossterm::event::{Event, KeyCode, KeyEventKind};
-use dialoguer::{Input, Select};
+use crossterm::terminal;
+use dialoguer::{Confirm, Input, MultiSelect, Select, Sort};
 use std::env;
+use std::fs;
 use std::io;
+use std::path::Path;
 use std::process::exit;
 
-fn prompt_for_width() -> u32 {
+fn prompt_for_width(size_limits: &SizeLimits, default_width: Option<WidthSpec>) -> WidthSpec {
    
     loop {
-        let width_str = prompt_user("Enter image width (tip: enter 100 and zoom out with CRTL-)");
-        match width_str.trim().parse::<u32>() {
+        let width_str = match default_width {
+            Some(width) => prompt_user_with_default(messages().prompt_width, &width.to_string()),
+            None => prompt_user(messages().prompt_width),
+        };
+        match width_str.parse::<WidthSpec>().and_then(|width| width.check(size_limits).map(|_| width)) {
             Ok(width) => return width,
-            Err(_) => Logger::log_error("Invalid width. Please enter a positive integer."),
+            Err(e) => Logger::log_error(&e.to_string()),
+        }
+    }
+}
+
+fn prompt_for_crop() -> Option<CropRegion> {
+    loop {
+        let crop = prompt_user_allow_empty(messages().prompt_crop);
+        if crop.trim().is_empty() {
+            return None;
+        }
+        match crop.parse::<CropRegion>() {
+            Ok(crop) => return Some(crop),
+            Err(e) => Logger::log_error(&e.to_string()),
         }
     }
 }
@@ -32,86 +95,997 @@ fn prompt_user(prompt: &str) -> String {
         match Input::new().with_prompt(prompt).interact_text() {
             Ok(text) => return text,
             Err(e) => {
-                Logger::log_error(&format!(
-                    "Failed to get user input: {}. Please try again.",
-                    e
-                ));
+                Logger::log_error(&fill(messages().input_error, &[&e]));
+            }
+        }
+    }
+}
+
+fn prompt_user_with_default(prompt: &str, default: &str) -> String {
+    loop {
+        match Input::new().with_prompt(prompt).default(default.to_string()).interact_text() {
+            Ok(text) => return text,
+            Err(e) => {
+                Logger::log_error(&fill(messages().input_error, &[&e]));
+            }
+        }
+    }
+}
+
+fn prompt_user_with_suggestions(prompt: &str, suggestions: &KeywordSuggestions) -> String {
+    loop {
+        match Input::new().with_prompt(prompt).completion_with(suggestions).interact_text() {
+            Ok(text) => return text,
+            Err(e) => {
+                Logger::log_error(&fill(messages().input_error, &[&e]));
+            }
+        }
+    }
+}
+
+fn prompt_user_allow_empty(prompt: &str) -> String {
+    loop {
+        match Input::new().with_prompt(prompt).allow_empty(true).interact_text() {
+            Ok(text) => return text,
+            Err(e) => {
+                Logger::log_error(&fill(messages().input_error, &[&e]));
+            }
+        }
+    }
+}
+
+fn prompt_user_select<I: ToString, T>(prompt: &str, items: &[I], default: usize, value_at: impl FnOnce(usize) -> T) -> T {
+    let selection = Select::new()
+        .with_prompt(prompt)
+        .default(default)
+        .items(items)
+        .interact()
+        .unwrap();
+    value_at(selection)
+}
+
+fn prompt_user_option<T: Copy + PartialEq + ToString>(prompt: &str, options: &[T], current: T) -> T {
+    let default = options.iter().position(|option| *option == current).unwrap_or(0);
+    prompt_user_select(prompt, options, default, |selection| options[selection])
+}
+
+type SourceRegistration = fn(&Settings) -> Option<Box<dyn ImageSource>>;
+
+fn register_valid_source(settings: &Settings) -> Option<Box<dyn ImageSource>> {
+    if MockSource::is_enabled_by_env() {
+        Logger::log_info(&fill(messages().mock_source_enabled, &[&MockSource::ENABLE_VARIABLE]));
+        return Some(Box::new(MockSource::default()));
+    }
+    let sources: &[(&str, SourceRegistration)] = &[
+        (messages().source_search, |settings| Some(Box::new(register_valid_downloader(settings)))),
+        #[cfg(feature = "screen-capture")]
+        (messages().source_screen, register_valid_screen_capture),
+        #[cfg(feature = "webcam")]
+        (messages().source_webcam, register_valid_webcam),
+        #[cfg(feature = "mock-source")]
+        (messages().source_mock, |_| Some(Box::new(MockSource::default()))),
+    ];
+    if sources.len() == 1 {
+        return (sources[0].1)(settings);
+    }
+    let items: Vec<&str> = sources.iter().map(|(name, _)| *name).collect();
+    prompt_user_select(messages().prompt_source, &items, 0, |selection| (sources[selection].1)(settings))
+}
+
+#[cfg(feature = "screen-capture")]
+fn register_valid_screen_capture(_: &Settings) -> Option<Box<dyn ImageSource>> {
+    let screens = match ScreenCapture::screen_descriptions() {
+        Ok(screens) => screens,
+        Err(e) => {
+            Logger::log_error(&e.to_string());
+            return None;
+        }
+    };
+    let screen_index = prompt_user_select(messages().prompt_screen, &screens, 0, |selection| selection);
+    loop {
+        let region = prompt_user_allow_empty(messages().prompt_capture_region);
+        let region = if region.trim().is_empty() {
+            None
+        } else {
+            match region.parse::<CaptureRegion>() {
+                Ok(region) => Some(region),
+                Err(e) => {
+                    Logger::log_error(&e.to_string());
+                    continue;
+                }
+            }
+        };
+        match ScreenCapture::new(screen_index, region) {
+            Ok(screen_capture) => return Some(Box::new(screen_capture)),
+            Err(e) => Logger::log_error(&e.to_string()),
+        }
+    }
+}
+
+#[cfg(feature = "webcam")]
+fn register_valid_webcam(_: &Settings) -> Option<Box<dyn ImageSource>> {
+    let cameras = match Webcam::camera_descriptions() {
+        Ok(cameras) => cameras,
+        Err(e) => {
+            Logger::log_error(&e.to_string());
+            return None;
+        }
+    };
+    let items: Vec<&str> = cameras.iter().map(|(_, name)| name.as_str()).collect();
+    let camera_index = prompt_user_select(messages().prompt_webcam, &items, 0, |selection| cameras[selection].0);
+    match Webcam::new(camera_index) {
+        Ok(webcam) => Some(Box::new(webcam)),
+        Err(e) => {
+            Logger::log_error(&e.to_string());
+            None
+        }
+    }
+}
+
+fn prompt_for_keyword(history: Option<&KeywordHistory>, online_suggestions: bool) -> String {
+    let recent_keywords = history.map(KeywordHistory::keywords).unwrap_or_default();
+    let suggestions = KeywordSuggestions::new(recent_keywords, online_suggestions);
+    if recent_keywords.is_empty() {
+        return prompt_user_with_suggestions(messages().prompt_keyword, &suggestions);
+    }
+    let mut items = vec![messages().keyword_new];
+    items.extend(recent_keywords.iter().map(String::as_str));
+    prompt_user_select(messages().prompt_recent_keyword, &items, 0, |selection| match selection {
+        0 => prompt_user_with_suggestions(messages().prompt_keyword, &suggestions),
+        index => recent_keywords[index - 1].clone(),
+    })
+}
+
+fn register_valid_downloader(settings: &Settings) -> ImageDownloader {
+    let mut history = KeywordHistory::load()
+        .map_err(|e| Logger::log_error(&e.to_string()))
+        .ok();
+    loop {
+        let keyword = prompt_for_keyword(history.as_ref(), settings.online_suggestions);
+        match ImageDownloader::new(keyword.clone(), &settings.host_filter, &settings.search_options) {
+            Ok(downloader) => {
+                if let Some(history) = history.as_mut() {
+                    history.record(&keyword);
+                    if let Err(e) = history.save() {
+                        Logger::log_error(&e.to_string());
+                    }
+                }
+                return downloader;
+            }
+            Err(error) => Logger::log_error(&error.to_string()),
+        }
+    }
+}
+
+fn register_valid_printing_rate() -> u16 {
+    loop {
+        let rate = prompt_user(messages().prompt_printing_rate);
+        match rate.trim().parse::<u16>() {
+            Ok(rate) => return rate,
+            Err(_) => Logger::log_error(messages().invalid_printing_rate),
+        }
+    }
+}
+
+fn register_valid_character_ramp() -> CharacterRamp {
+    let text = messages();
+    let items = vec![text.ramp_preset_classic, text.ramp_preset_shading, text.ramp_preset_custom];
+    let preset = prompt_user_select(text.prompt_ramp_preset, &items, 0, |selection| match selection {
+        0 => Some(CharacterRamp::default()),
+        1 => Some(CharacterRamp::shading()),
+        _ => None,
+    });
+    if let Some(ramp) = preset {
+        return ramp;
+    }
+    loop {
+        let ramp = prompt_user_allow_empty(text.prompt_character_ramp);
+        let ramp = if ramp.is_empty() {
+            Ok(CharacterRamp::default())
+        } else {
+            ramp.parse::<CharacterRamp>()
+        };
+        match ramp {
+            Ok(ramp) => return ramp,
+            Err(e) => Logger::log_error(&e.to_string()),
+        }
+    }
+}
+
+fn register_valid_saturation() -> f32 {
+    let max_saturation = ConverterOptions::MAX_SATURATION;
+    loop {
+        let saturation = prompt_user(&fill(messages().prompt_saturation, &[&max_saturation]));
+        match saturation.trim().parse::<f32>() {
+            Ok(saturation) if (0.0..=max_saturation).contains(&saturation) => return saturation,
+            _ => Logger::log_error(&fill(messages().invalid_saturation, &[&max_saturation])),
+        }
+    }
+}
+
+fn register_valid_detail_threshold() -> Option<u32> {
+    let max_threshold = ConverterOptions::MAX_DETAIL_THRESHOLD;
+    loop {
+        let threshold = prompt_user_allow_empty(&fill(messages().prompt_detail_threshold, &[&ConverterOptions::DEFAULT_DETAIL_THRESHOLD]));
+        if threshold.trim().is_empty() {
+            return None;
+        }
+        match threshold.trim().parse::<u32>() {
+            Ok(threshold) if (1..=max_threshold).contains(&threshold) => return Some(threshold),
+            _ => Logger::log_error(&fill(messages().invalid_detail_threshold, &[&max_threshold])),
+        }
+    }
+}
+
+fn register_valid_slideshow_interval() -> u16 {
+    let max_interval = Settings::MAX_SLIDESHOW_INTERVAL_S;
+    loop {
+        let interval = prompt_user(&fill(messages().prompt_slideshow_interval, &[&Settings::DEFAULT_SLIDESHOW_INTERVAL_S]));
+        match interval.trim().parse::<u16>() {
+            Ok(interval) if (1..=max_interval).contains(&interval) => return interval,
+            _ => Logger::log_error(&fill(messages().invalid_slideshow_interval, &[&max_interval])),
+        }
+    }
+}
+
+fn register_valid_image_cache_limit() -> u16 {
+    loop {
+        let limit = prompt_user(&fill(messages().prompt_image_cache_limit, &[&Settings::DEFAULT_IMAGE_CACHE_LIMIT]));
+        match limit.trim().parse::<u16>() {
+            Ok(limit) => return limit,
+            _ => Logger::log_error(&fill(messages().invalid_image_cache_limit, &[&u16::MAX])),
+        }
+    }
+}
+
+fn register_valid_language() -> Language {
+    let items: Vec<&str> = Language::ALL.iter().map(Language::name).collect();
+    let current = Language::ALL.iter().position(|language| *language == Language::current()).unwrap_or(0);
+    prompt_user_select(messages().prompt_language, &items, current, |selection| Language::ALL[selection])
+}
+
+fn register_valid_render_style(current: RenderStyle) -> RenderStyle {
+    prompt_user_option(messages().prompt_render_style, &RenderStyle::ALL, current)
+}
+
+fn register_valid_colour_mode(current: ColourMode) -> ColourMode {
+    prompt_user_option(messages().prompt_colour_mode, &ColourMode::ALL, current)
+}
+
+fn register_valid_palette(current: Palette) -> Palette {
+    prompt_user_option(messages().prompt_palette, &Palette::ALL, current)
+}
+
+fn register_valid_dithering(current: Dithering) -> Dithering {
+    prompt_user_option(messages().prompt_dithering, &Dithering::ALL, current)
+}
+
+fn register_valid_luminance(current: Luminance) -> Luminance {
+    prompt_user_option(messages().prompt_luminance, &Luminance::ALL, current)
+}
+
+fn register_valid_inversion(current: Inversion) -> Inversion {
+    prompt_user_option(messages().prompt_inversion, &Inversion::ALL, current)
+}
+
+fn register_valid_alpha_mode(current: AlphaMode) -> AlphaMode {
+    prompt_user_option(messages().prompt_alpha, &AlphaMode::ALL, current)
+}
+
+fn register_valid_alpha_background(current: Colour) -> Colour {
+    loop {
+        let colour = prompt_user_with_default(messages().prompt_alpha_background, &current.hex());
+        match Colour::from_hex(&colour) {
+            Some(colour) => return colour,
+            None => Logger::log_error(messages().invalid_alpha_background),
+        }
+    }
+}
+
+fn register_valid_duotone() -> Option<Duotone> {
+    loop {
+        let duotone = prompt_user_allow_empty(&fill(messages().prompt_duotone, &[&Duotone::EXAMPLE]));
+        if duotone.trim().is_empty() {
+            return None;
+        }
+        match duotone.parse::<Duotone>() {
+            Ok(duotone) => return Some(duotone),
+            Err(e) => Logger::log_error(&e.to_string()),
+        }
+    }
+}
+
+fn register_valid_transform(current: Transform) -> Transform {
+    let text = messages();
+    let rotations: Vec<String> = Transform::ROTATIONS.iter().map(|rotation| format!("{}°", rotation)).collect();
+    let current_rotation = Transform::ROTATIONS.iter().position(|rotation| *rotation == current.rotation()).unwrap_or(0);
+    let rotation = prompt_user_select(text.prompt_rotation, &rotations, current_rotation, |selection| Transform::ROTATIONS[selection]);
+    let flips = MultiSelect::new()
+        .with_prompt(text.prompt_flips)
+        .items_checked(&[
+            (text.transform_flipped_horizontally, current.is_flipped_horizontally()),
+            (text.transform_flipped_vertically, current.is_flipped_vertically()),
+        ])
+        .interact()
+        .unwrap();
+    Transform::new(rotation, flips.contains(&0), flips.contains(&1))
+}
+
+fn register_valid_caption(current: &Caption) -> Caption {
+    let text = messages();
+    let items = vec![text.caption_off, text.caption_image_name, text.caption_custom];
+    let current_index = match current {
+        Caption::Off => 0,
+        Caption::ImageName => 1,
+        Caption::Text(_) => 2,
+    };
+    prompt_user_select(text.prompt_caption, &items, current_index, |selection| match selection {
+        0 => Caption::Off,
+        1 => Caption::ImageName,
+        2 => Caption::Text(prompt_user(text.prompt_caption_text)),
+        _ => unreachable!(),
+    })
+}
+
+fn register_valid_effects(current: &EffectChain) -> EffectChain {
+    let text = messages();
+    let items: Vec<(Effect, bool)> = Effect::ALL
+        .iter()
+        .map(|effect| (*effect, current.effects().contains(effect)))
+        .collect();
+    let selection = MultiSelect::new()
+        .with_prompt(text.prompt_effects)
+        .items_checked(&items)
+        .interact()
+        .unwrap();
+    let mut effects: Vec<Effect> = selection.into_iter().map(|index| Effect::ALL[index]).collect();
+    if effects.len() > 1 {
+        let order = Sort::new()
+            .with_prompt(text.prompt_effects_order)
+            .items(&effects)
+            .interact()
+            .unwrap();
+        effects = order.into_iter().map(|index| effects[index]).collect();
+    }
+    EffectChain::new(effects)
+}
+
+fn register_valid_reveal_style(current: RevealStyle) -> RevealStyle {
+    prompt_user_option(messages().prompt_reveal_style, &RevealStyle::ALL, current)
+}
+
+fn register_valid_clear_mode(current: ClearMode) -> ClearMode {
+    prompt_user_option(messages().prompt_clear_mode, &ClearMode::ALL, current)
+}
+
+fn register_valid_sound_feedback() -> SoundFeedback {
+    let items = vec![
+        messages().sound_option_off,
+        messages().sound_option_bell,
+        messages().sound_option_command,
+    ];
+    prompt_user_select(messages().prompt_sound, &items, 0, |selection| match selection {
+        0 => SoundFeedback::Off,
+        1 => SoundFeedback::Bell,
+        2 => SoundFeedback::Command(prompt_user(messages().prompt_sound_command)),
+        _ => unreachable!(),
+    })
+}
+
+struct Settings {
+    save_location: String,
+    load_location: String,
+    default_width: Option<WidthSpec>,
+    printing_rate_ms: u16,
+    reveal_style: RevealStyle,
+    character_ramp: CharacterRamp,
+    render_style: RenderStyle,
+    colour_mode: ColourMode,
+    palette: Palette,
+    detail_threshold: Option<u32>,
+    slideshow_interval_s: u16,
+    shuffle_load_order: bool,
+    image_cache_limit: u16,
+    dithering: Dithering,
+    auto_levels: bool,
+    double_width: bool,
+    luminance: Luminance,
+    size_limits: SizeLimits,
+    saturation: f32,
+    inversion: Inversion,
+    effects: EffectChain,
+    duotone: Option<Duotone>,
+    alpha_mode: AlphaMode,
+    alpha_background: Colour,
+    transform: Transform,
+    caption: Caption,
+    clear_mode: ClearMode,
+    show_timings: bool,
+    online_suggestions: bool,
+    host_filter: HostFilter,
+    search_options: SearchOptions,
+    cell_aspect_ratio: f32,
+    sound: SoundFeedback,
+    config: Config,
+}
+
+impl Settings {
+    const CELL_ASPECT_RATIO_KEY: &'static str = "cell_aspect_ratio";
+    const SEARCH_URL_TEMPLATE_KEY: &'static str = "search_url_template";
+    const IMAGE_SELECTOR_KEY: &'static str = "image_selector";
+    const CHARACTER_RAMP_KEY: &'static str = "character_ramp";
+    const RENDER_STYLE_KEY: &'static str = "render_style";
+    const COLOUR_MODE_KEY: &'static str = "colour_mode";
+    const PALETTE_KEY: &'static str = "palette";
+    const LUMINANCE_KEY: &'static str = "luminance";
+    const SATURATION_KEY: &'static str = "saturation";
+    const DETAIL_THRESHOLD_KEY: &'static str = "detail_threshold";
+    const SLIDESHOW_INTERVAL_KEY: &'static str = "slideshow_interval";
+    const DEFAULT_SLIDESHOW_INTERVAL_S: u16 = 5;
+    const MAX_SLIDESHOW_INTERVAL_S: u16 = 3600;
+    const SHUFFLE_LOAD_KEY: &'static str = "shuffle_load_order";
+    const SOUND_KEY: &'static str = "sound";
+    const SOUND_COMMAND_KEY: &'static str = "sound_command";
+    const IMAGE_CACHE_LIMIT_KEY: &'static str = "image_cache_limit";
+    const DEFAULT_IMAGE_CACHE_LIMIT: u16 = 50;
+    const INVERSION_KEY: &'static str = "inversion";
+    const EFFECTS_KEY: &'static str = "effects";
+    const DUOTONE_KEY: &'static str = "duotone";
+    const ALPHA_MODE_KEY: &'static str = "alpha_mode";
+    const ALPHA_BACKGROUND_KEY: &'static str = "alpha_background";
+    const MAX_WIDTH_KEY: &'static str = "max_width";
+    const MAX_DECODED_PIXELS_KEY: &'static str = "max_decoded_pixels";
+    const MAX_CELLS_KEY: &'static str = "max_cells";
+    const CLEAR_MODE_KEY: &'static str = "clear_mode";
+    const REVEAL_STYLE_KEY: &'static str = "reveal_style";
+    const ALLOWED_HOSTS_KEY: &'static str = "allowed_hosts";
+    const DENIED_HOSTS_KEY: &'static str = "denied_hosts";
+    const SAVE_LOCATION_KEY: &'static str = "save_location";
+    const LOAD_LOCATION_KEY: &'static str = "load_location";
+    const DEFAULT_WIDTH_KEY: &'static str = "default_width";
+
+    fn printer_options(&self) -> PrinterOptions {
+        PrinterOptions {
+            printing_rate_ms: self.printing_rate_ms,
+            reveal_style: self.reveal_style,
+            show_timings: self.show_timings,
+            cell_aspect_ratio: self.cell_aspect_ratio,
+            clear_mode: self.clear_mode,
+            cache_limit: self.image_cache_limit as usize,
+        }
+    }
+
+    fn converter_options(&self) -> ConverterOptions {
+        ConverterOptions {
+            character_ramp: self.character_ramp.clone(),
+            render_style: self.render_style,
+            colour_mode: self.colour_mode,
+            palette: self.palette,
+            detail_threshold: self.detail_threshold,
+            dithering: self.dithering,
+            auto_levels: self.auto_levels,
+            double_width: self.double_width,
+            luminance: self.luminance,
+            size_limits: self.size_limits,
+            saturation: self.saturation,
+            inversion: self.inversion,
+            effects: self.effects.clone(),
+            duotone: self.duotone,
+            alpha_mode: self.alpha_mode,
+            alpha_background: self.alpha_background,
+            transform: self.transform,
+            crop: None,
+            caption: self.caption.clone(),
+            cell_aspect_ratio: self.cell_aspect_ratio,
+        }
+    }
+
+    fn load_cell_aspect_ratio(config: &Config) -> f32 {
+        config
+            .get(&Config::terminal_section(), Self::CELL_ASPECT_RATIO_KEY)
+            .and_then(|ratio| ratio.parse::<f32>().ok())
+            .filter(|ratio| *ratio > 0.0)
+            .unwrap_or(ConverterOptions::DEFAULT_CELL_ASPECT_RATIO)
+    }
+
+    fn load_location(config: &Config, key: &str) -> io::Result<String> {
+        match config.get(Config::GLOBAL_SECTION, key) {
+            Some(location) => Ok(location.to_string()),
+            None => Ok(env::current_dir()?.to_str().unwrap().to_string()),
+        }
+    }
+
+    fn store_locations(&mut self) -> Result<(), config::ConfigError> {
+        self.config.set(Config::GLOBAL_SECTION, Self::SAVE_LOCATION_KEY, &self.save_location);
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::LOAD_LOCATION_KEY, &self.load_location)
+    }
+
+    fn load_default_width(config: &Config) -> Option<WidthSpec> {
+        config
+            .get(Config::GLOBAL_SECTION, Self::DEFAULT_WIDTH_KEY)?
+            .parse::<WidthSpec>()
+            .ok()
+    }
+
+    fn store_default_width(&mut self) -> Result<(), config::ConfigError> {
+        let default_width = self.default_width.map(|width| width.to_string()).unwrap_or_default();
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::DEFAULT_WIDTH_KEY, &default_width)
+    }
+
+    fn load_search_options(config: &Config) -> SearchOptions {
+        let mut search_options = SearchOptions::default();
+        if let Some(template) = config.get(Config::GLOBAL_SECTION, Self::SEARCH_URL_TEMPLATE_KEY) {
+            search_options.set_search_url_template(template);
+        }
+        if let Some(selector) = config.get(Config::GLOBAL_SECTION, Self::IMAGE_SELECTOR_KEY) {
+            search_options.set_image_selector(selector);
+        }
+        search_options
+    }
+
+    fn load_host_filter(config: &Config) -> HostFilter {
+        let mut host_filter = HostFilter::default();
+        if let Some(hosts) = config.get(Config::GLOBAL_SECTION, Self::ALLOWED_HOSTS_KEY) {
+            host_filter.set_allowed_hosts(hosts);
+        }
+        if let Some(hosts) = config.get(Config::GLOBAL_SECTION, Self::DENIED_HOSTS_KEY) {
+            host_filter.set_denied_hosts(hosts);
+        }
+        host_filter
+    }
+
+    fn store_host_filter(&mut self) -> Result<(), config::ConfigError> {
+        self.config.set(Config::GLOBAL_SECTION, Self::ALLOWED_HOSTS_KEY, &self.host_filter.allowed_hosts().join(","));
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::DENIED_HOSTS_KEY, &self.host_filter.denied_hosts().join(","))
+    }
+
+    fn load_character_ramp(config: &Config) -> CharacterRamp {
+        let ramp = match config.get(Config::GLOBAL_SECTION, Self::CHARACTER_RAMP_KEY) {
+            Some(ramp) => ramp,
+            None => return CharacterRamp::default(),
+        };
+        let ramp = ramp
+            .strip_prefix('"')
+            .and_then(|ramp| ramp.strip_suffix('"'))
+            .unwrap_or(ramp);
+        ramp.parse::<CharacterRamp>().unwrap_or_else(|e| {
+            Logger::log_error(&e.to_string());
+            CharacterRamp::default()
+        })
+    }
+
+    fn load_render_style(config: &Config) -> RenderStyle {
+        config
+            .get(Config::GLOBAL_SECTION, Self::RENDER_STYLE_KEY)
+            .and_then(RenderStyle::from_code)
+            .unwrap_or_default()
+    }
+
+    fn store_render_style(&mut self) -> Result<(), config::ConfigError> {
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::RENDER_STYLE_KEY, self.render_style.code())
+    }
+
+    fn load_colour_mode(config: &Config) -> ColourMode {
+        config
+            .get(Config::GLOBAL_SECTION, Self::COLOUR_MODE_KEY)
+            .and_then(ColourMode::from_code)
+            .unwrap_or_default()
+    }
+
+    fn store_colour_mode(&mut self) -> Result<(), config::ConfigError> {
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::COLOUR_MODE_KEY, self.colour_mode.code())
+    }
+
+    fn load_palette(config: &Config) -> Palette {
+        config
+            .get(Config::GLOBAL_SECTION, Self::PALETTE_KEY)
+            .and_then(Palette::from_code)
+            .unwrap_or_default()
+    }
+
+    fn store_palette(&mut self) -> Result<(), config::ConfigError> {
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::PALETTE_KEY, self.palette.code())
+    }
+
+    fn load_size_limits(config: &Config) -> SizeLimits {
+        let mut size_limits = SizeLimits::default();
+        let limit = |key: &str| config.get(Config::GLOBAL_SECTION, key).and_then(|value| value.parse::<u64>().ok()).filter(|value| *value > 0);
+        if let Some(max_width) = limit(Self::MAX_WIDTH_KEY) {
+            size_limits.max_width = max_width.min(u32::MAX as u64) as u32;
+        }
+        if let Some(max_decoded_pixels) = limit(Self::MAX_DECODED_PIXELS_KEY) {
+            size_limits.max_decoded_pixels = max_decoded_pixels;
+        }
+        if let Some(max_cells) = limit(Self::MAX_CELLS_KEY) {
+            size_limits.max_cells = max_cells;
+        }
+        size_limits
+    }
+
+    fn load_saturation(config: &Config) -> f32 {
+        config
+            .get(Config::GLOBAL_SECTION, Self::SATURATION_KEY)
+            .and_then(|saturation| saturation.parse::<f32>().ok())
+            .filter(|saturation| (0.0..=ConverterOptions::MAX_SATURATION).contains(saturation))
+            .unwrap_or(ConverterOptions::DEFAULT_SATURATION)
+    }
+
+    fn store_saturation(&mut self) -> Result<(), config::ConfigError> {
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::SATURATION_KEY, &self.saturation.to_string())
+    }
+
+    fn load_detail_threshold(config: &Config) -> Option<u32> {
+        config
+            .get(Config::GLOBAL_SECTION, Self::DETAIL_THRESHOLD_KEY)
+            .and_then(|threshold| threshold.parse::<u32>().ok())
+            .filter(|threshold| (1..=ConverterOptions::MAX_DETAIL_THRESHOLD).contains(threshold))
+    }
+
+    fn store_detail_threshold(&mut self) -> Result<(), config::ConfigError> {
+        let threshold = self.detail_threshold.map(|threshold| threshold.to_string()).unwrap_or_default();
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::DETAIL_THRESHOLD_KEY, &threshold)
+    }
+
+    fn load_slideshow_interval(config: &Config) -> u16 {
+        config
+            .get(Config::GLOBAL_SECTION, Self::SLIDESHOW_INTERVAL_KEY)
+            .and_then(|interval| interval.parse::<u16>().ok())
+            .filter(|interval| (1..=Self::MAX_SLIDESHOW_INTERVAL_S).contains(interval))
+            .unwrap_or(Self::DEFAULT_SLIDESHOW_INTERVAL_S)
+    }
+
+    fn store_slideshow_interval(&mut self) -> Result<(), config::ConfigError> {
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::SLIDESHOW_INTERVAL_KEY, &self.slideshow_interval_s.to_string())
+    }
+
+    fn load_shuffle_load_order(config: &Config) -> bool {
+        config
+            .get(Config::GLOBAL_SECTION, Self::SHUFFLE_LOAD_KEY)
+            .and_then(|shuffle| shuffle.parse::<bool>().ok())
+            .unwrap_or_default()
+    }
+
+    fn store_shuffle_load_order(&mut self) -> Result<(), config::ConfigError> {
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::SHUFFLE_LOAD_KEY, &self.shuffle_load_order.to_string())
+    }
+
+    fn load_sound(config: &Config) -> SoundFeedback {
+        config
+            .get(Config::GLOBAL_SECTION, Self::SOUND_KEY)
+            .and_then(|sound| SoundFeedback::from_code(sound, config.get(Config::GLOBAL_SECTION, Self::SOUND_COMMAND_KEY)))
+            .unwrap_or_default()
+    }
+
+    fn store_sound(&mut self) -> Result<(), config::ConfigError> {
+        self.config.set(Config::GLOBAL_SECTION, Self::SOUND_KEY, self.sound.code());
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::SOUND_COMMAND_KEY, self.sound.command())
+    }
+
+    fn load_image_cache_limit(config: &Config) -> u16 {
+        config
+            .get(Config::GLOBAL_SECTION, Self::IMAGE_CACHE_LIMIT_KEY)
+            .and_then(|limit| limit.parse::<u16>().ok())
+            .unwrap_or(Self::DEFAULT_IMAGE_CACHE_LIMIT)
+    }
+
+    fn store_image_cache_limit(&mut self) -> Result<(), config::ConfigError> {
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::IMAGE_CACHE_LIMIT_KEY, &self.image_cache_limit.to_string())
+    }
+
+    fn load_inversion(config: &Config) -> Inversion {
+        config
+            .get(Config::GLOBAL_SECTION, Self::INVERSION_KEY)
+            .and_then(Inversion::from_code)
+            .unwrap_or_default()
+    }
+
+    fn store_inversion(&mut self) -> Result<(), config::ConfigError> {
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::INVERSION_KEY, self.inversion.code())
+    }
+
+    fn load_effects(config: &Config) -> EffectChain {
+        config
+            .get(Config::GLOBAL_SECTION, Self::EFFECTS_KEY)
+            .and_then(EffectChain::from_code)
+            .unwrap_or_default()
+    }
+
+    fn store_effects(&mut self) -> Result<(), config::ConfigError> {
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::EFFECTS_KEY, &self.effects.code())
+    }
+
+    fn load_duotone(config: &Config) -> Option<Duotone> {
+        config
+            .get(Config::GLOBAL_SECTION, Self::DUOTONE_KEY)
+            .and_then(|duotone| duotone.parse::<Duotone>().ok())
+    }
+
+    fn store_duotone(&mut self) -> Result<(), config::ConfigError> {
+        let duotone = self.duotone.map(|duotone| duotone.code()).unwrap_or_default();
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::DUOTONE_KEY, &duotone)
+    }
+
+    fn load_alpha_mode(config: &Config) -> AlphaMode {
+        config
+            .get(Config::GLOBAL_SECTION, Self::ALPHA_MODE_KEY)
+            .and_then(AlphaMode::from_code)
+            .unwrap_or_default()
+    }
+
+    fn load_alpha_background(config: &Config) -> Colour {
+        config
+            .get(Config::GLOBAL_SECTION, Self::ALPHA_BACKGROUND_KEY)
+            .and_then(Colour::from_hex)
+            .unwrap_or(ConverterOptions::DEFAULT_ALPHA_BACKGROUND)
+    }
+
+    fn store_alpha(&mut self) -> Result<(), config::ConfigError> {
+        self.config.set(Config::GLOBAL_SECTION, Self::ALPHA_MODE_KEY, self.alpha_mode.code());
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::ALPHA_BACKGROUND_KEY, &self.alpha_background.hex())
+    }
+
+    fn load_luminance(config: &Config) -> Luminance {
+        config
+            .get(Config::GLOBAL_SECTION, Self::LUMINANCE_KEY)
+            .and_then(Luminance::from_code)
+            .unwrap_or_default()
+    }
+
+    fn store_luminance(&mut self) -> Result<(), config::ConfigError> {
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::LUMINANCE_KEY, self.luminance.code())
+    }
+
+    fn load_clear_mode(config: &Config) -> ClearMode {
+        config
+            .get(Config::GLOBAL_SECTION, Self::CLEAR_MODE_KEY)
+            .and_then(ClearMode::from_code)
+            .unwrap_or_default()
+    }
+
+    fn store_clear_mode(&mut self) -> Result<(), config::ConfigError> {
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::CLEAR_MODE_KEY, self.clear_mode.code())
+    }
+
+    fn load_reveal_style(config: &Config) -> RevealStyle {
+        config
+            .get(Config::GLOBAL_SECTION, Self::REVEAL_STYLE_KEY)
+            .and_then(RevealStyle::from_code)
+            .unwrap_or_default()
+    }
+
+    fn store_reveal_style(&mut self) -> Result<(), config::ConfigError> {
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::REVEAL_STYLE_KEY, self.reveal_style.code())
+    }
+
+    fn store_character_ramp(&mut self) -> Result<(), config::ConfigError> {
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::CHARACTER_RAMP_KEY, &format!("\"{}\"", self.character_ramp))
+    }
+
+    fn store_value(config: &mut Config, section: &str, key: &str, value: &str) -> Result<(), config::ConfigError> {
+        config.set(section, key, value);
+        config.save()
+    }
+
+    fn store_cell_aspect_ratio(&mut self) -> Result<(), config::ConfigError> {
+        Self::store_value(&mut self.config, &Config::terminal_section(), Self::CELL_ASPECT_RATIO_KEY, &self.cell_aspect_ratio.to_string())
+    }
+
+    fn store_language(&mut self, language: Language) -> Result<(), config::ConfigError> {
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Language::CONFIG_KEY, language.code())
+    }
+
+    fn store_search_options(&mut self) -> Result<(), config::ConfigError> {
+        self.config.set(Config::GLOBAL_SECTION, Self::SEARCH_URL_TEMPLATE_KEY, self.search_options.search_url_template());
+        Self::store_value(&mut self.config, Config::GLOBAL_SECTION, Self::IMAGE_SELECTOR_KEY, self.search_options.image_selector())
+    }
+}
+
+const RAMP_FLAG: &str = "--ramp";
+const REPLAY_FLAG: &str = "--replay";
+
+#[derive(Default)]
+struct Arguments {
+    character_ramp: Option<CharacterRamp>,
+    replay_path: Option<String>,
+}
+
+fn flag_value(arg: &str, flag: &str, args: &mut impl Iterator<Item = String>) -> Option<Option<String>> {
+    if arg == flag {
+        Some(args.next())
+    } else {
+        arg.strip_prefix(flag)
+            .and_then(|rest| rest.strip_prefix('='))
+            .map(|value| Some(value.to_string()))
+    }
+}
+
+fn arguments_from_args() -> Arguments {
+    let mut arguments = Arguments::default();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if let Some(ramp) = flag_value(&arg, RAMP_FLAG, &mut args) {
+            match ramp.map(|ramp| ramp.parse::<CharacterRamp>()) {
+                Some(Ok(ramp)) => arguments.character_ramp = Some(ramp),
+                Some(Err(e)) => Logger::log_error(&e.to_string()),
+                None => Logger::log_error(&fill(messages().missing_argument_value, &[&RAMP_FLAG])),
             }
+        } else if let Some(path) = flag_value(&arg, REPLAY_FLAG, &mut args) {
+            match path {
+                Some(path) => arguments.replay_path = Some(path),
+                None => {
+                    Logger::log_error(&fill(messages().missing_argument_value, &[&REPLAY_FLAG]));
+                    exit(Replay::USAGE_EXIT_CODE);
+                }
+            }
+        } else {
+            Logger::log_error(&fill(messages().unknown_argument, &[&arg]));
         }
     }
+    arguments
 }
 
-fn register_valid_downloader() -> ImageDownloader {
-    loop {
-        let keyword = prompt_user("Enter keyword");
-        match ImageDownloader::new(keyword) {
-            Ok(downloader) => return downloader,
-            Err(error) => Logger::log_error(&error.to_string()),
+fn replay(image_path: &str) -> ! {
+    match Replay::run(image_path) {
+        Ok(()) => exit(Replay::SUCCESS_EXIT_CODE),
+        Err(e) => {
+            Logger::log_error(&e.to_string());
+            exit(Replay::LOAD_FAILURE_EXIT_CODE)
         }
     }
 }
 
-fn register_valid_printing_rate() -> u16 {
+const BANNER_COLOURS: [(u8, u8, u8); 7] = [
+    (255, 0, 0),
+    (255, 127, 0),
+    (255, 255, 0),
+    (0, 255, 0),
+    (56, 241, 255),
+    (135, 255, 219),
+    (148, 0, 211),
+];
+
+fn banner() -> String {
+    messages()
+        .banner
+        .chars()
+        .zip(BANNER_COLOURS.iter().cycle())
+        .map(|(c, (r, g, b))| format!("\x1B[38;2;{};{};{}m{}\x1B[0m", r, g, b, c))
+        .collect()
+}
+
+
+fn calibrate_cell_aspect_ratio(settings: &mut Settings) {
+    let text = messages();
+    match FontCalibration::new(settings.cell_aspect_ratio).run() {
+        Ok(Some(cell_aspect_ratio)) => {
+            settings.cell_aspect_ratio = cell_aspect_ratio;
+            Logger::log_info(&fill(text.aspect_ratio_changed, &[&format!("{:.3}", settings.cell_aspect_ratio)]));
+            if let Err(e) = settings.store_cell_aspect_ratio() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        Ok(None) => Logger::log_info(text.calibration_cancelled),
+        Err(e) => Logger::log_error(&e.to_string()),
+    }
+}
+
+fn prepare_directory(prompt: &str, current: &str) -> String {
     loop {
-        let rate = prompt_user("Enter new printing rate in milliseconds (default is 5 ms)");
-        match rate.trim().parse::<u16>() {
-            Ok(rate) => return rate,
-            Err(_) => Logger::log_error("Invalid printing rate. Please enter an integer [0 - 655635]."),
+        let location = prompt_user_with_default(prompt, current);
+        match fs::create_dir_all(&location) {
+            Ok(()) => {
+                Logger::log_info(&fill(messages().wizard_directory_ready, &[&location]));
+                return location;
+            }
+            Err(e) => Logger::log_error(&fill(messages().wizard_directory_error, &[&location, &e])),
         }
     }
 }
 
-struct Settings {
-    save_location: String,
-    load_location: String,
-    printing_rate_ms: u16
-}
-
-const BANNER: &'static str =
-"\x1B[38;2;255;0;0mW\x1B[0m\
-\x1B[38;2;255;127;0me\x1B[0m\
-\x1B[38;2;255;255;0ml\x1B[0m\
-\x1B[38;2;0;255;0mc\x1B[0m\
-\x1B[38;2;56;241;255mo\x1B[0m\
-\x1B[38;2;135;255;219mm\x1B[0m\
-\x1B[38;2;148;0;211me\x1B[0m\
-\x1B[38;2;255;0;0m \x1B[0m\
-\x1B[38;2;255;127;0mt\x1B[0m\
-\x1B[38;2;255;255;0mo\x1B[0m\
-\x1B[38;2;0;255;0m \x1B[0m\
-\x1B[38;2;56;241;255mC\x1B[0m\
-\x1B[38;2;135;255;219mo\x1B[0m\
-\x1B[38;2;148;0;211ml\x1B[0m\
-\x1B[38;2;255;0;0mo\x1B[0m\
-\x1B[38;2;255;127;0mu\x1B[0m\
-\x1B[38;2;255;255;0mr\x1B[0m\
-\x1B[38;2;0;255;0mf\x1B[0m\
-\x1B[38;2;56;241;255mu\x1B[0m\
-\x1B[38;2;135;255;219ml\x1B[0m\
-\x1B[38;2;148;0;211m \x1B[0m\
-\x1B[38;2;255;0;0mW\x1B[0m\
-\x1B[38;2;255;127;0mo\x1B[0m\
-\x1B[38;2;255;255;0mr\x1B[0m\
-\x1B[38;2;0;255;0md\x1B[0m\
-\x1B[38;2;56;241;255ms\x1B[0m\
-\x1B[38;2;135;255;219m!\x1B[0m";
+fn run_setup_wizard(settings: &mut Settings) {
+    let text = messages();
+    Logger::log_info(text.wizard_welcome);
+    let (columns, rows) = terminal::size().unwrap_or_default();
+    Logger::log_info(&fill(text.wizard_terminal, &[&columns, &rows, &ColourMode::detect()]));
+
+    Logger::log_info(text.wizard_calibration);
+    calibrate_cell_aspect_ratio(settings);
+
+    let mut presets: Vec<String> = WidthSpec::PRESETS
+        .iter()
+        .map(|(name, percentage)| fill(text.wizard_width_preset, &[name, percentage]))
+        .collect();
+    presets.push(text.wizard_width_fit.to_string());
+    let selection = Select::new()
+        .with_prompt(text.wizard_prompt_width)
+        .items(&presets)
+        .default(1)
+        .interact()
+        .unwrap();
+    settings.default_width = match WidthSpec::PRESETS.get(selection) {
+        Some((_, percentage)) => Some(WidthSpec::TerminalPercentage(*percentage)),
+        None => Some(WidthSpec::FitTerminal),
+    };
+    Logger::log_info(&fill(text.default_width_changed, &[&presets[selection]]));
+    if let Err(e) = settings.store_default_width() {
+        Logger::log_error(&e.to_string());
+    }
+
+    settings.save_location = prepare_directory(text.wizard_prompt_save_location, &settings.save_location);
+    settings.load_location = prepare_directory(text.wizard_prompt_load_location, &settings.load_location);
+    if let Err(e) = settings.store_locations() {
+        Logger::log_error(&e.to_string());
+    }
 
+    Logger::log_info(text.wizard_checking_browser);
+    match ImageDownloader::check_browser() {
+        Ok(()) => Logger::log_info(text.wizard_browser_available),
+        Err(e) => Logger::log_error(&fill(text.wizard_browser_unavailable, &[&e])),
+    }
+    Logger::log_info(text.wizard_complete);
+}
 
 fn main() -> io::Result<()> {
+    let config = Config::load();
+    Language::detect(config.as_ref().ok()).activate();
+    // Read once: a broken line is reported a single time, and every setting falls back to its default.
+    let config = config.unwrap_or_else(|e| {
+        Logger::log_error(&e.to_string());
+        Config::default()
+    });
+    let _terminal_guard = TerminalGuard::install();
+    let arguments = arguments_from_args();
+    if let Some(image_path) = &arguments.replay_path {
+        replay(image_path);
+    }
     let mut settings = Settings {
-        save_location: env::current_dir()?.to_str().unwrap().to_string(),
-        load_location: env::current_dir()?.to_str().unwrap().to_string(),
-        printing_rate_ms: 5
+        save_location: Settings::load_location(&config, Settings::SAVE_LOCATION_KEY)?,
+        load_location: Settings::load_location(&config, Settings::LOAD_LOCATION_KEY)?,
+        default_width: Settings::load_default_width(&config),
+        printing_rate_ms: 5,
+        reveal_style: Settings::load_reveal_style(&config),
+        character_ramp: arguments.character_ramp.unwrap_or_else(|| Settings::load_character_ramp(&config)),
+        render_style: Settings::load_render_style(&config),
+        colour_mode: Settings::load_colour_mode(&config),
+        palette: Settings::load_palette(&config),
+        detail_threshold: Settings::load_detail_threshold(&config),
+        slideshow_interval_s: Settings::load_slideshow_interval(&config),
+        shuffle_load_order: Settings::load_shuffle_load_order(&config),
+        image_cache_limit: Settings::load_image_cache_limit(&config),
+        dithering: Dithering::default(),
+        auto_levels: false,
+        double_width: false,
+        luminance: Settings::load_luminance(&config),
+        size_limits: Settings::load_size_limits(&config),
+        saturation: Settings::load_saturation(&config),
+        inversion: Settings::load_inversion(&config),
+        effects: Settings::load_effects(&config),
+        duotone: Settings::load_duotone(&config),
+        alpha_mode: Settings::load_alpha_mode(&config),
+        alpha_background: Settings::load_alpha_background(&config),
+        transform: Transform::default(),
+        caption: Caption::default(),
+        clear_mode: Settings::load_clear_mode(&config),
+        show_timings: false,
+        online_suggestions: false,
+        host_filter: Settings::load_host_filter(&config),
+        search_options: Settings::load_search_options(&config),
+        cell_aspect_ratio: Settings::load_cell_aspect_ratio(&config),
+        sound: Settings::load_sound(&config),
+        config,
     };
+    if Config::is_first_run() {
+        run_setup_wizard(&mut settings);
+    }
     loop {
         let items = vec![
-            "Generator mode",
-            "Load saved images",
-            "Change settings",
-            "Quit",
+            messages().main_menu_generator,
+            messages().main_menu_load,
+            messages().main_menu_settings,
+            messages().main_menu_quit,
         ];
         let selection = Select::new()
-            .with_prompt(BANNER)
+            .with_prompt(banner())
             .default(0)
             .items(&items)
             .interact()
@@ -120,28 +1094,27 @@ fn main() -> io::Result<()> {
             0 => {
                 match ImageStorage::new(settings.save_location.clone()) {
                     Ok(image_storage) => {
-                        let downloader: ImageDownloader = register_valid_downloader();
-                        let mut printer: Printer<Converter> =
-                            Printer::new(Converter::new(downloader, prompt_for_width()), settings.printing_rate_ms);
-                        printer_menu(&create_generator_menu(), &mut printer, &image_storage)?;
+                        if let Some(source) = register_valid_source(&settings) {
+                            let image_width = prompt_for_width(&settings.size_limits, settings.default_width);
+                            let converter_options = ConverterOptions { crop: prompt_for_crop(), ..settings.converter_options() };
+                            let mut printer: Printer<Converter> =
+                                Printer::new(Converter::new(source, image_width, converter_options), settings.printer_options())
+                                    .with_status_hints(messages().status_hints_generator)
+                                    .with_character_ramp(settings.character_ramp.clone())
+                                    .with_image_restorer(|converter, image_name, source, _| converter.reconvert(image_name, source?))
+                                    .with_cancellation_check(Converter::take_cancellation);
+                            let menu_result = printer_menu(&create_generator_menu(), &mut printer, &MenuContext { image_storage: &image_storage, settings: &settings });
+                            restore_screen(&mut printer);
+                            menu_result?;
+                            settings.printing_rate_ms = printer.printing_rate();
+                            log_session_report(&printer);
+                        }
                     }
                     Err(e) => Logger::log_error(&e.to_string()),
                 }
             }
             1 => {
-                match ImageStorage::new(settings.save_location.clone()) {
-                    Ok(image_storage) => {
-                        match image_storage.to_load_iterator(settings.load_location.as_str()) {
-                            Ok(img_loader) => {
-                                let mut printer: Printer<ValidImageLoadIterator> =
-                                    Printer::new(img_loader.wrap_into_valid(), settings.printing_rate_ms);
-                                printer_menu(&create_load_menu(), &mut printer, &image_storage)?;
-                            }
-                            Err(e) => Logger::log_error(&e.to_string()),
-                        }
-                    }
-                    Err(e) => Logger::log_error(&e.to_string()),
-                }
+                load_mode_menu(&mut settings)?;
             }
             2 => {
                 settings_menu(&mut settings);
@@ -154,75 +1127,418 @@ fn main() -> io::Result<()> {
     }
 }
 
+fn load_mode_menu(settings: &mut Settings) -> io::Result<()> {
+    let items = vec![
+        messages().load_menu_browse,
+        messages().load_menu_browse_favourites,
+        messages().load_menu_gallery_stats,
+        messages().go_back,
+    ];
+    let selection = Select::new()
+        .with_prompt(messages().main_menu_load)
+        .default(0)
+        .items(&items)
+        .interact()
+        .unwrap();
+    match selection {
+        0 | 1 => {
+            match ImageStorage::new(settings.save_location.clone()) {
+                Ok(image_storage) => {
+                    match image_storage.to_load_iterator(settings.load_location.as_str()) {
+                        Ok(mut img_loader) => {
+                            if settings.shuffle_load_order {
+                                img_loader.shuffle_remaining();
+                            }
+                            let mut valid_loader = img_loader.wrap_into_valid();
+                            if selection == 1 {
+                                valid_loader = valid_loader.favourites_only();
+                            }
+                            let mut printer: Printer<ValidImageLoadIterator> =
+                                Printer::new(valid_loader, settings.printer_options())
+                                    .with_status_hints(messages().status_hints_load)
+                                    .with_character_ramp(settings.character_ramp.clone())
+                                    .with_image_restorer(|_, _, _, path| ImageLoadIterator::load_file(path?).ok());
+                            let menu_result = printer_menu(&create_load_menu(), &mut printer, &MenuContext { image_storage: &image_storage, settings });
+                            restore_screen(&mut printer);
+                            menu_result?;
+                            settings.printing_rate_ms = printer.printing_rate();
+                        }
+                        Err(e) => Logger::log_error(&e.to_string()),
+                    }
+                }
+                Err(e) => Logger::log_error(&e.to_string()),
+            }
+        }
+        2 => {
+            match GalleryStatistics::collect(settings.load_location.as_str()) {
+                Ok(statistics) => print!("{}", statistics),
+                Err(e) => Logger::log_error(&e.to_string()),
+            }
+        }
+        3 => {}
+        _ => unreachable!(),
+    }
+    Ok(())
+}
+
 fn settings_menu(settings: &mut Settings) {
+    let text = messages();
     let items = vec![
-        "Change image save location",
-        "Change image loading location",
-        "Change image printing rate",
-        "Go back",
+        text.settings_save_location,
+        text.settings_load_location,
+        text.settings_printing_rate,
+        text.settings_reveal_style,
+        text.settings_character_ramp,
+        text.settings_detail_threshold,
+        text.settings_render_style,
+        text.settings_colour_mode,
+        text.settings_palette,
+        text.settings_dithering,
+        text.settings_auto_levels,
+        text.settings_double_width,
+        text.settings_luminance,
+        text.settings_saturation,
+        text.settings_inversion,
+        text.settings_effects,
+        text.settings_duotone,
+        text.settings_alpha,
+        text.settings_transform,
+        text.settings_caption,
+        text.settings_clear_mode,
+        text.settings_timings,
+        text.settings_slideshow_interval,
+        text.settings_shuffle_load,
+        text.settings_image_cache_limit,
+        text.settings_allowed_hosts,
+        text.settings_denied_hosts,
+        text.settings_search_url,
+        text.settings_image_selector,
+        text.settings_online_suggestions,
+        text.settings_calibrate,
+        text.settings_sound,
+        text.settings_language,
+        text.go_back,
     ];
     let selection = Select::new()
-        .with_prompt("Settings")
+        .with_prompt(text.settings_title)
         .default(0)
         .items(&items)
         .interact()
         .unwrap();
     match selection {
         0 => {
-            let new_location =
-                prompt_user("Enter new saving directory path (it must already exist)");
+            let new_location = prompt_user(text.prompt_save_location);
             settings.save_location = new_location;
-            Logger::log_info(
-                format!("Saving location changed to: {}", settings.save_location).as_str(),
-            );
+            Logger::log_info(&fill(text.save_location_changed, &[&settings.save_location]));
+            if let Err(e) = settings.store_locations() {
+                Logger::log_error(&e.to_string());
+            }
         }
         1 => {
-            let new_location =
-                prompt_user("Enter new loading directory path (it must already exist)");
+            let new_location = prompt_user(text.prompt_load_location);
             settings.load_location = new_location;
-            Logger::log_info(
-                format!("Loading location changed to: {}", settings.load_location).as_str(),
-            );
+            Logger::log_info(&fill(text.load_location_changed, &[&settings.load_location]));
+            if let Err(e) = settings.store_locations() {
+                Logger::log_error(&e.to_string());
+            }
         }
         2 => {
             let new_printing_rate = register_valid_printing_rate();
             settings.printing_rate_ms = new_printing_rate;
-            Logger::log_info(
-                format!("Printing rate changed to: {}", settings.printing_rate_ms).as_str(),
-            );
+            Logger::log_info(&fill(text.printing_rate_changed, &[&settings.printing_rate_ms]));
         }
         3 => {
+            settings.reveal_style = register_valid_reveal_style(settings.reveal_style);
+            Logger::log_info(&fill(text.reveal_style_changed, &[&settings.reveal_style]));
+            if let Err(e) = settings.store_reveal_style() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        4 => {
+            settings.character_ramp = register_valid_character_ramp();
+            Logger::log_info(&fill(text.character_ramp_changed, &[&settings.character_ramp]));
+            if let Err(e) = settings.store_character_ramp() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        5 => {
+            settings.detail_threshold = register_valid_detail_threshold();
+            match settings.detail_threshold {
+                Some(threshold) => Logger::log_info(&fill(text.detail_threshold_changed, &[&threshold])),
+                None => Logger::log_info(text.detail_threshold_disabled),
+            }
+            if let Err(e) = settings.store_detail_threshold() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        6 => {
+            settings.render_style = register_valid_render_style(settings.render_style);
+            Logger::log_info(&fill(text.render_style_changed, &[&settings.render_style]));
+            if let Err(e) = settings.store_render_style() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        7 => {
+            settings.colour_mode = register_valid_colour_mode(settings.colour_mode);
+            Logger::log_info(&fill(text.colour_mode_changed, &[&settings.colour_mode]));
+            if let Err(e) = settings.store_colour_mode() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        8 => {
+            settings.palette = register_valid_palette(settings.palette);
+            Logger::log_info(&fill(text.palette_changed, &[&settings.palette]));
+            if let Err(e) = settings.store_palette() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        9 => {
+            settings.dithering = register_valid_dithering(settings.dithering);
+            Logger::log_info(&fill(text.dithering_changed, &[&settings.dithering]));
+        }
+        10 => {
+            settings.auto_levels = !settings.auto_levels;
+            Logger::log_info(if settings.auto_levels { text.auto_levels_enabled } else { text.auto_levels_disabled });
+        }
+        11 => {
+            settings.double_width = !settings.double_width;
+            Logger::log_info(if settings.double_width { text.double_width_enabled } else { text.double_width_disabled });
+        }
+        12 => {
+            settings.luminance = register_valid_luminance(settings.luminance);
+            Logger::log_info(&fill(text.luminance_changed, &[&settings.luminance]));
+            if let Err(e) = settings.store_luminance() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        13 => {
+            settings.saturation = register_valid_saturation();
+            Logger::log_info(&fill(text.saturation_changed, &[&settings.saturation]));
+            if let Err(e) = settings.store_saturation() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        14 => {
+            settings.inversion = register_valid_inversion(settings.inversion);
+            Logger::log_info(&fill(text.inversion_changed, &[&settings.inversion]));
+            if let Err(e) = settings.store_inversion() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        15 => {
+            settings.effects = register_valid_effects(&settings.effects);
+            Logger::log_info(&fill(text.effects_changed, &[&settings.effects]));
+            if let Err(e) = settings.store_effects() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        16 => {
+            settings.duotone = register_valid_duotone();
+            match settings.duotone {
+                Some(duotone) => Logger::log_info(&fill(text.duotone_changed, &[&duotone])),
+                None => Logger::log_info(text.duotone_disabled),
+            }
+            if let Err(e) = settings.store_duotone() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        17 => {
+            settings.alpha_mode = register_valid_alpha_mode(settings.alpha_mode);
+            if settings.alpha_mode != AlphaMode::Checkerboard {
+                settings.alpha_background = register_valid_alpha_background(settings.alpha_background);
+            }
+            Logger::log_info(&fill(text.alpha_changed, &[&settings.alpha_mode, &settings.alpha_background.hex()]));
+            if let Err(e) = settings.store_alpha() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        18 => {
+            settings.transform = register_valid_transform(settings.transform);
+            Logger::log_info(&fill(text.transform_changed, &[&settings.transform]));
+        }
+        19 => {
+            settings.caption = register_valid_caption(&settings.caption);
+            Logger::log_info(&fill(text.caption_changed, &[&settings.caption]));
+        }
+        20 => {
+            settings.clear_mode = register_valid_clear_mode(settings.clear_mode);
+            Logger::log_info(&fill(text.clear_mode_changed, &[&settings.clear_mode]));
+            if let Err(e) = settings.store_clear_mode() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        21 => {
+            settings.show_timings = !settings.show_timings;
+            Logger::log_info(if settings.show_timings { text.timings_enabled } else { text.timings_disabled });
+        }
+        22 => {
+            settings.slideshow_interval_s = register_valid_slideshow_interval();
+            Logger::log_info(&fill(text.slideshow_interval_changed, &[&settings.slideshow_interval_s]));
+            if let Err(e) = settings.store_slideshow_interval() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        23 => {
+            settings.shuffle_load_order = !settings.shuffle_load_order;
+            Logger::log_info(if settings.shuffle_load_order { text.shuffle_load_enabled } else { text.shuffle_load_disabled });
+            if let Err(e) = settings.store_shuffle_load_order() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        24 => {
+            settings.image_cache_limit = register_valid_image_cache_limit();
+            Logger::log_info(&fill(text.image_cache_limit_changed, &[&settings.image_cache_limit]));
+            if let Err(e) = settings.store_image_cache_limit() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        25 => {
+            let hosts = prompt_user_allow_empty(text.prompt_allowed_hosts);
+            settings.host_filter.set_allowed_hosts(&hosts);
+            Logger::log_info(&fill(text.allowed_hosts_changed, &[&format_host_list(settings.host_filter.allowed_hosts())]));
+            if let Err(e) = settings.store_host_filter() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        26 => {
+            let hosts = prompt_user_allow_empty(text.prompt_denied_hosts);
+            settings.host_filter.set_denied_hosts(&hosts);
+            Logger::log_info(&fill(text.denied_hosts_changed, &[&format_host_list(settings.host_filter.denied_hosts())]));
+            if let Err(e) = settings.store_host_filter() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        27 => {
+            let template = prompt_user_allow_empty(text.prompt_search_url);
+            settings.search_options.set_search_url_template(&template);
+            Logger::log_info(&fill(text.search_url_changed, &[&settings.search_options.search_url_template()]));
+            if let Err(e) = settings.store_search_options() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        28 => {
+            let selector = prompt_user_allow_empty(text.prompt_image_selector);
+            settings.search_options.set_image_selector(&selector);
+            Logger::log_info(&fill(text.image_selector_changed, &[&settings.search_options.image_selector()]));
+            if let Err(e) = settings.store_search_options() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        29 => {
+            settings.online_suggestions = !settings.online_suggestions;
+            Logger::log_info(if settings.online_suggestions { text.online_suggestions_enabled } else { text.online_suggestions_disabled });
+        }
+        30 => {
+            calibrate_cell_aspect_ratio(settings);
+        }
+        31 => {
+            settings.sound = register_valid_sound_feedback();
+            Logger::log_info(&fill(text.sound_changed, &[&settings.sound]));
+            if let Err(e) = settings.store_sound() {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        32 => {
+            let language = register_valid_language();
+            language.activate();
+            Logger::log_info(&fill(messages().language_changed, &[&language.name()]));
+            if let Err(e) = settings.store_language(language) {
+                Logger::log_error(&e.to_string());
+            }
+        }
+        33 => {
             return;
         }
         _ => unreachable!(),
     }
 }
 
+// The report and any errors after a viewer mode belong on the user's own screen, not the alternate one.
+fn restore_screen<G>(printer: &mut Printer<G>)
+where G: Iterator<Item = PrinterImageData>{
+    if let Err(e) = printer.restore_screen() {
+        Logger::log_error(e.to_string().as_str());
+    }
+}
+
+fn log_session_report(printer: &Printer<Converter>) {
+    Logger::log_info(&fill(messages().session_report, &[&printer.image_count()]));
+    if let Some(report) = printer.image_generator().source_report() {
+        Logger::log_info(&report);
+    }
+}
+
+fn format_host_list(hosts: &[String]) -> String {
+    if hosts.is_empty() {
+        messages().host_list_empty.to_string()
+    } else {
+        hosts.join(", ")
+    }
+}
+
+struct MenuContext<'a> {
+    image_storage: &'a ImageStorage,
+    settings: &'a Settings,
+}
+
 struct MenuInfo<G>
 where
     G: Iterator<Item = PrinterImageData>,
 {
-    handle_key_press: fn(KeyCode, image_storage: &ImageStorage, printer: &mut Printer<G>) -> bool,
+    handle_key_press: fn(KeyCode, context: &MenuContext, printer: &mut Printer<G>) -> bool,
     print_info: fn() -> (),
+    // Runs between key presses, while the current image is on screen.
+    on_idle: fn(printer: &mut Printer<G>),
 }
 
 fn printer_menu<G>(
     menu_info: &MenuInfo<G>,
     printer: &mut Printer<G>,
-    image_storage: &ImageStorage,
+    context: &MenuContext,
 ) -> io::Result<()>
 where
     G: Iterator<Item = PrinterImageData>,
 {
     (menu_info.print_info)();
+    let slideshow_interval = std::time::Duration::from_secs(context.settings.slideshow_interval_s as u64);
+    let mut slideshow_advanced_at: Option<std::time::Instant> = None;
+    let mut resized_at: Option<std::time::Instant> = None;
     loop {
-        if event::poll(std::time::Duration::from_millis(500))? {
-            if let Event::Key(key_event) = event::read()? {
-                if key_event.kind == KeyEventKind::Press {
-                    if !(menu_info.handle_key_press)(key_event.code, image_storage, printer) {
+        (menu_info.on_idle)(printer);
+        if let Err(e) = printer.expire_toast() {
+            Logger::log_error(e.to_string().as_str());
+        }
+        if slideshow_advanced_at.is_some_and(|advanced_at| advanced_at.elapsed() >= slideshow_interval) {
+            handle_and_print(printer.move_to_next_image_looping());
+            slideshow_advanced_at = Some(std::time::Instant::now());
+        }
+        // Dragging a window edge sends a burst of resizes, so the image is only redrawn once they settle.
+        if resized_at.is_some_and(|resized_at| resized_at.elapsed() >= RESIZE_SETTLE_TIME) {
+            resized_at = None;
+            if let Err(e) = printer.reprint_current_image() {
+                Logger::log_error(e.to_string().as_str());
+            }
+        }
+        let poll_timeout = if resized_at.is_some() { RESIZE_SETTLE_TIME } else { std::time::Duration::from_millis(500) };
+        if InputQueue::poll(poll_timeout)? {
+            match InputQueue::read()? {
+                Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                    // While a slideshow runs, any key only stops it.
+                    if slideshow_advanced_at.take().is_some() {
+                        show_toast(printer, messages().toast_slideshow_stopped, ToastKind::Info);
+                        continue;
+                    }
+                    if matches!(key_event.code, KeyCode::Char('l') | KeyCode::Char('L')) {
+                        slideshow_advanced_at = Some(std::time::Instant::now());
+                        show_toast(printer, &fill(messages().toast_slideshow_started, &[&context.settings.slideshow_interval_s]), ToastKind::Info);
+                        continue;
+                    }
+                    if !(menu_info.handle_key_press)(key_event.code, context, printer) {
                         return Ok(());
                     }
                 }
+                Event::Resize(_, _) => resized_at = Some(std::time::Instant::now()),
+                _ => {}
             }
         }
     }
@@ -231,10 +1547,26 @@ where
 fn create_load_menu() -> MenuInfo<ValidImageLoadIterator> {
     MenuInfo {
         handle_key_press: load_menu_handler,
+        on_idle: |_| {},
         print_info: || -> () {
-            println!("Press 'B' to go back to previous image or 'N' to swap to the next one.");
-            println!("Press 'C' to copy a colourless version of the current image to clipboard.");
-            println!("Press 'Q' to quit the mode.");
+            println!("{}", messages().help_navigation);
+            println!("{}", messages().help_jump);
+            println!("{}", messages().help_gallery);
+            println!("{}", messages().help_copy);
+            println!("{}", messages().help_copy_ansi);
+            println!("{}", messages().help_export);
+            println!("{}", messages().help_favourite_load);
+            println!("{}", messages().help_delete);
+            println!("{}", messages().help_compare);
+            println!("{}", messages().help_aspect);
+            println!("{}", messages().help_skip_reveal);
+            println!("{}", messages().help_pause_reveal);
+            println!("{}", messages().help_printing_rate);
+            println!("{}", messages().help_slideshow);
+            println!("{}", messages().help_pan);
+            println!("{}", messages().help_shuffle);
+            println!("{}", messages().help_animation);
+            println!("{}", messages().help_quit);
         },
     }
 }
@@ -244,7 +1576,7 @@ where G: Iterator<Item = PrinterImageData>{
     res.map_or_else(
         |e| Logger::log_error(e.to_string().as_str()),
         |printer| -> () {
-            let res = printer.print_current_image();
+            let res = printer.print_current_image().and_then(|_| printer.play_current_animation());
             if res.is_err() {
                 Logger::log_error(res.err().unwrap().to_string().as_str());
             }
@@ -252,21 +1584,285 @@ where G: Iterator<Item = PrinterImageData>{
     )
 }
 
+fn show_toast<G>(printer: &mut Printer<G>, message: &str, kind: ToastKind)
+where G: Iterator<Item = PrinterImageData>{
+    if let Err(e) = printer.show_toast(message, kind) {
+        Logger::log_error(e.to_string().as_str());
+    }
+}
+
+const RESIZE_SETTLE_TIME: std::time::Duration = std::time::Duration::from_millis(150);
+const PAN_ROW_STEP: isize = 2;
+const PAN_COLUMN_STEP: isize = 4;
+
+fn pan_current_image<G>(printer: &mut Printer<G>, code: KeyCode)
+where G: Iterator<Item = PrinterImageData>{
+    let (row_step, column_step) = match code {
+        KeyCode::Up => (-PAN_ROW_STEP, 0),
+        KeyCode::Down => (PAN_ROW_STEP, 0),
+        KeyCode::Left => (0, -PAN_COLUMN_STEP),
+        _ => (0, PAN_COLUMN_STEP),
+    };
+    if let Err(e) = printer.pan_current_image(row_step, column_step) {
+        Logger::log_error(e.to_string().as_str());
+    }
+}
+
+fn jump_to_image<G>(printer: &mut Printer<G>)
+where G: Iterator<Item = PrinterImageData>{
+    let image_count = printer.image_count();
+    if image_count == 0 {
+        Logger::log_error(&PrinterError::NoImagesRegisteredError.to_string());
+        return;
+    }
+    let number = prompt_user(&fill(messages().prompt_jump, &[&image_count]));
+    match number.trim().parse::<usize>() {
+        Ok(number) if (1..=image_count).contains(&number) => handle_and_print(printer.move_to_image(number - 1)),
+        _ => Logger::log_error(&fill(messages().invalid_jump, &[&number.trim(), &image_count])),
+    }
+}
+
+// Keys arrive a line at a time, so the Enter that ends every line is ignored and Space opens the selection instead.
+fn browse_gallery<G>(printer: &mut Printer<G>)
+where G: Iterator<Item = PrinterImageData>{
+    let image_count = printer.image_count();
+    if image_count == 0 {
+        Logger::log_error(&PrinterError::NoImagesRegisteredError.to_string());
+        return;
+    }
+    if !printer.is_terminal() {
+        Logger::log_error(messages().gallery_needs_terminal);
+        return;
+    }
+    let mut selected = printer.current_image_index();
+    loop {
+        if let Err(e) = printer.print_gallery(selected) {
+            Logger::log_error(e.to_string().as_str());
+            return;
+        }
+        let key_event = match InputQueue::read() {
+            Ok(Event::Key(key_event)) if key_event.kind == KeyEventKind::Press => key_event,
+            Ok(_) => continue,
+            Err(e) => {
+                Logger::log_error(&e.to_string());
+                return;
+            }
+        };
+        let columns = printer.gallery_columns();
+        match key_event.code {
+            KeyCode::Left => selected = selected.saturating_sub(1),
+            KeyCode::Right => selected = (selected + 1).min(image_count - 1),
+            KeyCode::Up => selected = selected.saturating_sub(columns),
+            KeyCode::Down => selected = (selected + columns).min(image_count - 1),
+            KeyCode::Char(' ') => {
+                handle_and_print(printer.move_to_image(selected));
+                return;
+            }
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => {
+                handle_and_print(Ok(printer));
+                return;
+            }
+            _ => {}
+        }
+    }
+}
+
+// Pinning moves on to the next image right away, so the two can be compared at once.
+fn toggle_comparison<G>(printer: &mut Printer<G>)
+where G: Iterator<Item = PrinterImageData>{
+    match printer.toggle_pinned_image() {
+        Ok(true) => {
+            if let Err(e) = printer.move_to_next_image() {
+                Logger::log_error(e.to_string().as_str());
+            }
+            handle_and_print(Ok(&mut *printer));
+            show_toast(printer, messages().toast_pinned, ToastKind::Info);
+        }
+        Ok(false) => {
+            handle_and_print(Ok(&mut *printer));
+            show_toast(printer, messages().toast_unpinned, ToastKind::Info);
+        }
+        Err(e) => Logger::log_error(e.to_string().as_str()),
+    }
+}
+
+fn toast_aspect_correction<G>(printer: &mut Printer<G>)
+where G: Iterator<Item = PrinterImageData>{
+    if printer.is_current_image_aspect_corrected() {
+        show_toast(printer, messages().toast_aspect_enabled, ToastKind::Info);
+    } else {
+        show_toast(printer, messages().toast_aspect_disabled, ToastKind::Info);
+    }
+}
+
+// Images without their source bytes keep their current look.
+fn reconvert_current_image(printer: &mut Printer<Converter>, applied_message: &str, next_message: &str) {
+    let Some((image_name, image_bytes)) = printer.get_current_image_source() else {
+        show_toast(printer, next_message, ToastKind::Info);
+        return;
+    };
+    if let Some(image_data) = printer.image_generator().reconvert(image_name, image_bytes) {
+        match printer.replace_current_image(image_data) {
+            Ok(printer) => {
+                handle_and_print(Ok(&mut *printer));
+                show_toast(printer, applied_message, ToastKind::Info);
+            }
+            Err(e) => Logger::log_error(e.to_string().as_str()),
+        }
+    }
+}
+
+fn change_transform(printer: &mut Printer<Converter>, change: fn(Transform) -> Transform) {
+    let transform = change(printer.image_generator().transform());
+    printer.image_generator_mut().set_transform(transform);
+    let message = fill(messages().toast_transform_applied, &[&transform]);
+    reconvert_current_image(printer, &message, messages().toast_transform_next);
+}
+
+fn export_image<G>(context: &MenuContext, printer: &mut Printer<G>)
+where G: Iterator<Item = PrinterImageData>{
+    let format = prompt_user_select(messages().prompt_export_format, &ExportFormat::ALL, 0, |selection| ExportFormat::ALL[selection]);
+    let export_result = printer
+        .get_current_displayed_image_data()
+        .map_err(|e| e.to_string())
+        .and_then(|(image_name, image_rows)| {
+            context.image_storage
+                .export_image(image_name, &image_rows, format)
+                .map_err(|e| e.to_string())
+        });
+    match export_result {
+        Ok(image_name) => {
+            context.settings.sound.play(SoundEvent::Save);
+            show_toast(printer, &fill(messages().toast_exported, &[&image_name]), ToastKind::Success);
+        }
+        Err(e) => {
+            context.settings.sound.play(SoundEvent::Error);
+            show_toast(printer, e.as_str(), ToastKind::Error);
+        }
+    }
+}
+
+// Loaded images are marked in their file right away, and the mark is taken back if that fails.
+fn toggle_stored_favourite(context: &MenuContext, printer: &mut Printer<ValidImageLoadIterator>) {
+    let favourite = match printer.toggle_current_image_favourite() {
+        Ok(favourite) => favourite,
+        Err(e) => return Logger::log_error(e.to_string().as_str()),
+    };
+    let stored = match printer.get_current_image_path() {
+        Some(image_path) => context.image_storage.set_favourite(image_path, favourite),
+        None => return,
+    };
+    if let Err(e) = stored {
+        let _ = printer.toggle_current_image_favourite();
+        context.settings.sound.play(SoundEvent::Error);
+        show_toast(printer, e.to_string().as_str(), ToastKind::Error);
+        return;
+    }
+    handle_and_print(Ok(&mut *printer));
+    let message = if favourite { messages().toast_favourite_added } else { messages().toast_favourite_removed };
+    show_toast(printer, message, ToastKind::Success);
+}
+
+// Returns whether there is still an image left to browse.
+fn delete_current_image(context: &MenuContext, printer: &mut Printer<ValidImageLoadIterator>) -> bool {
+    let Some(image_path) = printer.get_current_image_path().map(Path::to_path_buf) else {
+        return true;
+    };
+    let file_name = image_path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+    let confirmed = Confirm::new()
+        .with_prompt(fill(messages().prompt_delete, &[&file_name]))
+        .default(false)
+        .interact()
+        .unwrap();
+    if !confirmed {
+        return true;
+    }
+    if let Err(e) = context.image_storage.delete_image(&image_path) {
+        context.settings.sound.play(SoundEvent::Error);
+        show_toast(printer, e.to_string().as_str(), ToastKind::Error);
+        return true;
+    }
+    match printer.remove_current_image() {
+        Ok(printer) => {
+            handle_and_print(Ok(&mut *printer));
+            show_toast(printer, &fill(messages().toast_deleted, &[&file_name]), ToastKind::Success);
+            true
+        }
+        // A cancelled load also leaves the list empty.
+        Err(PrinterError::NoImagesRegisteredError | PrinterError::CancelledError) => {
+            println!("{}", messages().no_images_left);
+            false
+        }
+        Err(e) => {
+            Logger::log_error(&e.to_string());
+            true
+        }
+    }
+}
+
 fn load_menu_handler(
     code: KeyCode,
-    _: &ImageStorage,
+    context: &MenuContext,
     printer: &mut Printer<ValidImageLoadIterator>,
 ) -> bool {
     match code {
         KeyCode::Char('b') | KeyCode::Char('B') => {
             handle_and_print(printer.move_to_previous_image());
         }
+        KeyCode::Char('g') | KeyCode::Char('G') => jump_to_image(printer),
+        KeyCode::Char('t') | KeyCode::Char('T') => browse_gallery(printer),
         KeyCode::Char('n') | KeyCode::Char('N') => {
             handle_and_print(printer.move_to_next_image());
         }
         KeyCode::Char('C') | KeyCode::Char('c') => {
-            printer.copy_current_image_to_clipboard()
-                .map_or_else(|e| Logger::log_error(e.to_string().as_str()), |_| Logger::log_success("Image copied to clipboard."));
+            match printer.copy_current_image_to_clipboard() {
+                Ok(_) => {
+                    context.settings.sound.play(SoundEvent::Copy);
+                    show_toast(printer, messages().toast_copied, ToastKind::Success);
+                }
+                Err(e) => {
+                    context.settings.sound.play(SoundEvent::Error);
+                    show_toast(printer, e.to_string().as_str(), ToastKind::Error);
+                }
+            }
+        }
+        KeyCode::Char('X') | KeyCode::Char('x') => {
+            match printer.copy_current_ansi_image_to_clipboard() {
+                Ok(true) => {
+                    context.settings.sound.play(SoundEvent::Copy);
+                    show_toast(printer, messages().toast_copied_ansi, ToastKind::Success);
+                }
+                Ok(false) => {
+                    context.settings.sound.play(SoundEvent::Copy);
+                    show_toast(printer, messages().toast_copied_without_colours, ToastKind::Info);
+                }
+                Err(e) => {
+                    context.settings.sound.play(SoundEvent::Error);
+                    show_toast(printer, e.to_string().as_str(), ToastKind::Error);
+                }
+            }
+        }
+        KeyCode::Char('o') | KeyCode::Char('O') => export_image(context, printer),
+        KeyCode::Char('|') => toggle_comparison(printer),
+        KeyCode::Char('a') | KeyCode::Char('A') => {
+            match printer.toggle_aspect_correction() {
+                Ok(printer) => {
+                    handle_and_print(Ok(&mut *printer));
+                    toast_aspect_correction(printer);
+                }
+                Err(e) => Logger::log_error(e.to_string().as_str()),
+            }
+        }
+        KeyCode::Char(sign @ ('+' | '-')) => {
+            let printing_rate = printer.step_printing_rate(sign == '+');
+            show_toast(printer, &fill(messages().printing_rate_changed, &[&printing_rate]), ToastKind::Info);
+        }
+        KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => pan_current_image(printer, code),
+        KeyCode::Char('d') | KeyCode::Char('D') => return delete_current_image(context, printer),
+        KeyCode::Char('f') | KeyCode::Char('F') | KeyCode::Char('*') => toggle_stored_favourite(context, printer),
+        KeyCode::Char('u') | KeyCode::Char('U') => {
+            let shuffled = printer.image_generator_mut().shuffle_remaining();
+            show_toast(printer, &fill(messages().toast_shuffled, &[&shuffled]), ToastKind::Info);
         }
         KeyCode::Char('q') | KeyCode::Char('Q') => {
             return false;
@@ -279,49 +1875,159 @@ fn load_menu_handler(
 fn create_generator_menu() -> MenuInfo<Converter> {
     MenuInfo {
         handle_key_press: generator_menu_handler,
+        on_idle: |printer| printer.image_generator_mut().prefetch(),
         print_info: || -> () {
-            println!("Press 'B' to go back to previous image or 'N' to swap to the next one.");
-            println!("Press 'S' to save the current image in the specified folder.");
-            println!("Press 'C' to copy a colourless version of the current image to clipboard.");
-            println!("Press 'Q' to quit the mode.");
+            println!("{}", messages().help_navigation);
+            println!("{}", messages().help_jump);
+            println!("{}", messages().help_gallery);
+            println!("{}", messages().help_save);
+            println!("{}", messages().help_note);
+            println!("{}", messages().help_favourite);
+            println!("{}", messages().help_copy);
+            println!("{}", messages().help_copy_ansi);
+            println!("{}", messages().help_export);
+            println!("{}", messages().help_compare);
+            println!("{}", messages().help_aspect);
+            println!("{}", messages().help_effects);
+            println!("{}", messages().help_transform);
+            println!("{}", messages().help_width);
+            println!("{}", messages().help_skip_reveal);
+            println!("{}", messages().help_pause_reveal);
+            println!("{}", messages().help_printing_rate);
+            println!("{}", messages().help_slideshow);
+            println!("{}", messages().help_pan);
+            println!("{}", messages().help_animation);
+            println!("{}", messages().help_cancel);
+            println!("{}", messages().help_quit);
         },
     }
 }
 
 fn generator_menu_handler(
     code: KeyCode,
-    image_storage: &ImageStorage,
+    context: &MenuContext,
     printer: &mut Printer<Converter>,
 ) -> bool {
     match code {
         KeyCode::Char('b') | KeyCode::Char('B') => {
             handle_and_print(printer.move_to_previous_image());
         }
+        KeyCode::Char('g') | KeyCode::Char('G') => jump_to_image(printer),
+        KeyCode::Char('t') | KeyCode::Char('T') => browse_gallery(printer),
         KeyCode::Char('n') | KeyCode::Char('N') => {
-            handle_and_print(printer.move_to_next_image());
+            match printer.move_to_next_image().map(|_| ()) {
+                Ok(()) => handle_and_print(Ok(printer)),
+                Err(PrinterError::CancelledError) => {
+                    show_toast(printer, messages().conversion_cancelled, ToastKind::Info);
+                }
+                Err(e) => Logger::log_error(e.to_string().as_str()),
+            }
         }
         KeyCode::Char('s') | KeyCode::Char('S') => {
-            let current_image = printer.get_current_image_data();
-            if current_image.is_err() {
-                Logger::log_error(current_image.err().unwrap().to_string().as_str());
+            let save_result = printer
+                .get_current_image_data()
+                .map_err(|e| e.to_string())
+                .and_then(|(image_name, image_array)| {
+                    let custom_name = prompt_user_with_default(messages().prompt_save_name, image_name);
+                    let custom_name = custom_name.trim();
+                    let save_name = if custom_name.is_empty() { image_name } else { custom_name };
+                    context.image_storage
+                        .save_image(save_name, image_array, printer.get_current_image_note(), printer.is_current_image_favourite())
+                        .map_err(|e| e.to_string())
+                });
+            match save_result {
+                Ok(image_name) => {
+                    context.settings.sound.play(SoundEvent::Save);
+                    show_toast(printer, &fill(messages().toast_saved, &[&image_name]), ToastKind::Success);
+                }
+                Err(e) => {
+                    context.settings.sound.play(SoundEvent::Error);
+                    show_toast(printer, e.as_str(), ToastKind::Error);
+                }
+            }
+        }
+        KeyCode::Char('e') | KeyCode::Char('E') => {
+            if let Err(e) = printer.get_current_image_data() {
+                Logger::log_error(e.to_string().as_str());
             } else {
-                let (image_name, image_array) = current_image.unwrap();
-                image_storage
-                    .save_image(image_name, image_array)
-                    .map_or_else(
-                        |e| Logger::log_error(e.to_string().as_str()),
-                        |image_name| -> () {
-                            Logger::log_success(format!(
-                                "Image {} saved successfully.",
-                                image_name
-                            ).as_str());
-                        },
-                    )
+                let note = prompt_user_allow_empty(messages().prompt_note);
+                handle_and_print(printer.set_current_image_note(&note));
+            }
+        }
+        KeyCode::Char('*') => {
+            match printer.toggle_current_image_favourite() {
+                Ok(favourite) => {
+                    handle_and_print(Ok(&mut *printer));
+                    let message = if favourite { messages().toast_favourite_unsaved } else { messages().toast_favourite_removed };
+                    show_toast(printer, message, ToastKind::Info);
+                }
+                Err(e) => Logger::log_error(e.to_string().as_str()),
             }
         }
         KeyCode::Char('C') | KeyCode::Char('c') => {
-            printer.copy_current_image_to_clipboard()
-                .map_or_else(|e| Logger::log_error(e.to_string().as_str()), |_| Logger::log_success("Image copied to clipboard."));
+            match printer.copy_current_image_to_clipboard() {
+                Ok(_) => {
+                    context.settings.sound.play(SoundEvent::Copy);
+                    show_toast(printer, messages().toast_copied, ToastKind::Success);
+                }
+                Err(e) => {
+                    context.settings.sound.play(SoundEvent::Error);
+                    show_toast(printer, e.to_string().as_str(), ToastKind::Error);
+                }
+            }
+        }
+        KeyCode::Char('X') | KeyCode::Char('x') => {
+            match printer.copy_current_ansi_image_to_clipboard() {
+                Ok(true) => {
+                    context.settings.sound.play(SoundEvent::Copy);
+                    show_toast(printer, messages().toast_copied_ansi, ToastKind::Success);
+                }
+                Ok(false) => {
+                    context.settings.sound.play(SoundEvent::Copy);
+                    show_toast(printer, messages().toast_copied_without_colours, ToastKind::Info);
+                }
+                Err(e) => {
+                    context.settings.sound.play(SoundEvent::Error);
+                    show_toast(printer, e.to_string().as_str(), ToastKind::Error);
+                }
+            }
+        }
+        KeyCode::Char('o') | KeyCode::Char('O') => export_image(context, printer),
+        KeyCode::Char('|') => toggle_comparison(printer),
+        KeyCode::Char('a') | KeyCode::Char('A') => {
+            match printer.toggle_aspect_correction() {
+                Ok(printer) => {
+                    handle_and_print(Ok(&mut *printer));
+                    toast_aspect_correction(printer);
+                }
+                Err(e) => Logger::log_error(e.to_string().as_str()),
+            }
+        }
+        KeyCode::Char(sign @ ('+' | '-')) => {
+            let printing_rate = printer.step_printing_rate(sign == '+');
+            show_toast(printer, &fill(messages().printing_rate_changed, &[&printing_rate]), ToastKind::Info);
+        }
+        KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => pan_current_image(printer, code),
+        KeyCode::Char('f') | KeyCode::Char('F') => {
+            let effects = register_valid_effects(printer.image_generator().effects());
+            printer.image_generator_mut().set_effects(effects);
+            let message = fill(messages().toast_effects_applied, &[printer.image_generator().effects()]);
+            reconvert_current_image(printer, &message, messages().toast_effects_next);
+        }
+        KeyCode::Char('w') | KeyCode::Char('W') => {
+            let image_width = prompt_for_width(&context.settings.size_limits, Some(printer.image_generator().image_width()));
+            printer.image_generator_mut().set_image_width(image_width);
+            let message = fill(messages().toast_width_applied, &[&image_width]);
+            reconvert_current_image(printer, &message, messages().toast_width_next);
+        }
+        KeyCode::Char('r') | KeyCode::Char('R') => {
+            change_transform(printer, Transform::rotated_clockwise);
+        }
+        KeyCode::Char('h') | KeyCode::Char('H') => {
+            change_transform(printer, Transform::flipped_horizontally);
+        }
+        KeyCode::Char('v') | KeyCode::Char('V') => {
+            change_transform(printer, Transform::flipped_vertically);
         }
         KeyCode::Char('q') | KeyCode::Char('Q') => {
             return false;
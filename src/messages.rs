@@ -0,0 +1,1264 @@
+use crate::config::Config;
+use std::env;
+use std::fmt;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    German,
+}
+
+static CURRENT_LANGUAGE: AtomicU8 = AtomicU8::new(Language::English as u8);
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+    pub const CONFIG_KEY: &'static str = "language";
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    fn from_code(code: &str) -> Option<Language> {
+        let code = code.trim().to_lowercase();
+        Self::ALL
+            .into_iter()
+            .find(|language| code.starts_with(language.code()))
+    }
+
+    pub fn detect(config: Option<&Config>) -> Language {
+        if let Some(language) = config
+            .and_then(|config| config.get(Config::GLOBAL_SECTION, Self::CONFIG_KEY))
+            .and_then(Self::from_code)
+        {
+            return language;
+        }
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|variable| env::var(variable).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::from_code(&value))
+            .unwrap_or(Language::English)
+    }
+
+    pub fn current() -> Language {
+        match CURRENT_LANGUAGE.load(Ordering::Relaxed) {
+            1 => Language::German,
+            _ => Language::English,
+        }
+    }
+
+    pub fn activate(&self) {
+        CURRENT_LANGUAGE.store(*self as u8, Ordering::Relaxed);
+    }
+}
+
+pub fn messages() -> &'static Messages {
+    match Language::current() {
+        Language::English => &ENGLISH,
+        Language::German => &GERMAN,
+    }
+}
+
+pub fn fill(template: &str, args: &[&dyn fmt::Display]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut parts = template.split("{}");
+    if let Some(first_part) = parts.next() {
+        result.push_str(first_part);
+    }
+    for part in parts {
+        if let Some(arg) = args.next() {
+            write!(result, "{}", arg).expect("Writing to String should not fail");
+        }
+        result.push_str(part);
+    }
+    result
+}
+
+pub struct Messages {
+    pub banner: &'static str,
+    pub log_info_prefix: &'static str,
+    pub log_success_prefix: &'static str,
+    pub log_error_prefix: &'static str,
+    pub input_error: &'static str,
+    pub unknown_argument: &'static str,
+    pub missing_argument_value: &'static str,
+
+    pub main_menu_generator: &'static str,
+    pub main_menu_load: &'static str,
+    pub load_menu_browse: &'static str,
+    pub load_menu_browse_favourites: &'static str,
+    pub load_menu_gallery_stats: &'static str,
+    pub gallery_title: &'static str,
+    pub gallery_image_count: &'static str,
+    pub gallery_disk_usage: &'static str,
+    pub gallery_largest: &'static str,
+    pub gallery_oldest: &'static str,
+    pub gallery_by_keyword: &'static str,
+    pub gallery_ansi_group: &'static str,
+    pub main_menu_settings: &'static str,
+    pub main_menu_quit: &'static str,
+
+    pub prompt_width: &'static str,
+    pub prompt_crop: &'static str,
+    pub prompt_source: &'static str,
+    pub source_search: &'static str,
+    #[cfg(feature = "screen-capture")]
+    pub source_screen: &'static str,
+    #[cfg(feature = "webcam")]
+    pub source_webcam: &'static str,
+    #[cfg(feature = "mock-source")]
+    pub source_mock: &'static str,
+    pub mock_source_enabled: &'static str,
+    #[cfg(feature = "screen-capture")]
+    pub prompt_screen: &'static str,
+    #[cfg(feature = "screen-capture")]
+    pub prompt_capture_region: &'static str,
+    #[cfg(feature = "webcam")]
+    pub prompt_webcam: &'static str,
+    pub prompt_keyword: &'static str,
+    pub keyword_new: &'static str,
+    pub prompt_recent_keyword: &'static str,
+    pub prompt_printing_rate: &'static str,
+    pub invalid_printing_rate: &'static str,
+    pub prompt_sound: &'static str,
+    pub sound_option_off: &'static str,
+    pub sound_option_bell: &'static str,
+    pub sound_option_command: &'static str,
+    pub prompt_sound_command: &'static str,
+
+    pub settings_title: &'static str,
+    pub settings_save_location: &'static str,
+    pub settings_load_location: &'static str,
+    pub settings_printing_rate: &'static str,
+    pub settings_character_ramp: &'static str,
+    pub settings_render_style: &'static str,
+    pub settings_clear_mode: &'static str,
+    pub settings_timings: &'static str,
+    pub settings_slideshow_interval: &'static str,
+    pub settings_image_cache_limit: &'static str,
+    pub settings_shuffle_load: &'static str,
+    pub settings_allowed_hosts: &'static str,
+    pub settings_denied_hosts: &'static str,
+    pub settings_search_url: &'static str,
+    pub settings_image_selector: &'static str,
+    pub settings_online_suggestions: &'static str,
+    pub settings_calibrate: &'static str,
+    pub settings_sound: &'static str,
+    pub settings_language: &'static str,
+    pub go_back: &'static str,
+    pub prompt_save_location: &'static str,
+    pub save_location_changed: &'static str,
+    pub prompt_load_location: &'static str,
+    pub load_location_changed: &'static str,
+    pub printing_rate_changed: &'static str,
+    pub prompt_character_ramp: &'static str,
+    pub prompt_ramp_preset: &'static str,
+    pub ramp_preset_classic: &'static str,
+    pub ramp_preset_shading: &'static str,
+    pub ramp_preset_custom: &'static str,
+    pub character_ramp_changed: &'static str,
+    pub settings_detail_threshold: &'static str,
+    pub prompt_detail_threshold: &'static str,
+    pub invalid_detail_threshold: &'static str,
+    pub detail_threshold_changed: &'static str,
+    pub prompt_slideshow_interval: &'static str,
+    pub invalid_slideshow_interval: &'static str,
+    pub prompt_image_cache_limit: &'static str,
+    pub invalid_image_cache_limit: &'static str,
+    pub prompt_jump: &'static str,
+    pub invalid_jump: &'static str,
+    pub slideshow_interval_changed: &'static str,
+    pub image_cache_limit_changed: &'static str,
+    pub shuffle_load_enabled: &'static str,
+    pub shuffle_load_disabled: &'static str,
+    pub detail_threshold_disabled: &'static str,
+    pub prompt_render_style: &'static str,
+    pub render_style_ascii: &'static str,
+    pub render_style_braille: &'static str,
+    pub render_style_half_block: &'static str,
+    pub render_style_quadrant: &'static str,
+    pub render_style_mosaic: &'static str,
+    pub render_style_edge_aware: &'static str,
+    pub render_style_sixel: &'static str,
+    pub render_style_kitty: &'static str,
+    pub render_style_iterm2: &'static str,
+    pub render_style_emoji: &'static str,
+    pub render_style_changed: &'static str,
+    pub settings_colour_mode: &'static str,
+    pub prompt_colour_mode: &'static str,
+    pub colour_mode_auto: &'static str,
+    pub colour_mode_true_colour: &'static str,
+    pub colour_mode_ansi_256: &'static str,
+    pub colour_mode_ansi_16: &'static str,
+    pub colour_mode_monochrome: &'static str,
+    pub colour_mode_changed: &'static str,
+    pub settings_palette: &'static str,
+    pub prompt_palette: &'static str,
+    pub palette_off: &'static str,
+    pub palette_changed: &'static str,
+    pub settings_dithering: &'static str,
+    pub prompt_dithering: &'static str,
+    pub dithering_off: &'static str,
+    pub dithering_floyd_steinberg: &'static str,
+    pub dithering_bayer: &'static str,
+    pub dithering_changed: &'static str,
+    pub settings_auto_levels: &'static str,
+    pub auto_levels_enabled: &'static str,
+    pub auto_levels_disabled: &'static str,
+    pub settings_double_width: &'static str,
+    pub double_width_enabled: &'static str,
+    pub double_width_disabled: &'static str,
+    pub settings_luminance: &'static str,
+    pub prompt_luminance: &'static str,
+    pub luminance_rec709: &'static str,
+    pub luminance_rec601: &'static str,
+    pub luminance_average: &'static str,
+    pub luminance_changed: &'static str,
+    pub settings_saturation: &'static str,
+    pub prompt_saturation: &'static str,
+    pub invalid_saturation: &'static str,
+    pub saturation_changed: &'static str,
+    pub settings_inversion: &'static str,
+    pub prompt_inversion: &'static str,
+    pub inversion_off: &'static str,
+    pub inversion_characters: &'static str,
+    pub inversion_all: &'static str,
+    pub inversion_changed: &'static str,
+    pub settings_effects: &'static str,
+    pub prompt_effects: &'static str,
+    pub prompt_effects_order: &'static str,
+    pub effect_sepia: &'static str,
+    pub effect_invert: &'static str,
+    pub effect_posterize: &'static str,
+    pub effect_hue_rotate: &'static str,
+    pub effects_none: &'static str,
+    pub effects_changed: &'static str,
+    pub settings_alpha: &'static str,
+    pub prompt_alpha: &'static str,
+    pub alpha_background: &'static str,
+    pub alpha_checkerboard: &'static str,
+    pub alpha_transparent: &'static str,
+    pub prompt_alpha_background: &'static str,
+    pub invalid_alpha_background: &'static str,
+    pub alpha_changed: &'static str,
+    pub settings_transform: &'static str,
+    pub prompt_rotation: &'static str,
+    pub prompt_flips: &'static str,
+    pub transform_rotated: &'static str,
+    pub transform_flipped_horizontally: &'static str,
+    pub transform_flipped_vertically: &'static str,
+    pub transform_none: &'static str,
+    pub transform_changed: &'static str,
+    pub settings_caption: &'static str,
+    pub prompt_caption: &'static str,
+    pub caption_off: &'static str,
+    pub caption_image_name: &'static str,
+    pub caption_custom: &'static str,
+    pub prompt_caption_text: &'static str,
+    pub caption_changed: &'static str,
+    pub prompt_clear_mode: &'static str,
+    pub clear_mode_full: &'static str,
+    pub clear_mode_alternate: &'static str,
+    pub clear_mode_preserve: &'static str,
+    pub settings_reveal_style: &'static str,
+    pub prompt_reveal_style: &'static str,
+    pub reveal_style_random: &'static str,
+    pub reveal_style_spiral: &'static str,
+    pub reveal_style_centre_out: &'static str,
+    pub reveal_style_matrix: &'static str,
+    pub reveal_style_wipe_down: &'static str,
+    pub reveal_style_wipe_up: &'static str,
+    pub reveal_style_fade_in: &'static str,
+    pub reveal_style_changed: &'static str,
+    pub clear_mode_changed: &'static str,
+    pub timings_enabled: &'static str,
+    pub timings_disabled: &'static str,
+    pub prompt_allowed_hosts: &'static str,
+    pub allowed_hosts_changed: &'static str,
+    pub prompt_denied_hosts: &'static str,
+    pub denied_hosts_changed: &'static str,
+    pub prompt_search_url: &'static str,
+    pub search_url_changed: &'static str,
+    pub prompt_image_selector: &'static str,
+    pub image_selector_changed: &'static str,
+    pub online_suggestions_enabled: &'static str,
+    pub online_suggestions_disabled: &'static str,
+    pub host_list_empty: &'static str,
+    pub session_report: &'static str,
+    pub host_statistics: &'static str,
+    pub aspect_ratio_changed: &'static str,
+    pub calibration_cancelled: &'static str,
+    pub wizard_welcome: &'static str,
+    pub wizard_terminal: &'static str,
+    pub wizard_calibration: &'static str,
+    pub wizard_prompt_width: &'static str,
+    pub wizard_width_preset: &'static str,
+    pub wizard_width_fit: &'static str,
+    pub default_width_changed: &'static str,
+    pub wizard_prompt_save_location: &'static str,
+    pub wizard_prompt_load_location: &'static str,
+    pub wizard_directory_ready: &'static str,
+    pub wizard_directory_error: &'static str,
+    pub wizard_checking_browser: &'static str,
+    pub wizard_browser_available: &'static str,
+    pub wizard_browser_unavailable: &'static str,
+    pub wizard_complete: &'static str,
+    pub sound_changed: &'static str,
+    pub prompt_language: &'static str,
+    pub language_changed: &'static str,
+
+    pub help_navigation: &'static str,
+    pub help_jump: &'static str,
+    pub help_gallery: &'static str,
+    pub help_save: &'static str,
+    pub help_note: &'static str,
+    pub prompt_note: &'static str,
+    pub prompt_save_name: &'static str,
+    pub help_copy: &'static str,
+    pub help_copy_ansi: &'static str,
+    pub help_delete: &'static str,
+    pub help_favourite: &'static str,
+    pub help_favourite_load: &'static str,
+    pub help_compare: &'static str,
+    pub help_export: &'static str,
+    pub help_aspect: &'static str,
+    pub help_effects: &'static str,
+    pub help_transform: &'static str,
+    pub help_width: &'static str,
+    pub help_skip_reveal: &'static str,
+    pub help_pause_reveal: &'static str,
+    pub help_printing_rate: &'static str,
+    pub help_slideshow: &'static str,
+    pub help_shuffle: &'static str,
+    pub help_pan: &'static str,
+    pub help_animation: &'static str,
+    pub help_cancel: &'static str,
+    pub help_quit: &'static str,
+    pub toast_copied: &'static str,
+    pub toast_copied_ansi: &'static str,
+    pub toast_copied_without_colours: &'static str,
+    pub toast_saved: &'static str,
+    pub prompt_delete: &'static str,
+    pub toast_deleted: &'static str,
+    pub toast_favourite_added: &'static str,
+    pub toast_favourite_unsaved: &'static str,
+    pub toast_favourite_removed: &'static str,
+    pub toast_pinned: &'static str,
+    pub toast_unpinned: &'static str,
+    pub no_images_left: &'static str,
+    pub toast_exported: &'static str,
+    pub prompt_export_format: &'static str,
+    pub export_format_ansi: &'static str,
+    pub export_format_html: &'static str,
+    pub export_format_svg: &'static str,
+    pub export_format_png: &'static str,
+    pub toast_aspect_enabled: &'static str,
+    pub toast_aspect_disabled: &'static str,
+    pub toast_effects_applied: &'static str,
+    pub toast_effects_next: &'static str,
+    pub settings_duotone: &'static str,
+    pub prompt_duotone: &'static str,
+    pub duotone_changed: &'static str,
+    pub duotone_disabled: &'static str,
+    pub toast_transform_applied: &'static str,
+    pub toast_transform_next: &'static str,
+    pub toast_width_applied: &'static str,
+    pub toast_width_next: &'static str,
+    pub reveal_paused: &'static str,
+    pub toast_slideshow_started: &'static str,
+    pub toast_slideshow_stopped: &'static str,
+    pub toast_shuffled: &'static str,
+
+    pub calibration_prompt: &'static str,
+    pub calibration_square: &'static str,
+    pub calibration_too_tall: &'static str,
+    pub calibration_too_wide: &'static str,
+    pub calibration_cancel: &'static str,
+
+    pub sound_off: &'static str,
+    pub sound_bell: &'static str,
+    pub sound_command: &'static str,
+    pub sound_command_failed: &'static str,
+
+    pub image_header: &'static str,
+    pub image_header_with_note: &'static str,
+    pub comparison_header: &'static str,
+    pub gallery_header: &'static str,
+    pub gallery_needs_terminal: &'static str,
+    pub status_bar: &'static str,
+    pub status_hints_generator: &'static str,
+    pub status_hints_load: &'static str,
+    pub timings_header: &'static str,
+    pub timing_download: &'static str,
+    pub timing_decode: &'static str,
+    pub timing_resize: &'static str,
+    pub timing_colorize: &'static str,
+    pub timing_render: &'static str,
+    pub timings_empty: &'static str,
+
+    pub no_image_left: &'static str,
+    pub no_images_registered: &'static str,
+    pub evicted_image: &'static str,
+    pub print_io_error: &'static str,
+    pub empty_image: &'static str,
+    pub clipboard_error: &'static str,
+    pub clipboard_too_large: &'static str,
+
+    pub image_loading_error: &'static str,
+    pub image_format_unknown: &'static str,
+    pub avif_feature_hint: &'static str,
+    pub invalid_width: &'static str,
+    pub width_limit_exceeded: &'static str,
+    pub pixel_limit_exceeded: &'static str,
+    pub cell_limit_exceeded: &'static str,
+    pub invalid_crop: &'static str,
+    pub invalid_duotone: &'static str,
+    pub ramp_empty: &'static str,
+    pub ramp_unsorted: &'static str,
+    pub conversion_failed: &'static str,
+    pub conversion_cancelled: &'static str,
+
+    pub browser_error: &'static str,
+    pub connection_error: &'static str,
+    pub searcher_error: &'static str,
+    pub selector_error: &'static str,
+    pub no_results: &'static str,
+    pub request_error: &'static str,
+    pub status_error: &'static str,
+    pub body_error: &'static str,
+    pub duplicate_image: &'static str,
+    pub download_resuming: &'static str,
+
+    pub save_path_error: &'static str,
+    pub save_error: &'static str,
+    pub load_error: &'static str,
+    pub delete_error: &'static str,
+    pub favourite_error: &'static str,
+    pub not_a_dir_error: &'static str,
+    pub opening_dir_error: &'static str,
+    pub storage_io_error: &'static str,
+    pub file_read_failed: &'static str,
+    pub interrupt_handler_failed: &'static str,
+    pub skipping_file: &'static str,
+
+    pub unterminated_sequence: &'static str,
+    pub invalid_sgr_parameter: &'static str,
+
+    pub no_config_dir: &'static str,
+    pub config_parse_error: &'static str,
+    pub config_io_error: &'static str,
+
+    #[cfg(feature = "screen-capture")]
+    pub no_screen: &'static str,
+    #[cfg(feature = "screen-capture")]
+    pub invalid_region: &'static str,
+    #[cfg(feature = "screen-capture")]
+    pub capture_failed: &'static str,
+    #[cfg(feature = "screen-capture")]
+    pub screen_description: &'static str,
+    #[cfg(feature = "screen-capture")]
+    pub screen_primary: &'static str,
+    #[cfg(feature = "webcam")]
+    pub no_camera: &'static str,
+    #[cfg(feature = "webcam")]
+    pub camera_error: &'static str,
+    #[cfg(any(feature = "screen-capture", feature = "webcam"))]
+    pub frame_encoding_error: &'static str,
+}
+
+pub const ENGLISH: Messages = Messages {
+    banner: "Welcome to Colourful Words!",
+    log_info_prefix: "INFO",
+    log_success_prefix: "Success",
+    log_error_prefix: "ERROR",
+    input_error: "Failed to get user input: {}. Please try again.",
+    unknown_argument: "Ignoring unknown argument '{}'",
+    missing_argument_value: "Missing value for '{}'",
+
+    main_menu_generator: "Generator mode",
+    main_menu_load: "Load saved images",
+    load_menu_browse: "Browse saved images",
+    load_menu_browse_favourites: "Browse favourites",
+    load_menu_gallery_stats: "Gallery stats",
+    gallery_title: "Gallery statistics",
+    gallery_image_count: "Images: {}",
+    gallery_disk_usage: "Disk usage: {}",
+    gallery_largest: "Largest image: {} ({})",
+    gallery_oldest: "Oldest image: {}",
+    gallery_by_keyword: "Images by keyword:",
+    gallery_ansi_group: "ANSI art",
+    main_menu_settings: "Change settings",
+    main_menu_quit: "Quit",
+
+    prompt_width: "Enter image width as columns, terminal percentage (80%), small/medium/full or auto to fit the whole terminal (tip: enter 100 and zoom out with CRTL-)",
+    prompt_crop: "Crop before converting: left,top,width,height or centre x,centre y,zoom in percent (leave empty for the whole frame)",
+    prompt_source: "Choose image source",
+    source_search: "Bing image search",
+    #[cfg(feature = "screen-capture")]
+    source_screen: "Screen capture",
+    #[cfg(feature = "webcam")]
+    source_webcam: "Webcam snapshots",
+    #[cfg(feature = "mock-source")]
+    source_mock: "Offline sample images",
+    mock_source_enabled: "{} is set, using offline sample images",
+    #[cfg(feature = "screen-capture")]
+    prompt_screen: "Choose screen",
+    #[cfg(feature = "screen-capture")]
+    prompt_capture_region: "Enter capture region as x,y,width,height (leave empty for the whole screen)",
+    #[cfg(feature = "webcam")]
+    prompt_webcam: "Choose webcam",
+    prompt_keyword: "Enter keyword (Tab cycles through suggestions)",
+    keyword_new: "Enter a new keyword",
+    prompt_recent_keyword: "Choose a recent keyword",
+    prompt_printing_rate: "Enter new printing rate in milliseconds (default is 5 ms)",
+    invalid_printing_rate: "Invalid printing rate. Please enter an integer [0 - 65535].",
+    prompt_sound: "Sound feedback",
+    sound_option_off: "Off",
+    sound_option_bell: "Terminal bell (1 ring on save, 2 on copy, 3 on error)",
+    sound_option_command: "External command (receives save/copy/error as its last argument)",
+    prompt_sound_command: "Enter the command to run",
+
+    settings_title: "Settings",
+    settings_save_location: "Change image save location",
+    settings_load_location: "Change image loading location",
+    settings_printing_rate: "Change image printing rate",
+    settings_character_ramp: "Change character ramp",
+    settings_render_style: "Change render style",
+    settings_clear_mode: "Change screen clearing",
+    settings_timings: "Toggle per-image timing breakdown",
+    settings_slideshow_interval: "Change slideshow interval",
+    settings_image_cache_limit: "Change how many images are kept in memory",
+    settings_shuffle_load: "Toggle shuffled load order",
+    settings_allowed_hosts: "Change allowed download hosts",
+    settings_denied_hosts: "Change denied download hosts",
+    settings_search_url: "Change search URL template",
+    settings_image_selector: "Change result image selector",
+    settings_online_suggestions: "Toggle online keyword suggestions",
+    settings_calibrate: "Calibrate terminal font aspect ratio",
+    settings_sound: "Change sound feedback",
+    settings_language: "Change language",
+    go_back: "Go back",
+    prompt_save_location: "Enter new saving directory path (it must already exist)",
+    save_location_changed: "Saving location changed to: {}",
+    prompt_load_location: "Enter new loading directory path (it must already exist)",
+    load_location_changed: "Loading location changed to: {}",
+    printing_rate_changed: "Printing rate changed to: {}",
+    prompt_character_ramp: "Enter characters from darkest to lightest, e.g. @%#*+=-:. (leave empty for the default)",
+    prompt_ramp_preset: "Choose a character ramp",
+    ramp_preset_classic: "Classic ASCII (@#S%&?*=+-:,.)",
+    ramp_preset_shading: "Unicode shading blocks (█▓▒░) - reads better at small widths",
+    ramp_preset_custom: "Custom...",
+    character_ramp_changed: "Character ramp changed to: {}",
+    settings_detail_threshold: "Change detail-adaptive ramp",
+    prompt_detail_threshold: "Brightness variance below which a region counts as flat and gets sparse characters (e.g. {}, leave empty to turn off)",
+    invalid_detail_threshold: "The threshold must be a whole number from 1 to {}",
+    detail_threshold_changed: "Detail-adaptive ramp on: regions with a variance below {} count as flat",
+    prompt_slideshow_interval: "Enter the number of seconds each image stays on screen during a slideshow (default is {} s)",
+    invalid_slideshow_interval: "The interval must be a whole number of seconds from 1 to {}",
+    prompt_image_cache_limit: "Enter how many images keep their pixels in memory while browsing - older ones are rebuilt when you return to them (0 keeps all, default is {})",
+    invalid_image_cache_limit: "The limit must be a whole number from 0 to {}",
+    prompt_jump: "Enter the number of the image to jump to (1 - {})",
+    invalid_jump: "There is no image {} - enter a number from 1 to {}",
+    slideshow_interval_changed: "Slideshow interval changed to: {} s",
+    image_cache_limit_changed: "Images kept in memory changed to: {}",
+    shuffle_load_enabled: "Shuffled load order enabled - saved images are shown in a random order",
+    shuffle_load_disabled: "Shuffled load order disabled - saved images are shown in directory order",
+    detail_threshold_disabled: "Detail-adaptive ramp off",
+    prompt_render_style: "Choose render style",
+    render_style_ascii: "ASCII character ramp",
+    render_style_braille: "Braille dots (2x4 pixels per cell)",
+    render_style_half_block: "Half blocks (2 full-colour pixels per cell)",
+    render_style_quadrant: "Quadrant blocks (2×2 pixels per cell, pure text)",
+    render_style_mosaic: "Mosaic (background colours only, no glyphs)",
+    render_style_edge_aware: "Edge-aware ASCII (directional glyphs along outlines)",
+    render_style_sixel: "Sixel graphics (real pixels on xterm, mlterm, foot...)",
+    render_style_kitty: "Kitty graphics (the actual photo on Kitty, WezTerm, Ghostty - ASCII elsewhere)",
+    render_style_iterm2: "iTerm2 inline images (the actual photo on iTerm2 - ASCII elsewhere)",
+    render_style_emoji: "Emoji squares (🟥🟩🟦 - survives chat apps that strip colours)",
+    render_style_changed: "Render style changed to: {}",
+    settings_colour_mode: "Change colour mode",
+    prompt_colour_mode: "Choose colour mode",
+    colour_mode_auto: "Automatic (detected: {})",
+    colour_mode_true_colour: "True colour (24-bit)",
+    colour_mode_ansi_256: "256 colours",
+    colour_mode_ansi_16: "16 basic colours",
+    colour_mode_monochrome: "Monochrome (plain characters, no escape codes)",
+    colour_mode_changed: "Colour mode changed to: {}",
+    settings_palette: "Change theme palette",
+    prompt_palette: "Match the output colours to a terminal theme",
+    palette_off: "Off (keep the image's own colours)",
+    palette_changed: "Theme palette changed to: {}",
+    settings_dithering: "Change dithering",
+    prompt_dithering: "Choose how colours and brightness are dithered",
+    dithering_off: "Off",
+    dithering_floyd_steinberg: "Floyd-Steinberg (smooth error diffusion)",
+    dithering_bayer: "Bayer {}×{} (ordered, retro look)",
+    dithering_changed: "Dithering changed to: {}",
+    settings_auto_levels: "Toggle auto levels (stretch contrast)",
+    auto_levels_enabled: "Auto levels enabled - low-contrast images now use the whole character ramp",
+    auto_levels_disabled: "Auto levels disabled",
+    settings_double_width: "Toggle double width pixels (pixel art)",
+    double_width_enabled: "Double width pixels enabled - every pixel is drawn as two characters, turn off aspect correction for square pixels",
+    double_width_disabled: "Double width pixels disabled",
+    settings_luminance: "Change brightness formula",
+    prompt_luminance: "Choose brightness formula",
+    luminance_rec709: "Perceptual, Rec. 709 (modern displays)",
+    luminance_rec601: "Perceptual, Rec. 601 (classic video)",
+    luminance_average: "Plain RGB average",
+    luminance_changed: "Brightness formula changed to: {}",
+    settings_saturation: "Change saturation boost",
+    prompt_saturation: "Enter a saturation multiplier between 0 and {} (1 leaves colours unchanged)",
+    invalid_saturation: "Invalid saturation - enter a number between 0 and {}",
+    saturation_changed: "Saturation multiplier changed to: {}",
+    settings_inversion: "Change invert mode (light terminal themes)",
+    prompt_inversion: "Choose invert mode",
+    inversion_off: "Off (dark terminal background)",
+    inversion_characters: "Invert characters",
+    inversion_all: "Invert characters and colours",
+    inversion_changed: "Invert mode changed to: {}",
+    settings_effects: "Change colour effects",
+    prompt_effects: "Choose colour effects (space toggles, enter confirms)",
+    prompt_effects_order: "Order the effects - they are applied from top to bottom",
+    effect_sepia: "Sepia",
+    effect_invert: "Invert colours",
+    effect_posterize: "Posterize",
+    effect_hue_rotate: "Rotate hue",
+    effects_none: "None",
+    effects_changed: "Colour effects changed to: {}",
+    settings_alpha: "Change transparency handling",
+    prompt_alpha: "How should transparent pixels be shown?",
+    alpha_background: "Blend onto a background colour",
+    alpha_checkerboard: "Blend onto a checkerboard",
+    alpha_transparent: "Leave fully transparent pixels empty (terminal background)",
+    prompt_alpha_background: "Enter the background colour as hex, e.g. #000000",
+    invalid_alpha_background: "Invalid colour - enter six hex digits, e.g. #1e1e2e",
+    alpha_changed: "Transparency handling changed to: {} (background {})",
+    settings_transform: "Rotate and flip images",
+    prompt_rotation: "Rotate images clockwise by",
+    prompt_flips: "Flip images (space toggles, enter confirms)",
+    transform_rotated: "rotated {}°",
+    transform_flipped_horizontally: "flipped horizontally",
+    transform_flipped_vertically: "flipped vertically",
+    transform_none: "Unchanged",
+    transform_changed: "Rotation and flips changed to: {}",
+    settings_caption: "Change image caption",
+    prompt_caption: "Burn a caption into the bottom row of each image",
+    caption_off: "No caption",
+    caption_image_name: "Image name (the search keyword)",
+    caption_custom: "Custom text...",
+    prompt_caption_text: "Enter the caption text",
+    caption_changed: "Caption changed to: {}",
+    prompt_clear_mode: "How should images replace each other on screen?",
+    clear_mode_full: "Clear the whole screen (may wipe scrollback)",
+    clear_mode_alternate: "Use the alternate screen while browsing",
+    clear_mode_preserve: "Keep scrollback and redraw below the cursor",
+    settings_reveal_style: "Change reveal animation",
+    prompt_reveal_style: "Choose how new images appear",
+    reveal_style_random: "Random cells",
+    reveal_style_spiral: "Spiral, from the edges inwards",
+    reveal_style_centre_out: "From the centre outwards",
+    reveal_style_matrix: "Matrix rain, columns falling into place",
+    reveal_style_wipe_down: "Row by row, top to bottom",
+    reveal_style_wipe_up: "Row by row, bottom to top",
+    reveal_style_fade_in: "Fading in from grey to full colour",
+    reveal_style_changed: "Reveal animation changed to: {}",
+    clear_mode_changed: "Screen clearing changed to: {}",
+    timings_enabled: "Per-image timing breakdown enabled",
+    timings_disabled: "Per-image timing breakdown disabled",
+    prompt_allowed_hosts: "Enter allowed hosts separated by commas, subdomains included (leave empty to allow all)",
+    allowed_hosts_changed: "Allowed download hosts changed to: {}",
+    prompt_denied_hosts: "Enter denied hosts separated by commas, subdomains included (leave empty to deny none)",
+    denied_hosts_changed: "Denied download hosts changed to: {}",
+    prompt_search_url: "Enter search URL with {keyword} where the keyword goes (leave empty for the Bing default)",
+    search_url_changed: "Search URL template changed to: {}",
+    prompt_image_selector: "Enter CSS selector matching result images (leave empty for the Bing default)",
+    image_selector_changed: "Result image selector changed to: {}",
+    online_suggestions_enabled: "Online keyword suggestions enabled",
+    online_suggestions_disabled: "Online keyword suggestions disabled",
+    host_list_empty: "(none)",
+    session_report: "Session finished, images shown: {}",
+    host_statistics: "Result hosts - permitted: {}, denied: {}, not on the allow list: {}",
+    aspect_ratio_changed: "Cell aspect ratio changed to: {}",
+    calibration_cancelled: "Calibration cancelled.",
+    wizard_welcome: "No configuration found - running the first-time setup.",
+    wizard_terminal: "Detected terminal: {} columns x {} rows, colour support: {}",
+    wizard_calibration: "Calibrate the font aspect ratio so images are not stretched.",
+    wizard_prompt_width: "Choose the default image width",
+    wizard_width_preset: "{} ({}% of the terminal width)",
+    wizard_width_fit: "auto (fit the whole terminal)",
+    default_width_changed: "Default image width set to: {}",
+    wizard_prompt_save_location: "Directory for saved images",
+    wizard_prompt_load_location: "Directory to load images from",
+    wizard_directory_ready: "Directory ready: {}",
+    wizard_directory_error: "Could not create directory {}: {}",
+    wizard_checking_browser: "Checking whether Chrome can be started for image searches...",
+    wizard_browser_available: "Chrome is available.",
+    wizard_browser_unavailable: "Chrome could not be started, online image search will not work: {}",
+    wizard_complete: "Setup complete - settings saved.",
+    sound_changed: "Sound feedback changed to: {}",
+    prompt_language: "Choose language",
+    language_changed: "Language changed to: {}",
+
+    help_navigation: "Press 'B' to go back to previous image or 'N' to swap to the next one.",
+    help_jump: "Press 'G' to jump straight to an image by its number.",
+    help_gallery: "Press 'T' to browse thumbnails of all images so far - choose one with the arrow keys and open it with Space, or leave with Q.",
+    help_save: "Press 'S' to save the current image in the specified folder.",
+    help_note: "Press 'E' to enter a note for the current image - it is saved along with the image.",
+    prompt_note: "Enter note (leave empty to remove it)",
+    prompt_save_name: "Save as",
+    help_copy: "Press 'C' to copy a colourless version of the current image to clipboard.",
+    help_copy_ansi: "Press 'X' to copy the current image with its colours (ANSI escape codes) to clipboard.",
+    help_delete: "Press 'D' to delete the file of the current image (after confirming).",
+    help_favourite: "Press '*' to mark the current image as a favourite, or unmark it ('F' applies colour effects here).",
+    help_favourite_load: "Press 'F' (or '*') to mark the current image as a favourite, or unmark it - the mark is written into its .cwi file.",
+    help_compare: "Press '|' to pin the current image and compare the next ones beside it; press it again to unpin.",
+    help_export: "Press 'O' to export the current image to the save directory as a standalone .ans file (ANSI escape codes), an HTML page, an SVG image or a PNG picture ('E' is taken by the note key).",
+    help_aspect: "Press 'A' to toggle aspect correction of the current image.",
+    help_effects: "Press 'F' to change the colour effects - the current image is converted again right away.",
+    help_transform: "Press 'R' to rotate the images by 90°, 'H' to flip them horizontally or 'V' to flip them vertically.",
+    help_width: "Press 'W' to convert the current image again at a new width.",
+    help_skip_reveal: "Press 'Space' or 'Enter' while an image is being revealed to show the rest of it at once.",
+    help_pause_reveal: "Press 'P' while an image is being revealed to pause the reveal and again to resume it.",
+    help_printing_rate: "Press '+' or '-' to speed up or slow down the printing rate, also while an image is being revealed.",
+    help_slideshow: "Press 'L' to start a slideshow that moves on to the next image every few seconds and loops back to the first one - any key stops it.",
+    help_shuffle: "Press 'U' to shuffle the order of the images not shown yet.",
+    help_pan: "Use the arrow keys to pan around an image that is larger than the terminal.",
+    help_animation: "Animated GIFs play in a loop - press any key to stop the playback.",
+    help_cancel: "Press 'Esc' while an image is being converted to cancel the conversion.",
+    help_quit: "Press 'Q' to quit the mode.",
+    toast_copied: "Image copied to clipboard.",
+    toast_copied_ansi: "Copied with colours to clipboard",
+    toast_copied_without_colours: "Copied without colours - with them it is too large for the terminal clipboard",
+    toast_saved: "Image {} saved successfully.",
+    prompt_delete: "Delete {}?",
+    toast_deleted: "Deleted {}.",
+    toast_favourite_added: "Marked as favourite",
+    toast_favourite_unsaved: "Marked as favourite - kept when you save the image",
+    toast_favourite_removed: "Favourite mark removed",
+    toast_pinned: "Pinned - move between images to compare",
+    toast_unpinned: "Unpinned",
+    no_images_left: "No images left to show.",
+    toast_exported: "Image exported as {}.",
+    prompt_export_format: "Export as",
+    export_format_ansi: "ANSI text (.ans) - cat it in any terminal",
+    export_format_html: "HTML page (.html) - embed it in a blog or open it in a browser",
+    export_format_svg: "SVG image (.svg) - scalable, for printing posters",
+    export_format_png: "PNG picture (.png) - for platforms that strip text formatting",
+    toast_aspect_enabled: "Aspect correction enabled.",
+    toast_aspect_disabled: "Aspect correction disabled.",
+    toast_effects_applied: "Colour effects: {}",
+    toast_effects_next: "Colour effects changed - they apply from the next image on.",
+    settings_duotone: "Change duotone",
+    prompt_duotone: "Shadow and highlight colours for a duotone, e.g. {} (leave empty to turn off)",
+    duotone_changed: "Duotone changed to: {}",
+    duotone_disabled: "Duotone off - the images keep their own colours",
+    toast_transform_applied: "Orientation: {}",
+    toast_transform_next: "Orientation changed - it applies from the next image on.",
+    toast_width_applied: "Width: {}",
+    toast_width_next: "Width changed - it applies from the next image on.",
+    reveal_paused: "Reveal paused - press 'P' to resume or 'Space' to show the rest at once",
+    toast_slideshow_started: "Slideshow started - a new image every {} s, press any key to stop",
+    toast_slideshow_stopped: "Slideshow stopped",
+    toast_shuffled: "Shuffled the {} images not shown yet",
+
+    calibration_prompt: "How does the block above look?",
+    calibration_square: "It looks square",
+    calibration_too_tall: "It is too tall",
+    calibration_too_wide: "It is too wide",
+    calibration_cancel: "Cancel",
+
+    sound_off: "off",
+    sound_bell: "terminal bell",
+    sound_command: "command '{}'",
+    sound_command_failed: "Failed to run sound command '{}': {}",
+
+    image_header: "Image {}",
+    image_header_with_note: "Image {} - {}",
+    comparison_header: "Image {} │ Image {}",
+    gallery_header: "Gallery - image {} of {} - arrows: choose  Space: open  Q: back",
+    gallery_needs_terminal: "The gallery needs a terminal - output is being streamed to a file or pipe.",
+    status_bar: "Image {}/{} - {} - {}×{}",
+    status_hints_generator: "B/N: move  S: save  C/X: copy  F: effects  W: width  L: slideshow  Q: quit",
+    status_hints_load: "B/N: move  C/X: copy  D: delete  U: shuffle  L: slideshow  Q: quit",
+    timings_header: "Timings: {}",
+    timing_download: "download",
+    timing_decode: "decode",
+    timing_resize: "resize",
+    timing_colorize: "colorize",
+    timing_render: "render",
+    timings_empty: "no timings recorded",
+
+    no_image_left: "No images left.",
+    no_images_registered: "No images registered.",
+    evicted_image: "This image was dropped from memory and could not be brought back.",
+    print_io_error: "IO Error during print: {}",
+    empty_image: "Cannot print an empty image.",
+    clipboard_error: "Failed to copy the current image to clipboard.",
+    clipboard_too_large: "The image is too large for the terminal clipboard (OSC 52 takes at most {} encoded bytes).",
+
+    image_loading_error: "Failed to load the {} image: {}",
+    image_format_unknown: "Failed to load image: {}",
+    avif_feature_hint: "AVIF images need a build with `--features avif` and the dav1d library installed",
+    invalid_width: "Invalid width. Enter a positive integer, a percentage like 80% or one of: small, medium, full, auto",
+    width_limit_exceeded: "Width of {} columns exceeds the limit of {} (max_width in the config file)",
+    pixel_limit_exceeded: "Image has {} pixels, more than the limit of {} (max_decoded_pixels in the config file)",
+    cell_limit_exceeded: "Result would have {} cells, more than the limit of {} (max_cells in the config file)",
+    invalid_crop: "Invalid crop - enter left,top,width,height or centre x,centre y,zoom in percent, e.g. 25,10,50,60 or 50,40,200",
+    invalid_duotone: "Invalid duotone - enter two hex colours separated by a comma, e.g. {}",
+    ramp_empty: "Character ramp must contain at least one character",
+    ramp_unsorted: "Character ramp must go from darkest to lightest, but '{}' looks darker than '{}' before it",
+    conversion_failed: "Failed to convert image '{}': {}",
+    conversion_cancelled: "Conversion cancelled.",
+
+    browser_error: "Failed to initialize browser: {}",
+    connection_error: "Failed to open {}: {}",
+    searcher_error: "Search page {} did not finish loading: {}",
+    selector_error: "No elements matching '{}' found on {}: {}",
+    no_results: "No results found for keyword '{}'",
+    request_error: "Failed to send request to {}: {}",
+    status_error: "Request to {} failed with status: {}",
+    body_error: "Failed to read bytes from {}: {}",
+    duplicate_image: "Skipping duplicate image from {}",
+    download_resuming: "Download from {} interrupted after {} bytes ({}), resuming",
+
+    save_path_error: "Given save path is not a valid directory",
+    save_error: "Could not save image to the given save directory",
+    load_error: "Image {} couldn't be loaded",
+    delete_error: "Could not delete {}.",
+    favourite_error: "Could not mark {} - only writable .cwi images can be favourites.",
+    not_a_dir_error: "Given path is not a directory - it may be a file instead",
+    opening_dir_error: "Failed to open the given directory",
+    storage_io_error: "IO error: {}",
+    file_read_failed: "Failed to read file: {}",
+    interrupt_handler_failed: "Failed to set up the Ctrl+C handler, the terminal may not be restored when interrupted: {}",
+    skipping_file: "Skipping problematic file during load: {}",
+
+    unterminated_sequence: "Escape sequence is not terminated",
+    invalid_sgr_parameter: "Invalid SGR parameter '{}'",
+
+    no_config_dir: "Could not determine the configuration directory",
+    config_parse_error: "Malformed configuration file at line {}",
+    config_io_error: "IO error while accessing the configuration: {}",
+
+    #[cfg(feature = "screen-capture")]
+    no_screen: "No screen found under the given index",
+    #[cfg(feature = "screen-capture")]
+    invalid_region: "Region must be given as x,y,width,height with a positive width and height",
+    #[cfg(feature = "screen-capture")]
+    capture_failed: "Failed to capture the screen: {}",
+    #[cfg(feature = "screen-capture")]
+    screen_description: "Screen {} ({}x{}{})",
+    #[cfg(feature = "screen-capture")]
+    screen_primary: ", primary",
+    #[cfg(feature = "webcam")]
+    no_camera: "No webcam found",
+    #[cfg(feature = "webcam")]
+    camera_error: "Webcam failure: {}",
+    #[cfg(any(feature = "screen-capture", feature = "webcam"))]
+    frame_encoding_error: "Failed to encode the captured frame",
+};
+
+pub const GERMAN: Messages = Messages {
+    banner: "Willkommen bei Colourful Words!",
+    log_info_prefix: "INFO",
+    log_success_prefix: "Erfolg",
+    log_error_prefix: "FEHLER",
+    input_error: "Eingabe konnte nicht gelesen werden: {}. Bitte erneut versuchen.",
+    unknown_argument: "Unbekanntes Argument '{}' wird ignoriert",
+    missing_argument_value: "Fehlender Wert für '{}'",
+
+    main_menu_generator: "Generatormodus",
+    main_menu_load: "Gespeicherte Bilder laden",
+    load_menu_browse: "Gespeicherte Bilder durchblättern",
+    load_menu_browse_favourites: "Favoriten durchblättern",
+    load_menu_gallery_stats: "Galerie-Statistik",
+    gallery_title: "Galerie-Statistik",
+    gallery_image_count: "Bilder: {}",
+    gallery_disk_usage: "Speicherbedarf: {}",
+    gallery_largest: "Größtes Bild: {} ({})",
+    gallery_oldest: "Ältestes Bild: {}",
+    gallery_by_keyword: "Bilder nach Suchbegriff:",
+    gallery_ansi_group: "ANSI-Art",
+    main_menu_settings: "Einstellungen ändern",
+    main_menu_quit: "Beenden",
+
+    prompt_width: "Bildbreite als Spaltenzahl, Prozent des Terminals (80%), small/medium/full oder auto (passt ins ganze Terminal) eingeben (Tipp: 100 eingeben und mit STRG- herauszoomen)",
+    prompt_crop: "Vor dem Umwandeln zuschneiden: links,oben,Breite,Höhe oder Mitte x,Mitte y,Zoom in Prozent (leer lassen für das ganze Bild)",
+    prompt_source: "Bildquelle wählen",
+    source_search: "Bing-Bildersuche",
+    #[cfg(feature = "screen-capture")]
+    source_screen: "Bildschirmaufnahme",
+    #[cfg(feature = "webcam")]
+    source_webcam: "Webcam-Schnappschüsse",
+    #[cfg(feature = "mock-source")]
+    source_mock: "Offline-Beispielbilder",
+    mock_source_enabled: "{} ist gesetzt, es werden Offline-Beispielbilder verwendet",
+    #[cfg(feature = "screen-capture")]
+    prompt_screen: "Bildschirm wählen",
+    #[cfg(feature = "screen-capture")]
+    prompt_capture_region: "Aufnahmebereich als x,y,Breite,Höhe eingeben (leer lassen für den ganzen Bildschirm)",
+    #[cfg(feature = "webcam")]
+    prompt_webcam: "Webcam wählen",
+    prompt_keyword: "Suchbegriff eingeben (Tab wechselt durch Vorschläge)",
+    keyword_new: "Neuen Suchbegriff eingeben",
+    prompt_recent_keyword: "Einen der letzten Suchbegriffe wählen",
+    prompt_printing_rate: "Neue Zeichenrate in Millisekunden eingeben (Standard: 5 ms)",
+    invalid_printing_rate: "Ungültige Zeichenrate. Bitte eine Ganzzahl [0 - 65535] eingeben.",
+    prompt_sound: "Tonrückmeldung",
+    sound_option_off: "Aus",
+    sound_option_bell: "Terminalglocke (1 Signal beim Speichern, 2 beim Kopieren, 3 bei Fehlern)",
+    sound_option_command: "Externer Befehl (erhält save/copy/error als letztes Argument)",
+    prompt_sound_command: "Auszuführenden Befehl eingeben",
+
+    settings_title: "Einstellungen",
+    settings_save_location: "Speicherort der Bilder ändern",
+    settings_load_location: "Ladeort der Bilder ändern",
+    settings_printing_rate: "Zeichenrate der Bilder ändern",
+    settings_character_ramp: "Zeichenskala ändern",
+    settings_render_style: "Darstellungsstil ändern",
+    settings_clear_mode: "Bildschirmlöschung ändern",
+    settings_timings: "Zeitaufschlüsselung pro Bild umschalten",
+    settings_slideshow_interval: "Intervall der Diashow ändern",
+    settings_image_cache_limit: "Anzahl der im Speicher gehaltenen Bilder ändern",
+    settings_shuffle_load: "Zufällige Ladereihenfolge umschalten",
+    settings_allowed_hosts: "Erlaubte Download-Hosts ändern",
+    settings_denied_hosts: "Gesperrte Download-Hosts ändern",
+    settings_search_url: "Vorlage der Such-URL ändern",
+    settings_image_selector: "Selektor für Ergebnisbilder ändern",
+    settings_online_suggestions: "Online-Vorschläge für Suchbegriffe umschalten",
+    settings_calibrate: "Seitenverhältnis der Terminalschrift kalibrieren",
+    settings_sound: "Tonrückmeldung ändern",
+    settings_language: "Sprache ändern",
+    go_back: "Zurück",
+    prompt_save_location: "Neuen Speicherordner eingeben (er muss bereits existieren)",
+    save_location_changed: "Speicherort geändert auf: {}",
+    prompt_load_location: "Neuen Ladeordner eingeben (er muss bereits existieren)",
+    load_location_changed: "Ladeort geändert auf: {}",
+    printing_rate_changed: "Zeichenrate geändert auf: {}",
+    prompt_character_ramp: "Zeichen von dunkel nach hell eingeben, z. B. @%#*+=-:. (leer lassen für die Vorgabe)",
+    prompt_ramp_preset: "Zeichenskala wählen",
+    ramp_preset_classic: "Klassisches ASCII (@#S%&?*=+-:,.)",
+    ramp_preset_shading: "Unicode-Schattierungsblöcke (█▓▒░) - besser lesbar bei kleinen Breiten",
+    ramp_preset_custom: "Eigene...",
+    character_ramp_changed: "Zeichenskala geändert auf: {}",
+    settings_detail_threshold: "Detailabhängige Zeichenskala ändern",
+    prompt_detail_threshold: "Helligkeitsvarianz, unter der ein Bereich als flach gilt und wenige Zeichen bekommt (z. B. {}, leer lassen zum Ausschalten)",
+    invalid_detail_threshold: "Der Schwellenwert muss eine ganze Zahl von 1 bis {} sein",
+    detail_threshold_changed: "Detailabhängige Zeichenskala an: Bereiche mit einer Varianz unter {} gelten als flach",
+    prompt_slideshow_interval: "Anzahl der Sekunden eingeben, die jedes Bild während einer Diashow angezeigt wird (Standard: {} s)",
+    invalid_slideshow_interval: "Das Intervall muss eine ganze Zahl von Sekunden von 1 bis {} sein",
+    prompt_image_cache_limit: "Anzahl der Bilder eingeben, deren Pixel beim Durchblättern im Speicher bleiben - ältere werden bei der Rückkehr neu aufgebaut (0 behält alle, Standard: {})",
+    invalid_image_cache_limit: "Das Limit muss eine ganze Zahl von 0 bis {} sein",
+    prompt_jump: "Nummer des Bildes eingeben, zu dem gesprungen werden soll (1 - {})",
+    invalid_jump: "Es gibt kein Bild {} - eine Zahl von 1 bis {} eingeben",
+    slideshow_interval_changed: "Intervall der Diashow geändert auf: {} s",
+    image_cache_limit_changed: "Im Speicher gehaltene Bilder geändert auf: {}",
+    shuffle_load_enabled: "Zufällige Ladereihenfolge aktiviert - gespeicherte Bilder werden in zufälliger Reihenfolge angezeigt",
+    shuffle_load_disabled: "Zufällige Ladereihenfolge deaktiviert - gespeicherte Bilder werden in Verzeichnisreihenfolge angezeigt",
+    detail_threshold_disabled: "Detailabhängige Zeichenskala aus",
+    prompt_render_style: "Darstellungsstil wählen",
+    render_style_ascii: "ASCII-Zeichenskala",
+    render_style_braille: "Braille-Punkte (2x4 Pixel pro Zelle)",
+    render_style_half_block: "Halbblöcke (2 vollfarbige Pixel pro Zelle)",
+    render_style_quadrant: "Viertelblöcke (2×2 Pixel pro Zelle, reiner Text)",
+    render_style_mosaic: "Mosaik (nur Hintergrundfarben, keine Zeichen)",
+    render_style_edge_aware: "Kantenbewusstes ASCII (Richtungszeichen entlang von Umrissen)",
+    render_style_sixel: "Sixel-Grafik (echte Pixel in xterm, mlterm, foot...)",
+    render_style_kitty: "Kitty-Grafik (das echte Foto in Kitty, WezTerm, Ghostty - sonst ASCII)",
+    render_style_iterm2: "iTerm2-Inline-Bilder (das echte Foto in iTerm2 - sonst ASCII)",
+    render_style_emoji: "Emoji-Quadrate (🟥🟩🟦 - übersteht Chat-Apps ohne Farbunterstützung)",
+    render_style_changed: "Darstellungsstil geändert auf: {}",
+    settings_colour_mode: "Farbmodus ändern",
+    prompt_colour_mode: "Farbmodus wählen",
+    colour_mode_auto: "Automatisch (erkannt: {})",
+    colour_mode_true_colour: "Echtfarben (24 Bit)",
+    colour_mode_ansi_256: "256 Farben",
+    colour_mode_ansi_16: "16 Grundfarben",
+    colour_mode_monochrome: "Monochrom (reine Zeichen, keine Escape-Sequenzen)",
+    colour_mode_changed: "Farbmodus geändert auf: {}",
+    settings_palette: "Theme-Palette ändern",
+    prompt_palette: "Ausgabefarben an ein Terminal-Theme anpassen",
+    palette_off: "Aus (eigene Farben des Bildes behalten)",
+    palette_changed: "Theme-Palette geändert zu: {}",
+    settings_dithering: "Dithering ändern",
+    prompt_dithering: "Wählen, wie Farben und Helligkeit gedithert werden",
+    dithering_off: "Aus",
+    dithering_floyd_steinberg: "Floyd-Steinberg (weiche Fehlerdiffusion)",
+    dithering_bayer: "Bayer {}×{} (geordnet, Retro-Look)",
+    dithering_changed: "Dithering geändert zu: {}",
+    settings_auto_levels: "Automatische Tonwerte umschalten (Kontrast strecken)",
+    auto_levels_enabled: "Automatische Tonwerte aktiviert - kontrastarme Bilder nutzen jetzt die ganze Zeichenskala",
+    auto_levels_disabled: "Automatische Tonwerte deaktiviert",
+    settings_double_width: "Doppelt breite Pixel umschalten (Pixelkunst)",
+    double_width_enabled: "Doppelt breite Pixel aktiviert - jedes Pixel wird als zwei Zeichen gezeichnet, für quadratische Pixel die Seitenverhältniskorrektur ausschalten",
+    double_width_disabled: "Doppelt breite Pixel deaktiviert",
+    settings_luminance: "Helligkeitsformel ändern",
+    prompt_luminance: "Helligkeitsformel wählen",
+    luminance_rec709: "Wahrnehmungsbasiert, Rec. 709 (moderne Bildschirme)",
+    luminance_rec601: "Wahrnehmungsbasiert, Rec. 601 (klassisches Video)",
+    luminance_average: "Einfacher RGB-Durchschnitt",
+    luminance_changed: "Helligkeitsformel geändert auf: {}",
+    settings_saturation: "Sättigungsverstärkung ändern",
+    prompt_saturation: "Sättigungsfaktor zwischen 0 und {} eingeben (1 lässt die Farben unverändert)",
+    invalid_saturation: "Ungültige Sättigung - eine Zahl zwischen 0 und {} eingeben",
+    saturation_changed: "Sättigungsfaktor geändert auf: {}",
+    settings_inversion: "Invertierung ändern (helle Terminal-Designs)",
+    prompt_inversion: "Invertierung wählen",
+    inversion_off: "Aus (dunkler Terminal-Hintergrund)",
+    inversion_characters: "Zeichen invertieren",
+    inversion_all: "Zeichen und Farben invertieren",
+    inversion_changed: "Invertierung geändert auf: {}",
+    settings_effects: "Farbeffekte ändern",
+    prompt_effects: "Farbeffekte wählen (Leertaste wählt aus, Enter bestätigt)",
+    prompt_effects_order: "Effekte anordnen - sie werden von oben nach unten angewendet",
+    effect_sepia: "Sepia",
+    effect_invert: "Farben umkehren",
+    effect_posterize: "Posterisieren",
+    effect_hue_rotate: "Farbton drehen",
+    effects_none: "Keine",
+    effects_changed: "Farbeffekte geändert zu: {}",
+    settings_alpha: "Transparenzbehandlung ändern",
+    prompt_alpha: "Wie sollen transparente Pixel dargestellt werden?",
+    alpha_background: "Auf eine Hintergrundfarbe mischen",
+    alpha_checkerboard: "Auf ein Schachbrettmuster mischen",
+    alpha_transparent: "Vollständig transparente Pixel leer lassen (Terminalhintergrund)",
+    prompt_alpha_background: "Hintergrundfarbe als Hex eingeben, z. B. #000000",
+    invalid_alpha_background: "Ungültige Farbe - sechs Hex-Ziffern eingeben, z. B. #1e1e2e",
+    alpha_changed: "Transparenzbehandlung geändert zu: {} (Hintergrund {})",
+    settings_transform: "Bilder drehen und spiegeln",
+    prompt_rotation: "Bilder im Uhrzeigersinn drehen um",
+    prompt_flips: "Bilder spiegeln (Leertaste wählt aus, Enter bestätigt)",
+    transform_rotated: "um {}° gedreht",
+    transform_flipped_horizontally: "horizontal gespiegelt",
+    transform_flipped_vertically: "vertikal gespiegelt",
+    transform_none: "Unverändert",
+    transform_changed: "Drehung und Spiegelung geändert zu: {}",
+    settings_caption: "Bildunterschrift ändern",
+    prompt_caption: "Eine Bildunterschrift in die unterste Zeile jedes Bildes einbrennen",
+    caption_off: "Keine Bildunterschrift",
+    caption_image_name: "Bildname (das Suchwort)",
+    caption_custom: "Eigener Text...",
+    prompt_caption_text: "Text der Bildunterschrift eingeben",
+    caption_changed: "Bildunterschrift geändert zu: {}",
+    prompt_clear_mode: "Wie sollen Bilder einander auf dem Bildschirm ersetzen?",
+    clear_mode_full: "Ganzen Bildschirm löschen (kann den Verlauf löschen)",
+    clear_mode_alternate: "Beim Durchblättern den alternativen Bildschirm verwenden",
+    clear_mode_preserve: "Verlauf behalten und unterhalb des Cursors neu zeichnen",
+    settings_reveal_style: "Einblend-Animation ändern",
+    prompt_reveal_style: "Wählen, wie neue Bilder erscheinen",
+    reveal_style_random: "Zufällige Zellen",
+    reveal_style_spiral: "Spirale, von den Rändern nach innen",
+    reveal_style_centre_out: "Von der Mitte nach außen",
+    reveal_style_matrix: "Matrix-Regen, Spalten fallen an ihren Platz",
+    reveal_style_wipe_down: "Zeile für Zeile, von oben nach unten",
+    reveal_style_wipe_up: "Zeile für Zeile, von unten nach oben",
+    reveal_style_fade_in: "Einblenden von Grau zu voller Farbe",
+    reveal_style_changed: "Einblend-Animation geändert zu: {}",
+    clear_mode_changed: "Bildschirmlöschung geändert auf: {}",
+    timings_enabled: "Zeitaufschlüsselung pro Bild aktiviert",
+    timings_disabled: "Zeitaufschlüsselung pro Bild deaktiviert",
+    prompt_allowed_hosts: "Erlaubte Hosts durch Kommas getrennt eingeben, Subdomains eingeschlossen (leer lassen, um alle zu erlauben)",
+    allowed_hosts_changed: "Erlaubte Download-Hosts geändert auf: {}",
+    prompt_denied_hosts: "Gesperrte Hosts durch Kommas getrennt eingeben, Subdomains eingeschlossen (leer lassen, um keine zu sperren)",
+    denied_hosts_changed: "Gesperrte Download-Hosts geändert auf: {}",
+    prompt_search_url: "Such-URL mit {keyword} an der Stelle des Suchbegriffs eingeben (leer lassen für die Bing-Vorgabe)",
+    search_url_changed: "Vorlage der Such-URL geändert auf: {}",
+    prompt_image_selector: "CSS-Selektor für Ergebnisbilder eingeben (leer lassen für die Bing-Vorgabe)",
+    image_selector_changed: "Selektor für Ergebnisbilder geändert auf: {}",
+    online_suggestions_enabled: "Online-Vorschläge für Suchbegriffe aktiviert",
+    online_suggestions_disabled: "Online-Vorschläge für Suchbegriffe deaktiviert",
+    host_list_empty: "(keine)",
+    session_report: "Sitzung beendet, angezeigte Bilder: {}",
+    host_statistics: "Ergebnis-Hosts - zugelassen: {}, gesperrt: {}, nicht auf der Erlaubnisliste: {}",
+    aspect_ratio_changed: "Seitenverhältnis der Zellen geändert auf: {}",
+    calibration_cancelled: "Kalibrierung abgebrochen.",
+    wizard_welcome: "Keine Konfiguration gefunden - die Ersteinrichtung wird gestartet.",
+    wizard_terminal: "Erkanntes Terminal: {} Spalten x {} Zeilen, Farbunterstützung: {}",
+    wizard_calibration: "Seitenverhältnis der Schrift kalibrieren, damit Bilder nicht verzerrt werden.",
+    wizard_prompt_width: "Standard-Bildbreite wählen",
+    wizard_width_preset: "{} ({}% der Terminalbreite)",
+    wizard_width_fit: "auto (passt ins ganze Terminal)",
+    default_width_changed: "Standard-Bildbreite gesetzt auf: {}",
+    wizard_prompt_save_location: "Ordner für gespeicherte Bilder",
+    wizard_prompt_load_location: "Ordner, aus dem Bilder geladen werden",
+    wizard_directory_ready: "Ordner bereit: {}",
+    wizard_directory_error: "Ordner {} konnte nicht erstellt werden: {}",
+    wizard_checking_browser: "Prüfe, ob Chrome für die Bildsuche gestartet werden kann...",
+    wizard_browser_available: "Chrome ist verfügbar.",
+    wizard_browser_unavailable: "Chrome konnte nicht gestartet werden, die Online-Bildsuche wird nicht funktionieren: {}",
+    wizard_complete: "Einrichtung abgeschlossen - Einstellungen gespeichert.",
+    sound_changed: "Tonrückmeldung geändert auf: {}",
+    prompt_language: "Sprache wählen",
+    language_changed: "Sprache geändert auf: {}",
+
+    help_navigation: "'B' drücken, um zum vorherigen Bild zurückzukehren, oder 'N' für das nächste.",
+    help_jump: "'G' drücken, um direkt zu einem Bild mit seiner Nummer zu springen.",
+    help_gallery: "'T' drücken, um Vorschaubilder aller bisherigen Bilder zu durchblättern - mit den Pfeiltasten eines wählen und mit der Leertaste öffnen, oder mit Q zurück.",
+    help_save: "'S' drücken, um das aktuelle Bild im festgelegten Ordner zu speichern.",
+    help_note: "'E' drücken, um eine Notiz zum aktuellen Bild einzugeben - sie wird mit dem Bild gespeichert.",
+    prompt_note: "Notiz eingeben (leer lassen, um sie zu entfernen)",
+    prompt_save_name: "Speichern als",
+    help_copy: "'C' drücken, um eine farblose Version des aktuellen Bildes in die Zwischenablage zu kopieren.",
+    help_copy_ansi: "'X' drücken, um das aktuelle Bild mit seinen Farben (ANSI-Escape-Codes) in die Zwischenablage zu kopieren.",
+    help_delete: "'D' drücken, um die Datei des aktuellen Bildes (nach Bestätigung) zu löschen.",
+    help_favourite: "'*' drücken, um das aktuelle Bild als Favorit zu markieren oder die Markierung aufzuheben ('F' wendet hier Farbeffekte an).",
+    help_favourite_load: "'F' (oder '*') drücken, um das aktuelle Bild als Favorit zu markieren oder die Markierung aufzuheben - sie wird in seine .cwi-Datei geschrieben.",
+    help_compare: "'|' drücken, um das aktuelle Bild anzuheften und die nächsten daneben zu vergleichen; erneut drücken zum Lösen.",
+    help_export: "'O' drücken, um das aktuelle Bild als eigenständige .ans-Datei (ANSI-Escape-Codes), als HTML-Seite, als SVG-Bild oder als PNG-Bild in das Speicherverzeichnis zu exportieren ('E' ist bereits für Notizen belegt).",
+    help_aspect: "'A' drücken, um die Seitenverhältniskorrektur des aktuellen Bildes umzuschalten.",
+    help_effects: "'F' drücken, um die Farbeffekte zu ändern - das aktuelle Bild wird sofort neu umgewandelt.",
+    help_transform: "'R' drücken, um die Bilder um 90° zu drehen, 'H' zum horizontalen oder 'V' zum vertikalen Spiegeln.",
+    help_width: "'W' drücken, um das aktuelle Bild mit einer neuen Breite neu umzuwandeln.",
+    help_skip_reveal: "'Leertaste' oder 'Enter' drücken, während ein Bild aufgebaut wird, um den Rest sofort anzuzeigen.",
+    help_pause_reveal: "'P' drücken, während ein Bild aufgebaut wird, um den Aufbau anzuhalten, und erneut, um ihn fortzusetzen.",
+    help_printing_rate: "'+' oder '-' drücken, um die Zeichenrate zu erhöhen oder zu verringern, auch während ein Bild aufgebaut wird.",
+    help_slideshow: "'L' drücken, um eine Diashow zu starten, die alle paar Sekunden zum nächsten Bild wechselt und wieder beim ersten beginnt - eine beliebige Taste beendet sie.",
+    help_shuffle: "'U' drücken, um die Reihenfolge der noch nicht gezeigten Bilder zu mischen.",
+    help_pan: "Mit den Pfeiltasten in einem Bild scrollen, das größer als das Terminal ist.",
+    help_animation: "Animierte GIFs laufen in Schleife - eine beliebige Taste beendet die Wiedergabe.",
+    help_cancel: "'Esc' drücken, während ein Bild konvertiert wird, um die Konvertierung abzubrechen.",
+    help_quit: "'Q' drücken, um den Modus zu verlassen.",
+    toast_copied: "Bild in die Zwischenablage kopiert.",
+    toast_copied_ansi: "Mit Farben in die Zwischenablage kopiert",
+    toast_copied_without_colours: "Ohne Farben kopiert - mit ihnen ist es zu groß für die Zwischenablage des Terminals",
+    toast_saved: "Bild {} erfolgreich gespeichert.",
+    prompt_delete: "{} löschen?",
+    toast_deleted: "{} gelöscht.",
+    toast_favourite_added: "Als Favorit markiert",
+    toast_favourite_unsaved: "Als Favorit markiert - wird beim Speichern übernommen",
+    toast_favourite_removed: "Favoritenmarkierung entfernt",
+    toast_pinned: "Angeheftet - zum Vergleichen zwischen Bildern wechseln",
+    toast_unpinned: "Gelöst",
+    no_images_left: "Keine Bilder mehr zum Anzeigen.",
+    toast_exported: "Bild als {} exportiert.",
+    prompt_export_format: "Exportieren als",
+    export_format_ansi: "ANSI-Text (.ans) - in jedem Terminal mit cat anzeigbar",
+    export_format_html: "HTML-Seite (.html) - in einen Blog einbetten oder im Browser öffnen",
+    export_format_svg: "SVG-Bild (.svg) - skalierbar, zum Drucken von Postern",
+    export_format_png: "PNG-Bild (.png) - für Plattformen, die Textformatierung entfernen",
+    toast_aspect_enabled: "Seitenverhältniskorrektur aktiviert.",
+    toast_aspect_disabled: "Seitenverhältniskorrektur deaktiviert.",
+    toast_effects_applied: "Farbeffekte: {}",
+    toast_effects_next: "Farbeffekte geändert - sie gelten ab dem nächsten Bild.",
+    settings_duotone: "Duotone ändern",
+    prompt_duotone: "Schatten- und Lichterfarbe für ein Duotone, z. B. {} (leer lassen zum Ausschalten)",
+    duotone_changed: "Duotone geändert zu: {}",
+    duotone_disabled: "Duotone aus - die Bilder behalten ihre eigenen Farben",
+    toast_transform_applied: "Ausrichtung: {}",
+    toast_transform_next: "Ausrichtung geändert - sie gilt ab dem nächsten Bild.",
+    toast_width_applied: "Breite: {}",
+    toast_width_next: "Breite geändert - sie gilt ab dem nächsten Bild.",
+    reveal_paused: "Aufbau angehalten - 'P' zum Fortsetzen oder 'Leertaste', um den Rest sofort anzuzeigen",
+    toast_slideshow_started: "Diashow gestartet - alle {} s ein neues Bild, beliebige Taste zum Beenden",
+    toast_slideshow_stopped: "Diashow beendet",
+    toast_shuffled: "Die {} noch nicht gezeigten Bilder wurden gemischt",
+
+    calibration_prompt: "Wie sieht der Block oben aus?",
+    calibration_square: "Er sieht quadratisch aus",
+    calibration_too_tall: "Er ist zu hoch",
+    calibration_too_wide: "Er ist zu breit",
+    calibration_cancel: "Abbrechen",
+
+    sound_off: "aus",
+    sound_bell: "Terminalglocke",
+    sound_command: "Befehl '{}'",
+    sound_command_failed: "Tonbefehl '{}' konnte nicht ausgeführt werden: {}",
+
+    image_header: "Bild {}",
+    image_header_with_note: "Bild {} - {}",
+    comparison_header: "Bild {} │ Bild {}",
+    gallery_header: "Galerie - Bild {} von {} - Pfeiltasten: wählen  Leertaste: öffnen  Q: zurück",
+    gallery_needs_terminal: "Die Galerie braucht ein Terminal - die Ausgabe wird in eine Datei oder Pipe geschrieben.",
+    status_bar: "Bild {}/{} - {} - {}×{}",
+    status_hints_generator: "B/N: blättern  S: speichern  C/X: kopieren  F: Effekte  W: Breite  L: Diashow  Q: beenden",
+    status_hints_load: "B/N: blättern  C/X: kopieren  D: löschen  U: mischen  L: Diashow  Q: beenden",
+    timings_header: "Zeiten: {}",
+    timing_download: "Download",
+    timing_decode: "Dekodierung",
+    timing_resize: "Skalierung",
+    timing_colorize: "Einfärbung",
+    timing_render: "Darstellung",
+    timings_empty: "keine Zeiten erfasst",
+
+    no_image_left: "Keine weiteren Bilder.",
+    no_images_registered: "Keine Bilder vorhanden.",
+    evicted_image: "Dieses Bild wurde aus dem Speicher entfernt und konnte nicht wiederhergestellt werden.",
+    print_io_error: "E/A-Fehler bei der Ausgabe: {}",
+    empty_image: "Ein leeres Bild kann nicht ausgegeben werden.",
+    clipboard_error: "Das aktuelle Bild konnte nicht in die Zwischenablage kopiert werden.",
+    clipboard_too_large: "Das Bild ist zu groß für die Zwischenablage des Terminals (OSC 52 nimmt höchstens {} kodierte Bytes).",
+
+    image_loading_error: "Das {}-Bild konnte nicht geladen werden: {}",
+    image_format_unknown: "Bild konnte nicht geladen werden: {}",
+    avif_feature_hint: "AVIF-Bilder brauchen einen Build mit `--features avif` und die installierte dav1d-Bibliothek",
+    invalid_width: "Ungültige Breite. Eine positive Ganzzahl, einen Prozentwert wie 80% oder small, medium, full bzw. auto eingeben",
+    width_limit_exceeded: "Breite von {} Spalten überschreitet das Limit von {} (max_width in der Konfigurationsdatei)",
+    pixel_limit_exceeded: "Bild hat {} Pixel, mehr als das Limit von {} (max_decoded_pixels in der Konfigurationsdatei)",
+    cell_limit_exceeded: "Ergebnis hätte {} Zellen, mehr als das Limit von {} (max_cells in der Konfigurationsdatei)",
+    invalid_crop: "Ungültiger Ausschnitt - links,oben,Breite,Höhe oder Mitte x,Mitte y,Zoom in Prozent eingeben, z. B. 25,10,50,60 oder 50,40,200",
+    invalid_duotone: "Ungültiges Duotone - zwei Hex-Farben durch ein Komma getrennt eingeben, z. B. {}",
+    ramp_empty: "Die Zeichenskala muss mindestens ein Zeichen enthalten",
+    ramp_unsorted: "Die Zeichenskala muss von dunkel nach hell verlaufen, aber '{}' wirkt dunkler als das vorangehende '{}'",
+    conversion_failed: "Bild '{}' konnte nicht konvertiert werden: {}",
+    conversion_cancelled: "Konvertierung abgebrochen.",
+
+    browser_error: "Browser konnte nicht gestartet werden: {}",
+    connection_error: "{} konnte nicht geöffnet werden: {}",
+    searcher_error: "Suchseite {} wurde nicht vollständig geladen: {}",
+    selector_error: "Keine Elemente passend zu '{}' auf {} gefunden: {}",
+    no_results: "Keine Ergebnisse für den Suchbegriff '{}' gefunden",
+    request_error: "Anfrage an {} konnte nicht gesendet werden: {}",
+    status_error: "Anfrage an {} schlug mit Status {} fehl",
+    body_error: "Daten von {} konnten nicht gelesen werden: {}",
+    duplicate_image: "Doppeltes Bild von {} wird übersprungen",
+    download_resuming: "Download von {} nach {} Bytes unterbrochen ({}), wird fortgesetzt",
+
+    save_path_error: "Der angegebene Speicherpfad ist kein gültiger Ordner",
+    save_error: "Das Bild konnte nicht im angegebenen Speicherordner gespeichert werden",
+    load_error: "Bild {} konnte nicht geladen werden",
+    delete_error: "{} konnte nicht gelöscht werden.",
+    favourite_error: "{} konnte nicht markiert werden - nur beschreibbare .cwi-Bilder können Favoriten sein.",
+    not_a_dir_error: "Der angegebene Pfad ist kein Ordner - möglicherweise ist es eine Datei",
+    opening_dir_error: "Der angegebene Ordner konnte nicht geöffnet werden",
+    storage_io_error: "E/A-Fehler: {}",
+    file_read_failed: "Datei konnte nicht gelesen werden: {}",
+    interrupt_handler_failed: "Der Strg+C-Handler konnte nicht eingerichtet werden, das Terminal wird bei einem Abbruch eventuell nicht wiederhergestellt: {}",
+    skipping_file: "Fehlerhafte Datei wird beim Laden übersprungen: {}",
+
+    unterminated_sequence: "Escape-Sequenz ist nicht abgeschlossen",
+    invalid_sgr_parameter: "Ungültiger SGR-Parameter '{}'",
+
+    no_config_dir: "Das Konfigurationsverzeichnis konnte nicht ermittelt werden",
+    config_parse_error: "Fehlerhafte Konfigurationsdatei in Zeile {}",
+    config_io_error: "E/A-Fehler beim Zugriff auf die Konfiguration: {}",
+
+    #[cfg(feature = "screen-capture")]
+    no_screen: "Unter dem angegebenen Index wurde kein Bildschirm gefunden",
+    #[cfg(feature = "screen-capture")]
+    invalid_region: "Der Bereich muss als x,y,Breite,Höhe mit positiver Breite und Höhe angegeben werden",
+    #[cfg(feature = "screen-capture")]
+    capture_failed: "Bildschirm konnte nicht aufgenommen werden: {}",
+    #[cfg(feature = "screen-capture")]
+    screen_description: "Bildschirm {} ({}x{}{})",
+    #[cfg(feature = "screen-capture")]
+    screen_primary: ", primär",
+    #[cfg(feature = "webcam")]
+    no_camera: "Keine Webcam gefunden",
+    #[cfg(feature = "webcam")]
+    camera_error: "Webcam-Fehler: {}",
+    #[cfg(any(feature = "screen-capture", feature = "webcam"))]
+    frame_encoding_error: "Das aufgenommene Bild konnte nicht kodiert werden",
+};
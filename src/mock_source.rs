@@ -0,0 +1,72 @@
+use crate::image_source::{self, ImageSource};
+use bytes::Bytes;
+use image::{DynamicImage, Rgb, RgbImage};
+use std::env;
+use std::rc::Rc;
+
+type SamplePainter = fn(u32, u32) -> Rgb<u8>;
+
+#[derive(Default)]
+pub struct MockSource {
+    index: usize,
+}
+
+impl MockSource {
+    pub const ENABLE_VARIABLE: &'static str = "COLOURFUL_WORDS_MOCK_SOURCE";
+    const SAMPLE_WIDTH: u32 = 96;
+    const SAMPLE_HEIGHT: u32 = 64;
+    const CHECKER_SIZE: u32 = 8;
+    const SAMPLES: [(&'static str, SamplePainter); 3] = [
+        ("sample_gradient", Self::paint_gradient),
+        ("sample_checkerboard", Self::paint_checkerboard),
+        ("sample_rings", Self::paint_rings),
+    ];
+
+    pub fn is_enabled_by_env() -> bool {
+        env::var_os(Self::ENABLE_VARIABLE).is_some_and(|value| !value.is_empty())
+    }
+
+    fn paint_gradient(x: u32, y: u32) -> Rgb<u8> {
+        Rgb([
+            (x * 255 / (Self::SAMPLE_WIDTH - 1)) as u8,
+            (y * 255 / (Self::SAMPLE_HEIGHT - 1)) as u8,
+            128,
+        ])
+    }
+
+    fn paint_checkerboard(x: u32, y: u32) -> Rgb<u8> {
+        if (x / Self::CHECKER_SIZE + y / Self::CHECKER_SIZE).is_multiple_of(2) {
+            Rgb([240, 200, 40])
+        } else {
+            Rgb([30, 60, 160])
+        }
+    }
+
+    fn paint_rings(x: u32, y: u32) -> Rgb<u8> {
+        let dx = x.abs_diff(Self::SAMPLE_WIDTH / 2);
+        let dy = y.abs_diff(Self::SAMPLE_HEIGHT / 2);
+        let distance = ((dx * dx + dy * dy) as f32).sqrt() as u32;
+        match (distance / 6) % 3 {
+            0 => Rgb([220, 40, 40]),
+            1 => Rgb([40, 200, 80]),
+            _ => Rgb([60, 80, 230]),
+        }
+    }
+}
+
+impl Iterator for MockSource {
+    type Item = (Rc<String>, Bytes);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((name, painter)) = Self::SAMPLES.get(self.index) {
+            self.index += 1;
+            let sample = RgbImage::from_fn(Self::SAMPLE_WIDTH, Self::SAMPLE_HEIGHT, painter);
+            if let Some(bytes) = image_source::encode_frame(DynamicImage::ImageRgb8(sample)) {
+                return Some((Rc::new(name.to_string()), bytes));
+            }
+        }
+        None
+    }
+}
+
+impl ImageSource for MockSource {}
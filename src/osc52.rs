@@ -0,0 +1,45 @@
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::env;
+use std::io::{self, Write};
+
+// Sets the clipboard of the terminal itself, which works over SSH where no local clipboard is reachable.
+pub struct Osc52;
+
+impl Osc52 {
+    // Several terminals drop longer sequences, xterm among them.
+    pub const MAX_ENCODED_LENGTH: usize = 100_000;
+    // GNU screen cuts passthrough strings longer than this.
+    const SCREEN_CHUNK_LENGTH: usize = 76;
+
+    pub fn fits(contents: &str) -> bool {
+        contents.len().div_ceil(3) * 4 <= Self::MAX_ENCODED_LENGTH
+    }
+
+    // Callers check fits() first; anything longer would only be dropped by the terminal.
+    pub fn copy(contents: &str) -> io::Result<()> {
+        if !Self::fits(contents) {
+            return Err(io::Error::from(io::ErrorKind::InvalidInput));
+        }
+        let encoded = STANDARD.encode(contents);
+        let mut stdout = io::stdout();
+        stdout.write_all(Self::sequence(&encoded).as_bytes())?;
+        stdout.flush()
+    }
+
+    // Multiplexers swallow unknown sequences, so they get theirs wrapped in a passthrough.
+    fn sequence(encoded: &str) -> String {
+        let sequence = format!("\x1B]52;c;{}\x07", encoded);
+        if env::var_os("TMUX").is_some() {
+            return format!("\x1BPtmux;{}\x1B\\", sequence.replace('\x1B', "\x1B\x1B"));
+        }
+        if env::var("TERM").is_ok_and(|term| term.starts_with("screen")) {
+            let bytes = sequence.as_bytes();
+            return bytes
+                .chunks(Self::SCREEN_CHUNK_LENGTH)
+                .map(|chunk| format!("\x1BP{}\x1B\\", String::from_utf8_lossy(chunk)))
+                .collect();
+        }
+        sequence
+    }
+}
@@ -0,0 +1,96 @@
+use crate::messages::messages;
+use image::{Rgb, RgbImage};
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Palette {
+    #[default]
+    Off,
+    Gruvbox,
+    Nord,
+    Dracula,
+    Solarized,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 5] = [Palette::Off, Palette::Gruvbox, Palette::Nord, Palette::Dracula, Palette::Solarized];
+
+    const GRUVBOX: [u32; 16] = [
+        0x282828, 0xcc241d, 0x98971a, 0xd79921, 0x458588, 0xb16286, 0x689d6a, 0xa89984,
+        0x928374, 0xfb4934, 0xb8bb26, 0xfabd2f, 0x83a598, 0xd3869b, 0x8ec07c, 0xebdbb2,
+    ];
+    const NORD: [u32; 16] = [
+        0x2e3440, 0x3b4252, 0x434c5e, 0x4c566a, 0xd8dee9, 0xe5e9f0, 0xeceff4, 0x8fbcbb,
+        0x88c0d0, 0x81a1c1, 0x5e81ac, 0xbf616a, 0xd08770, 0xebcb8b, 0xa3be8c, 0xb48ead,
+    ];
+    const DRACULA: [u32; 12] = [
+        0x21222c, 0x282a36, 0x44475a, 0x6272a4, 0xf8f8f2, 0x8be9fd, 0x50fa7b, 0xffb86c,
+        0xff79c6, 0xbd93f9, 0xff5555, 0xf1fa8c,
+    ];
+    const SOLARIZED: [u32; 16] = [
+        0x002b36, 0x073642, 0x586e75, 0x657b83, 0x839496, 0x93a1a1, 0xeee8d5, 0xfdf6e3,
+        0xb58900, 0xcb4b16, 0xdc322f, 0xd33682, 0x6c71c4, 0x268bd2, 0x2aa198, 0x859900,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            Palette::Off => "off",
+            Palette::Gruvbox => "gruvbox",
+            Palette::Nord => "nord",
+            Palette::Dracula => "dracula",
+            Palette::Solarized => "solarized",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<Palette> {
+        Self::ALL.into_iter().find(|palette| palette.code() == code.trim())
+    }
+
+    pub fn is_active(&self) -> bool {
+        *self != Palette::Off
+    }
+
+    fn colours(&self) -> &'static [u32] {
+        match self {
+            Palette::Off => &[],
+            Palette::Gruvbox => &Self::GRUVBOX,
+            Palette::Nord => &Self::NORD,
+            Palette::Dracula => &Self::DRACULA,
+            Palette::Solarized => &Self::SOLARIZED,
+        }
+    }
+
+    fn distance(a: [u8; 3], b: [u8; 3]) -> u32 {
+        a.iter().zip(b).map(|(x, y)| x.abs_diff(y) as u32).map(|d| d * d).sum()
+    }
+
+    // With the palette off every colour is its own nearest match.
+    pub fn nearest(&self, colour: [u8; 3]) -> [u8; 3] {
+        self.colours()
+            .iter()
+            .map(|hex| [(hex >> 16) as u8, (hex >> 8) as u8, *hex as u8])
+            .min_by_key(|candidate| Self::distance(colour, *candidate))
+            .unwrap_or(colour)
+    }
+
+    pub fn apply(&self, mut img: RgbImage) -> RgbImage {
+        if self.is_active() {
+            for pixel in img.pixels_mut() {
+                *pixel = Rgb(self.nearest(pixel.0));
+            }
+        }
+        img
+    }
+}
+
+impl fmt::Display for Palette {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Palette::Off => write!(f, "{}", messages().palette_off),
+            Palette::Gruvbox => write!(f, "Gruvbox"),
+            Palette::Nord => write!(f, "Nord"),
+            Palette::Dracula => write!(f, "Dracula"),
+            Palette::Solarized => write!(f, "Solarized"),
+        }
+    }
+}
@@ -1,32 +1,53 @@
+use crate::cell_grid::CellGrid;
+use crate::converter::{CharacterRamp, Luminance};
+use crate::logger::Logger;
+use crate::messages::{fill, messages};
+use crate::osc52::Osc52;
+use crate::sgr::{self, StyledChar};
+use crate::timings::{ImageTimings, TimingStage};
+use bytes::Bytes;
 use copypasta::{ClipboardContext, ClipboardProvider};
-use crossterm::style::Print;
-use crossterm::terminal::{Clear, ClearType};
-use crossterm::{cursor, QueueableCommand};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::style::{Attribute, Color, Print, ResetColor, SetAttribute, SetBackgroundColor, SetForegroundColor};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{cursor, ExecutableCommand, QueueableCommand};
+use image::Rgb;
 use rand::prelude::SliceRandom;
-use std::io::Write;
+use rand::Rng;
+use std::io::{IsTerminal, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::cell::Cell;
 use std::rc::Rc;
-use std::time::Duration;
-use std::{fmt, io, thread};
+use std::time::{Duration, Instant};
+use std::{fmt, io};
+
+// The image header above, the status bar and the cursor line below; images fitted to the terminal leave these free.
+pub const RESERVED_ROWS: usize = 3;
 
 #[derive(Debug)]
 pub enum PrinterError {
     NoImageLeftError,
     NoImagesRegisteredError,
+    EvictedImageError,
+    CancelledError,
     IoError(io::Error),
     EmptyImageError,
     ClipboardError,
-    InvalidImageError,
+    ClipboardTooLargeError,
 }
 
 impl fmt::Display for PrinterError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            PrinterError::NoImageLeftError => write!(f, "No images left."),
-            PrinterError::NoImagesRegisteredError => write!(f, "No images registered."),
-            PrinterError::IoError(e) => write!(f, "IO Error during print: {}", e),
-            PrinterError::EmptyImageError => write!(f, "Cannot print an empty image."),
-            PrinterError::ClipboardError => write!(f, "Failed to copy the current image to clipboard."),
-            PrinterError::InvalidImageError => write!(f, "Image contains invalid sequences of characters."),
+            PrinterError::NoImageLeftError => write!(f, "{}", messages().no_image_left),
+            PrinterError::NoImagesRegisteredError => write!(f, "{}", messages().no_images_registered),
+            PrinterError::EvictedImageError => write!(f, "{}", messages().evicted_image),
+            PrinterError::CancelledError => write!(f, "{}", messages().conversion_cancelled),
+            PrinterError::IoError(e) => write!(f, "{}", fill(messages().print_io_error, &[e])),
+            PrinterError::EmptyImageError => write!(f, "{}", messages().empty_image),
+            PrinterError::ClipboardError => write!(f, "{}", messages().clipboard_error),
+            PrinterError::ClipboardTooLargeError => write!(f, "{}", fill(messages().clipboard_too_large, &[&Osc52::MAX_ENCODED_LENGTH])),
         }
     }
 }
@@ -37,106 +58,775 @@ impl From<io::Error> for PrinterError {
     }
 }
 
+pub enum ToastKind {
+    Success,
+    Info,
+    Error,
+}
+
+struct Toast {
+    column: usize,
+    width: usize,
+    shown_at: Instant,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClearMode {
+    FullScreen,
+    #[default]
+    AlternateScreen,
+    PreserveScrollback,
+}
+
+impl ClearMode {
+    pub const ALL: [ClearMode; 3] = [ClearMode::FullScreen, ClearMode::AlternateScreen, ClearMode::PreserveScrollback];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            ClearMode::FullScreen => "full",
+            ClearMode::AlternateScreen => "alternate",
+            ClearMode::PreserveScrollback => "preserve",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<ClearMode> {
+        Self::ALL.into_iter().find(|mode| mode.code() == code.trim())
+    }
+}
+
+impl fmt::Display for ClearMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClearMode::FullScreen => write!(f, "{}", messages().clear_mode_full),
+            ClearMode::AlternateScreen => write!(f, "{}", messages().clear_mode_alternate),
+            ClearMode::PreserveScrollback => write!(f, "{}", messages().clear_mode_preserve),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RevealStyle {
+    #[default]
+    RandomCells,
+    SpiralInward,
+    CentreOutward,
+    MatrixRain,
+    WipeDown,
+    WipeUp,
+    FadeIn,
+}
+
+impl RevealStyle {
+    pub const ALL: [RevealStyle; 7] = [
+        RevealStyle::RandomCells,
+        RevealStyle::SpiralInward,
+        RevealStyle::CentreOutward,
+        RevealStyle::MatrixRain,
+        RevealStyle::WipeDown,
+        RevealStyle::WipeUp,
+        RevealStyle::FadeIn,
+    ];
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            RevealStyle::RandomCells => "random",
+            RevealStyle::SpiralInward => "spiral",
+            RevealStyle::CentreOutward => "centre-out",
+            RevealStyle::MatrixRain => "matrix",
+            RevealStyle::WipeDown => "wipe-down",
+            RevealStyle::WipeUp => "wipe-up",
+            RevealStyle::FadeIn => "fade-in",
+        }
+    }
+
+    pub fn from_code(code: &str) -> Option<RevealStyle> {
+        Self::ALL.into_iter().find(|style| style.code() == code.trim())
+    }
+
+    // Cell by cell styles give the order their cells appear in, the others animate whole rows or columns.
+    fn cell_order(&self, rows: usize, columns: usize, cell_aspect_ratio: f32) -> Option<Vec<(usize, usize)>> {
+        let mut cells: Vec<(usize, usize)> = (0..rows).flat_map(|row| (0..columns).map(move |col| (row, col))).collect();
+        match self {
+            RevealStyle::RandomCells => cells.shuffle(&mut rand::rng()),
+            RevealStyle::SpiralInward => cells = Self::spiral_order(rows, columns),
+            RevealStyle::CentreOutward => {
+                // Columns are scaled by the cell aspect ratio so the growing shape looks round on screen.
+                let (centre_row, centre_col) = ((rows as f32 - 1.0) / 2.0, (columns as f32 - 1.0) / 2.0);
+                let distance = |&(row, col): &(usize, usize)| {
+                    let (dy, dx) = (row as f32 - centre_row, (col as f32 - centre_col) * cell_aspect_ratio);
+                    dy * dy + dx * dx
+                };
+                cells.sort_by(|a, b| distance(a).total_cmp(&distance(b)));
+            }
+            RevealStyle::MatrixRain | RevealStyle::WipeDown | RevealStyle::WipeUp | RevealStyle::FadeIn => return None,
+        }
+        Some(cells)
+    }
+
+    fn spiral_order(rows: usize, columns: usize) -> Vec<(usize, usize)> {
+        let mut cells = Vec::with_capacity(rows * columns);
+        let (mut top, mut bottom, mut left, mut right) = (0, rows as isize - 1, 0, columns as isize - 1);
+        while top <= bottom && left <= right {
+            cells.extend((left..=right).map(|col| (top as usize, col as usize)));
+            top += 1;
+            cells.extend((top..=bottom).map(|row| (row as usize, right as usize)));
+            right -= 1;
+            if top <= bottom {
+                cells.extend((left..=right).rev().map(|col| (bottom as usize, col as usize)));
+                bottom -= 1;
+            }
+            if left <= right {
+                cells.extend((top..=bottom).rev().map(|row| (row as usize, left as usize)));
+                left += 1;
+            }
+        }
+        cells
+    }
+}
+
+impl fmt::Display for RevealStyle {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RevealStyle::RandomCells => write!(f, "{}", messages().reveal_style_random),
+            RevealStyle::SpiralInward => write!(f, "{}", messages().reveal_style_spiral),
+            RevealStyle::CentreOutward => write!(f, "{}", messages().reveal_style_centre_out),
+            RevealStyle::MatrixRain => write!(f, "{}", messages().reveal_style_matrix),
+            RevealStyle::WipeDown => write!(f, "{}", messages().reveal_style_wipe_down),
+            RevealStyle::WipeUp => write!(f, "{}", messages().reveal_style_wipe_up),
+            RevealStyle::FadeIn => write!(f, "{}", messages().reveal_style_fade_in),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct PrinterOptions {
+    pub printing_rate_ms: u16,
+    pub reveal_style: RevealStyle,
+    pub show_timings: bool,
+    pub cell_aspect_ratio: f32,
+    pub clear_mode: ClearMode,
+    // How many images keep their rows in memory; 0 keeps them all.
+    pub cache_limit: usize,
+}
+
+// '+' halves the per-cell delay and '-' doubles it, so a few presses cover the whole useful range.
+fn stepped_printing_rate(printing_rate_ms: u16, faster: bool) -> u16 {
+    if faster {
+        printing_rate_ms / 2
+    } else {
+        printing_rate_ms.saturating_mul(2).max(1)
+    }
+}
+
+// Waits up to the timeout for a key press, giving None once the time is up.
+type KeySource = fn(Duration) -> io::Result<Option<KeyCode>>;
+
+// What an image is drawn against: the size it has to fit and where the keys steering a reveal come from.
+#[derive(Clone, Copy)]
+struct Screen {
+    size: Option<(u16, u16)>,
+    keys: KeySource,
+}
+
+impl Screen {
+    fn terminal() -> Self {
+        Self {
+            size: terminal::size().ok(),
+            keys: Self::terminal_key,
+        }
+    }
+
+    fn terminal_key(timeout: Duration) -> io::Result<Option<KeyCode>> {
+        let deadline = Instant::now() + timeout;
+        while event::poll(deadline.saturating_duration_since(Instant::now()))? {
+            if let Event::Key(key_event) = event::read()? {
+                if key_event.kind == KeyEventKind::Press {
+                    return Ok(Some(key_event.code));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn width(&self) -> usize {
+        self.size.map_or(usize::MAX, |(width, _)| width as usize)
+    }
+
+    // The columns and rows left for the image once the reserved rows are taken; unlimited if the size is unknown.
+    fn image_area(&self) -> (usize, usize) {
+        self.size.map_or((usize::MAX, usize::MAX), |(width, height)| {
+            (width as usize, (height as usize).saturating_sub(RESERVED_ROWS).max(1))
+        })
+    }
+}
+
+pub struct AnimationFrame {
+    pub image_array: Vec<Vec<StyledChar>>,
+    pub delay: Duration,
+}
+
 struct ColouredImage {
-    image_array: Vec<Vec<String>>,
+    image_array: Vec<Vec<StyledChar>>,
     index: usize,
     image_name: Rc<String>,
     is_rendered: bool,
-    printing_rate_ms: u16
+    options: PrinterOptions,
+    timings: ImageTimings,
+    aspect_corrected: bool,
+    note: Option<String>,
+    favourite: bool,
+    graphic: Option<String>,
+    frames: Vec<AnimationFrame>,
+    source: Option<Bytes>,
+    path: Option<PathBuf>,
+    pan: (usize, usize),
+    evicted: bool,
+    // Taken just before eviction, so the gallery can still show the image without bringing it back.
+    evicted_thumbnail: Vec<Vec<StyledChar>>,
+    last_viewed: u64,
 }
 
 impl ColouredImage {
-    fn new(image_array: Vec<Vec<String>>, index: usize, image_name: &Rc<String>, printing_rate_ms: u16) -> Self {
+    const RAIN_TRAIL_LENGTH: usize = 6;
+    const RAIN_GLYPHS: &'static [u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ$+-*/=%<>";
+    const RAIN_HEAD_COLOUR: Color = Color::Rgb { r: 200, g: 255, b: 200 };
+    const RAIN_TRAIL_COLOUR: Color = Color::Rgb { r: 0, g: 190, b: 70 };
+    const FADE_STEPS: usize = 12;
+    // Roughly 60 frames per second.
+    const REVEAL_FRAME_MS: u16 = 16;
+    const REVEAL_PROGRESS_BAR_WIDTH: usize = 20;
+    // Messages shown during a reveal start right after the progress bar, so neither overwrites the other.
+    const REVEAL_MESSAGE_COLUMN: u16 = Self::REVEAL_PROGRESS_BAR_WIDTH as u16 + 6;
+    // A held reveal waits for its key without a deadline, asking the key source again whenever this runs out.
+    const HELD_REVEAL_KEY_WAIT: Duration = Duration::from_secs(1);
+
+    fn new(image_array: Vec<Vec<StyledChar>>, index: usize, image_name: &Rc<String>, options: PrinterOptions, timings: ImageTimings) -> Self {
         Self {
             image_array,
             index,
             image_name: image_name.clone(),
             is_rendered: false,
-            printing_rate_ms,
+            options,
+            timings,
+            aspect_corrected: false,
+            note: None,
+            favourite: false,
+            graphic: None,
+            frames: Vec::new(),
+            source: None,
+            path: None,
+            pan: (0, 0),
+            evicted: false,
+            evicted_thumbnail: Vec::new(),
+            last_viewed: 0,
         }
     }
 
-    fn get_random_indices(rows: usize, columns: usize) -> Vec<(usize, usize)> {
-        let mut indices: Vec<(usize, usize)> = Vec::with_capacity(rows * columns);
-        for row in 0..rows {
-            for col in 0..columns {
-                indices.push((row, col));
+    fn displayed_rows(&self, screen: &Screen) -> Vec<&[StyledChar]> {
+        self.displayed_rows_of(screen, &self.image_array)
+    }
+
+    // Only the part of the image that fits the terminal is drawn, so oversized images never wrap.
+    fn displayed_rows_of<'a>(&self, screen: &Screen, image_array: &'a [Vec<StyledChar>]) -> Vec<&'a [StyledChar]> {
+        let rows = self.corrected_rows_of(image_array);
+        if self.graphic.is_some() {
+            return rows.into_iter().map(Vec::as_slice).collect();
+        }
+        let (row_range, column_range) = self.viewport(screen, &rows);
+        rows[row_range]
+            .iter()
+            .map(|row| &row[column_range.start.min(row.len())..column_range.end.min(row.len())])
+            .collect()
+    }
+
+    // The visible rows and columns, starting from the panned corner but never scrolled past the image's edges.
+    fn viewport(&self, screen: &Screen, rows: &[&Vec<StyledChar>]) -> (Range<usize>, Range<usize>) {
+        let (width, height) = screen.image_area();
+        let widths: Vec<usize> = rows.first().map_or(Vec::new(), |row| row.iter().map(StyledChar::width).collect());
+        let (mut last_column_start, mut fitting_width) = (widths.len(), 0);
+        while last_column_start > 0 && fitting_width + widths[last_column_start - 1] <= width {
+            last_column_start -= 1;
+            fitting_width += widths[last_column_start];
+        }
+        let first_row = self.pan.0.min(rows.len().saturating_sub(height));
+        let first_column = self.pan.1.min(last_column_start);
+        let mut used_width = 0;
+        let visible_columns = widths[first_column..]
+            .iter()
+            .take_while(|cell_width| {
+                used_width += **cell_width;
+                used_width <= width
+            })
+            .count();
+        (first_row..first_row.saturating_add(height).min(rows.len()), first_column..first_column + visible_columns)
+    }
+
+    // Moves the viewport and reports whether anything new came into view.
+    fn pan(&mut self, screen: &Screen, row_step: isize, column_step: isize) -> bool {
+        let before = self.pan_origin(screen);
+        self.pan = (before.0.saturating_add_signed(row_step), before.1.saturating_add_signed(column_step));
+        self.pan = self.pan_origin(screen);
+        self.pan != before
+    }
+
+    fn pan_origin(&self, screen: &Screen) -> (usize, usize) {
+        let (row_range, column_range) = self.viewport(screen, &self.corrected_rows_of(&self.image_array));
+        (row_range.start, column_range.start)
+    }
+
+    fn corrected_rows_of<'a>(&self, image_array: &'a [Vec<StyledChar>]) -> Vec<&'a Vec<StyledChar>> {
+        let rows = image_array.len();
+        if !self.aspect_corrected || rows == 0 || self.graphic.is_some() {
+            return image_array.iter().collect();
+        }
+        let cell_aspect_ratio = self.options.cell_aspect_ratio;
+        let displayed_rows = ((rows as f32 * cell_aspect_ratio).round() as usize).max(1);
+        (0..displayed_rows)
+            .map(|row| &image_array[((row as f32 / cell_aspect_ratio) as usize).min(rows - 1)])
+            .collect()
+    }
+
+    // Fits the art into the given size keeping its proportions; small images are never enlarged.
+    fn thumbnail(&self, max_columns: usize, max_rows: usize) -> Vec<Vec<StyledChar>> {
+        if self.evicted {
+            return self.evicted_thumbnail.clone();
+        }
+        let rows = self.corrected_rows_of(&self.image_array);
+        let source_columns = rows.first().map_or(0, |row| row.len());
+        if source_columns == 0 {
+            return Vec::new();
+        }
+        let cell_width = rows[0][0].width();
+        let max_columns = (max_columns / cell_width).max(1);
+        let scale = (max_columns as f32 / source_columns as f32).min(max_rows as f32 / rows.len() as f32).min(1.0);
+        let thumbnail_columns = ((source_columns as f32 * scale).round() as usize).clamp(1, max_columns);
+        let thumbnail_rows = ((rows.len() as f32 * scale).round() as usize).clamp(1, max_rows);
+        CellGrid::new(rows.into_iter().cloned().collect()).thumbnail(thumbnail_columns, thumbnail_rows)
+    }
+
+    // Drops what takes the memory but a gallery thumbnail; the name, note, source bytes and path stay to bring the image back.
+    fn evict(&mut self, thumbnail_columns: usize, thumbnail_rows: usize) {
+        self.evicted_thumbnail = self.thumbnail(thumbnail_columns, thumbnail_rows);
+        self.image_array = Vec::new();
+        self.frames = Vec::new();
+        self.graphic = None;
+        self.evicted = true;
+    }
+
+    fn display_width(&self, screen: &Screen) -> usize {
+        self.displayed_rows(screen).first().map_or(0, |row| row.iter().map(StyledChar::width).sum())
+    }
+
+    fn is_animated(&self) -> bool {
+        self.frames.len() > 1 && self.graphic.is_none()
+    }
+
+    // Loops the frames at their own delays until any key is pressed.
+    fn play(&self, out: &mut impl Write, screen: &Screen, origin_row: u16) -> Result<(), PrinterError> {
+        for frame in self.frames.iter().cycle() {
+            self.print_frame(out, screen, frame, origin_row)?;
+            if (screen.keys)(frame.delay)?.is_some() {
+                return Ok(());
             }
         }
-        let mut rng = rand::rng();
-        indices.shuffle(&mut rng);
-        indices
+        Ok(())
     }
 
-    fn slow_print(&self) -> Result<(), PrinterError> {
+    fn print_frame(&self, out: &mut impl Write, screen: &Screen, frame: &AnimationFrame, origin_row: u16) -> Result<(), PrinterError> {
+        out.queue(cursor::MoveTo(0, origin_row))?;
+        for row in self.displayed_rows_of(screen, &frame.image_array) {
+            out.queue(Print(sgr::render(row)))?.queue(Print('\n'))?;
+        }
+        out.flush()?;
+        Ok(())
+    }
+
+    // Returns the printing rate the reveal ended on, as '+' and '-' may have changed it along the way.
+    fn slow_print(&self, out: &mut impl Write, screen: &Screen, origin_row: u16) -> Result<u16, PrinterError> {
         if self.image_array.is_empty() || self.image_array[0].is_empty() {
             return Err(PrinterError::EmptyImageError);
         }
-        if self.printing_rate_ms == 0 {
-            return self.instant_print();
+        if self.options.printing_rate_ms == 0 {
+            self.instant_print(out, screen, origin_row)?;
+            return Ok(0);
         }
-        let mut stdout = io::stdout();
-        let rows = self.image_array.len();
-        let cols = self.image_array[0].len();
-        let printing_order = Self::get_random_indices(rows, cols);
-        stdout.queue(cursor::Hide)?.queue(Clear(ClearType::All))?.queue(cursor::MoveTo(0, 0))?.flush()?;
-        let empty_row = " ".repeat(cols);
-        for _ in 0..rows {
-            stdout.queue(Print(&empty_row))?;
-        }
-        stdout.queue(cursor::MoveTo(0, 0))?.flush()?;
-        for &(row, col) in &printing_order {
-            stdout
-                .queue(cursor::MoveTo(col as u16, row as u16))?
-                .queue(Print(&self.image_array[row][col].to_string()))?
-                .flush()?;
-            thread::sleep(Duration::from_millis(self.printing_rate_ms as u64));
+        let image_rows = self.displayed_rows(screen);
+        let rows = image_rows.len();
+        let cols = image_rows[0].len();
+        out.queue(cursor::Hide)?;
+        let empty_row = " ".repeat(self.display_width(screen));
+        for row in 0..rows {
+            out.queue(cursor::MoveTo(0, origin_row + row as u16))?.queue(Print(&empty_row))?;
         }
-        stdout.queue(cursor::MoveTo(0, rows as u16))?
+        out.flush()?;
+        let printing_rate = Cell::new(self.options.printing_rate_ms);
+        let skipped = match self.options.reveal_style.cell_order(rows, cols, self.options.cell_aspect_ratio) {
+            Some(printing_order) => self.cell_print(out, screen, origin_row, &image_rows, printing_order, &printing_rate)?,
+            None if self.options.reveal_style == RevealStyle::MatrixRain => {
+                self.rain_print(out, screen, origin_row, &image_rows, &printing_rate)?
+            }
+            None if self.options.reveal_style == RevealStyle::FadeIn => self.fade_print(out, screen, origin_row, &image_rows, &printing_rate)?,
+            None => self.wipe_print(out, screen, origin_row, &image_rows, &printing_rate)?,
+        };
+        if skipped {
+            self.instant_print(out, screen, origin_row)?;
+        }
+        out.queue(cursor::MoveTo(0, origin_row + rows as u16))?
+            .queue(Clear(ClearType::UntilNewLine))?
             .queue(Print('\n'))?
             .queue(cursor::Show)?
             .flush()?;
+        Ok(printing_rate.get())
+    }
+
+    // Waits out the delay, but gives up early once Space or Enter asks for the rest of the image right away.
+    // 'P' holds the reveal where it is until it is pressed again, '+' and '-' change the pace of the next steps.
+    fn wait_or_skip(out: &mut impl Write, keys: KeySource, delay: Duration, status_row: u16, printing_rate: &Cell<u16>) -> Result<bool, PrinterError> {
+        let deadline = Instant::now() + delay;
+        loop {
+            let Some(key) = keys(deadline.saturating_duration_since(Instant::now()))? else {
+                return Ok(false);
+            };
+            match key {
+                KeyCode::Char(' ') | KeyCode::Enter => return Ok(true),
+                KeyCode::Char('p') | KeyCode::Char('P') => return Self::hold_reveal(out, keys, status_row),
+                KeyCode::Char(sign @ ('+' | '-')) => {
+                    printing_rate.set(stepped_printing_rate(printing_rate.get(), sign == '+'));
+                    out
+                        .queue(cursor::MoveTo(Self::REVEAL_MESSAGE_COLUMN, status_row))?
+                        .queue(Clear(ClearType::UntilNewLine))?
+                        .queue(Print(fill(messages().printing_rate_changed, &[&printing_rate.get()])))?
+                        .flush()?;
+                    // Going down to no delay at all means the rest of the image is wanted right away.
+                    if printing_rate.get() == 0 {
+                        return Ok(true);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn hold_reveal(out: &mut impl Write, keys: KeySource, status_row: u16) -> Result<bool, PrinterError> {
+        out
+            .queue(cursor::MoveTo(Self::REVEAL_MESSAGE_COLUMN, status_row))?
+            .queue(Clear(ClearType::UntilNewLine))?
+            .queue(Print(messages().reveal_paused))?
+            .flush()?;
+        let skipped = loop {
+            match keys(Self::HELD_REVEAL_KEY_WAIT)? {
+                Some(KeyCode::Char(' ') | KeyCode::Enter) => break true,
+                Some(KeyCode::Char('p') | KeyCode::Char('P')) => break false,
+                _ => {}
+            }
+        };
+        out.queue(cursor::MoveTo(Self::REVEAL_MESSAGE_COLUMN, status_row))?.queue(Clear(ClearType::UntilNewLine))?.flush()?;
+        Ok(skipped)
+    }
+
+    // Long reveals of big images would otherwise look frozen.
+    fn queue_reveal_progress(out: &mut impl Write, status_row: u16, done: usize, total: usize) -> Result<(), PrinterError> {
+        let total = total.max(1);
+        let filled = (done * Self::REVEAL_PROGRESS_BAR_WIDTH / total).min(Self::REVEAL_PROGRESS_BAR_WIDTH);
+        out
+            .queue(cursor::MoveTo(0, status_row))?
+            .queue(Print(format!(
+                "{:>3}% {}{}",
+                (done * 100 / total).min(100),
+                "█".repeat(filled),
+                "░".repeat(Self::REVEAL_PROGRESS_BAR_WIDTH - filled)
+            )))?;
         Ok(())
     }
 
-    fn instant_print(&self) -> Result<(), PrinterError> {
-        let mut stdout = io::stdout();
-        stdout.queue(Clear(ClearType::All))?.queue(cursor::MoveTo(0, 0))?.flush()?;
-        for row in &self.image_array {
-            stdout.queue(Print(&row.join("")))?.queue(Print('\n'))?.flush()?;
+    // The screen column each cell starts at, as wide cells take two.
+    fn column_offsets(row: &[StyledChar]) -> Vec<usize> {
+        row.iter()
+            .scan(0, |offset, cell| {
+                let column = *offset;
+                *offset += cell.width();
+                Some(column)
+            })
+            .collect()
+    }
+
+    fn cell_print(
+        &self,
+        out: &mut impl Write,
+        screen: &Screen,
+        origin_row: u16,
+        image_rows: &[&[StyledChar]],
+        printing_order: Vec<(usize, usize)>,
+        printing_rate: &Cell<u16>,
+    ) -> Result<bool, PrinterError> {
+        let status_row = origin_row + image_rows.len() as u16;
+        let column_offsets = Self::column_offsets(image_rows[0]);
+        let total_cells = printing_order.len();
+        let mut printed_cells = 0;
+        let mut cells = printing_order.into_iter().peekable();
+        while cells.peek().is_some() {
+            // Fast rates draw a whole frame's worth of cells per flush, as flushing every cell costs more than the delay.
+            let printing_rate_ms = printing_rate.get().max(1);
+            let batch_size = (Self::REVEAL_FRAME_MS / printing_rate_ms).max(1);
+            for (row, col) in cells.by_ref().take(batch_size as usize) {
+                out
+                    .queue(cursor::MoveTo(column_offsets[col] as u16, origin_row + row as u16))?
+                    .queue(Print(image_rows[row][col]))?;
+                printed_cells += 1;
+            }
+            Self::queue_reveal_progress(out, status_row, printed_cells, total_cells)?;
+            out.flush()?;
+            let batch_delay = Duration::from_millis(printing_rate_ms as u64 * batch_size as u64);
+            if Self::wait_or_skip(out, screen.keys, batch_delay, status_row, printing_rate)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    // A whole row takes as long as its cells would one by one, so both reveals last the same.
+    fn wipe_print(&self, out: &mut impl Write, screen: &Screen, origin_row: u16, image_rows: &[&[StyledChar]], printing_rate: &Cell<u16>) -> Result<bool, PrinterError> {
+        let status_row = origin_row + image_rows.len() as u16;
+        let mut order: Vec<usize> = (0..image_rows.len()).collect();
+        if self.options.reveal_style == RevealStyle::WipeUp {
+            order.reverse();
+        }
+        for (printed_rows, row) in order.into_iter().enumerate() {
+            out
+                .queue(cursor::MoveTo(0, origin_row + row as u16))?
+                .queue(Print(sgr::render(image_rows[row])))?;
+            Self::queue_reveal_progress(out, status_row, printed_rows + 1, image_rows.len())?;
+            out.flush()?;
+            let row_delay = Duration::from_millis(printing_rate.get() as u64 * image_rows[0].len() as u64);
+            if Self::wait_or_skip(out, screen.keys, row_delay, status_row, printing_rate)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    // Every column starts falling at a random moment; a green trail runs ahead of the settling cells.
+    fn rain_print(
+        &self,
+        out: &mut impl Write,
+        screen: &Screen,
+        origin_row: u16,
+        image_rows: &[&[StyledChar]],
+        printing_rate: &Cell<u16>,
+    ) -> Result<bool, PrinterError> {
+        let mut rng = rand::rng();
+        let column_offsets = Self::column_offsets(image_rows[0]);
+        let (rows, columns) = (image_rows.len(), column_offsets.len());
+        let starts: Vec<usize> = (0..columns).map(|_| rng.random_range(0..=rows / 2)).collect();
+        let frames = starts.iter().max().copied().unwrap_or(0) + rows + Self::RAIN_TRAIL_LENGTH;
+        for frame in 0..frames {
+            for (col, start) in starts.iter().enumerate() {
+                let Some(head) = frame.checked_sub(*start) else {
+                    continue;
+                };
+                let column = column_offsets[col] as u16;
+                if let Some(settled) = head.checked_sub(Self::RAIN_TRAIL_LENGTH).filter(|settled| *settled < rows) {
+                    out.queue(cursor::MoveTo(column, origin_row + settled as u16))?.queue(Print(image_rows[settled][col]))?;
+                }
+                for row in (head + 1).saturating_sub(Self::RAIN_TRAIL_LENGTH)..(head + 1).min(rows) {
+                    let colour = if row == head { Self::RAIN_HEAD_COLOUR } else { Self::RAIN_TRAIL_COLOUR };
+                    let glyph = Self::RAIN_GLYPHS[rng.random_range(0..Self::RAIN_GLYPHS.len())] as char;
+                    out
+                        .queue(cursor::MoveTo(column, origin_row + row as u16))?
+                        .queue(SetForegroundColor(colour))?
+                        .queue(Print(glyph))?
+                        .queue(ResetColor)?;
+                }
+            }
+            Self::queue_reveal_progress(out, origin_row + rows as u16, frame + 1, frames)?;
+            out.flush()?;
+            let frame_delay = Duration::from_millis(printing_rate.get() as u64 * columns as u64);
+            if Self::wait_or_skip(out, screen.keys, frame_delay, origin_row + rows as u16, printing_rate)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    // The whole image shows up dim and grey at once, then every cell is re-emitted a little closer to its true colour.
+    // A fade takes as long as revealing the image cell by cell would.
+    fn fade_print(&self, out: &mut impl Write, screen: &Screen, origin_row: u16, image_rows: &[&[StyledChar]], printing_rate: &Cell<u16>) -> Result<bool, PrinterError> {
+        let status_row = origin_row + image_rows.len() as u16;
+        let cells = image_rows.len() * image_rows[0].len();
+        for step in 0..=Self::FADE_STEPS {
+            let strength = step as f32 / Self::FADE_STEPS as f32;
+            for (row_index, row) in image_rows.iter().enumerate() {
+                let faded_row: Vec<StyledChar> = row.iter().map(|cell| cell.faded(strength)).collect();
+                out
+                    .queue(cursor::MoveTo(0, origin_row + row_index as u16))?
+                    .queue(Print(sgr::render(&faded_row)))?;
+            }
+            Self::queue_reveal_progress(out, status_row, step, Self::FADE_STEPS)?;
+            out.flush()?;
+            if step == Self::FADE_STEPS {
+                break;
+            }
+            let step_delay = Duration::from_millis(printing_rate.get() as u64 * (cells / Self::FADE_STEPS) as u64);
+            if Self::wait_or_skip(out, screen.keys, step_delay, status_row, printing_rate)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    fn graphic_print(&self, out: &mut impl Write, graphic: &str, origin_row: u16) -> Result<(), PrinterError> {
+        out
+            .queue(cursor::MoveTo(0, origin_row))?
+            .queue(Print(graphic))?
+            .queue(cursor::MoveTo(0, origin_row + self.image_array.len() as u16))?
+            .flush()?;
+        Ok(())
+    }
+
+    fn redraw(&self, out: &mut impl Write, screen: &Screen, origin_row: u16) -> Result<(), PrinterError> {
+        out.queue(cursor::SavePosition)?;
+        for (row_index, row) in self.displayed_rows(screen).into_iter().enumerate() {
+            out
+                .queue(cursor::MoveTo(0, origin_row + row_index as u16))?
+                .queue(Print(sgr::render(row)))?
+                .queue(Clear(ClearType::UntilNewLine))?;
+        }
+        out.queue(cursor::RestorePosition)?.flush()?;
+        Ok(())
+    }
+
+    // Only the cells that differ from what is already on screen are sent, which keeps redraws calm over slow links.
+    fn diff_print(&self, out: &mut impl Write, screen: &Screen, origin_row: u16, previous_rows: &[Vec<StyledChar>]) -> Result<(), PrinterError> {
+        let image_rows = self.displayed_rows(screen);
+        for (row_index, row) in image_rows.iter().enumerate() {
+            let screen_row = origin_row + row_index as u16;
+            match previous_rows.get(row_index) {
+                Some(previous) if previous.len() == row.len() && previous.iter().zip(row.iter()).all(|(old, new)| old.width() == new.width()) => {
+                    let (mut cell, mut column) = (0, 0);
+                    while cell < row.len() {
+                        if previous[cell] == row[cell] {
+                            column += row[cell].width();
+                            cell += 1;
+                            continue;
+                        }
+                        let (run_start, run_column) = (cell, column);
+                        while cell < row.len() && previous[cell] != row[cell] {
+                            column += row[cell].width();
+                            cell += 1;
+                        }
+                        out
+                            .queue(cursor::MoveTo(run_column as u16, screen_row))?
+                            .queue(Print(sgr::render(&row[run_start..cell])))?;
+                    }
+                }
+                // Cells of another width shift everything after them, so such rows are sent whole.
+                _ => {
+                    out
+                        .queue(cursor::MoveTo(0, screen_row))?
+                        .queue(Print(sgr::render(row)))?
+                        .queue(Clear(ClearType::UntilNewLine))?;
+                }
+            }
+        }
+        out
+            .queue(cursor::MoveTo(0, origin_row + image_rows.len() as u16))?
+            .queue(Clear(ClearType::FromCursorDown))?
+            .flush()?;
+        Ok(())
+    }
+
+    fn drawn_rows(&self, screen: &Screen) -> Option<Vec<Vec<StyledChar>>> {
+        match self.graphic {
+            Some(_) => None,
+            None => Some(self.displayed_rows(screen).into_iter().map(<[StyledChar]>::to_vec).collect()),
+        }
+    }
+
+    fn instant_print(&self, out: &mut impl Write, screen: &Screen, origin_row: u16) -> Result<(), PrinterError> {
+        out.queue(cursor::MoveTo(0, origin_row))?.flush()?;
+        for row in self.displayed_rows(screen) {
+            out.queue(Print(sgr::render(row)))?.queue(Print('\n'))?.flush()?;
+        }
+        Ok(())
+    }
+
+    fn header(&self) -> String {
+        match &self.note {
+            Some(note) => fill(messages().image_header_with_note, &[&(self.index + 1), note]),
+            None => fill(messages().image_header, &[&(self.index + 1)]),
         }
+    }
+
+    // Output that is not a terminal gets the whole image row after row, without cursor movement, clearing or animation.
+    fn stream(&mut self, out: &mut impl Write) -> Result<(), PrinterError> {
+        out.queue(Print(self.header()))?.queue(Print('\n'))?;
+        for row in self.corrected_rows_of(&self.image_array) {
+            out.queue(Print(sgr::render(row)))?.queue(Print('\n'))?;
+        }
+        out.flush()?;
+        self.is_rendered = true;
         Ok(())
     }
 
-    fn print(&mut self) -> Result<(), PrinterError> {
-        println!("Image {}", self.index + 1);
-        if !self.is_rendered {
-            self.slow_print()?;
+    fn print(
+        &mut self,
+        out: &mut impl Write,
+        screen: &Screen,
+        origin_row: u16,
+        previous_rows: Option<&[Vec<StyledChar>]>,
+        status_bar: &str,
+    ) -> Result<(), PrinterError> {
+        out.queue(Print(self.header()))?.queue(Clear(ClearType::UntilNewLine))?.queue(Print('\n'))?.flush()?;
+        let render_start = Instant::now();
+        if let Some(graphic) = &self.graphic {
+            self.graphic_print(out, graphic, origin_row + 1)?;
+            self.is_rendered = true;
+        } else if !self.is_rendered {
+            self.options.printing_rate_ms = self.slow_print(out, screen, origin_row + 1)?;
             self.is_rendered = true;
+        } else if let Some(previous_rows) = previous_rows {
+            self.diff_print(out, screen, origin_row + 1, previous_rows)?;
         } else {
-            self.instant_print()?;
+            self.instant_print(out, screen, origin_row + 1)?;
+        }
+        self.print_status_bar(out, screen, origin_row + 1 + self.displayed_rows(screen).len() as u16, status_bar)?;
+        self.timings.record(TimingStage::Render, render_start.elapsed());
+        if self.options.show_timings {
+            Logger::log_info(&fill(messages().timings_header, &[&self.timings]));
         }
         Ok(())
     }
 
-    fn get_clipboard_version(&self) -> Result<String, PrinterError> {
+    fn print_status_bar(&self, out: &mut impl Write, screen: &Screen, row: u16, status_bar: &str) -> Result<(), PrinterError> {
+        let status_bar: String = status_bar.chars().take(screen.width()).collect();
+        out
+            .queue(cursor::MoveTo(0, row))?
+            .queue(SetAttribute(Attribute::Reverse))?
+            .queue(Print(status_bar))?
+            .queue(SetAttribute(Attribute::Reset))?
+            .queue(Clear(ClearType::UntilNewLine))?
+            .queue(Print('\n'))?
+            .flush()?;
+        Ok(())
+    }
+
+    fn clipboard_char(styled_char: &StyledChar, character_ramp: &CharacterRamp) -> char {
+        match styled_char.background.map(|colour| colour.rgb()) {
+            Some((r, g, b)) if styled_char.ch == ' ' && styled_char.foreground.is_none() => {
+                character_ramp.char_for_brightness(Luminance::default().of(&Rgb([r, g, b])))
+            }
+            _ => styled_char.ch,
+        }
+    }
+
+    fn get_clipboard_version(&self, character_ramp: &CharacterRamp) -> Result<String, PrinterError> {
         if self.image_array.is_empty() || self.image_array[0].is_empty() {
             return Err(PrinterError::EmptyImageError);
         }
-        let mut result = String::with_capacity(self.image_array.len() * (self.image_array[0].len() + 1) + 1);
-        for row in &self.image_array {
-            row.into_iter().try_for_each(|cell|
-                return match cell[..cell.len() - 2].rfind('m') { //..m{CHAR}\..
-                Some(backslash_index) => {
-                    if backslash_index < cell.len() - 2 {
-                        result.push_str(&cell[backslash_index +1.. backslash_index + 2]);
-                        Ok(())
-                    } else {
-                        Err(PrinterError::InvalidImageError)
-                    }
-                }
-                None => Err(PrinterError::InvalidImageError),
-            })?;
+        let image_rows = self.corrected_rows_of(&self.image_array);
+        let mut result = String::with_capacity(image_rows.len() * (self.image_array[0].len() + 1) + 1);
+        for row in image_rows {
+            result.extend(row.iter().map(|styled_char| Self::clipboard_char(styled_char, character_ramp)));
             result.push('\n');
         }
         if !result.is_empty() {
@@ -144,22 +834,92 @@ impl ColouredImage {
         }
         Ok(result)
     }
+
+    // Every row carries its own colour codes and ends reset, so any slice of the paste still shows correctly.
+    fn get_ansi_clipboard_version(&self) -> Result<String, PrinterError> {
+        if self.image_array.is_empty() || self.image_array[0].is_empty() {
+            return Err(PrinterError::EmptyImageError);
+        }
+        let rows: Vec<String> = self.corrected_rows_of(&self.image_array).into_iter().map(|row| sgr::render(row)).collect();
+        Ok(rows.join("\n"))
+    }
 }
 
 pub struct PrinterImageData {
     image_name: Rc<String>,
-    image_array: Vec<Vec<String>>,
+    image_array: Vec<Vec<StyledChar>>,
+    timings: ImageTimings,
+    note: Option<String>,
+    favourite: bool,
+    graphic: Option<String>,
+    frames: Vec<AnimationFrame>,
+    source: Option<Bytes>,
+    path: Option<PathBuf>,
 }
 
 impl PrinterImageData {
-    pub fn new(image_name: Rc<String>, image_array: Vec<Vec<String>>) -> Self {
+    pub fn new(image_name: Rc<String>, image_array: Vec<Vec<StyledChar>>) -> Self {
         Self {
             image_name,
             image_array,
+            timings: ImageTimings::default(),
+            note: None,
+            favourite: false,
+            graphic: None,
+            frames: Vec::new(),
+            source: None,
+            path: None,
         }
     }
+
+    pub fn with_timings(mut self, timings: ImageTimings) -> Self {
+        self.timings = timings;
+        self
+    }
+
+    pub fn with_note(mut self, note: Option<String>) -> Self {
+        self.note = note;
+        self
+    }
+
+    pub fn with_favourite(mut self, favourite: bool) -> Self {
+        self.favourite = favourite;
+        self
+    }
+
+    pub fn is_favourite(&self) -> bool {
+        self.favourite
+    }
+
+    pub fn with_frames(mut self, frames: Vec<AnimationFrame>) -> Self {
+        self.frames = frames;
+        self
+    }
+
+    pub fn with_graphic(mut self, graphic: Option<String>) -> Self {
+        self.graphic = graphic;
+        self
+    }
+
+    pub fn with_source(mut self, source: Bytes) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    pub fn with_path(mut self, path: PathBuf) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    pub fn into_image_array(self) -> Vec<Vec<StyledChar>> {
+        self.image_array
+    }
 }
 
+// Brings back an evicted image from its source bytes or the file it was loaded from.
+pub type ImageRestorer<G> = fn(&G, Rc<String>, Option<Bytes>, Option<&Path>) -> Option<PrinterImageData>;
+pub type CancellationCheck<G> = fn(&G) -> bool;
+
 pub struct Printer<G>
 where
     G: Iterator<Item = PrinterImageData>,
@@ -167,23 +927,180 @@ where
     image_generator: G,
     coloured_images: Vec<ColouredImage>,
     current_image: usize,
-    printing_rate_ms: u16,
+    options: PrinterOptions,
+    toast: Option<Toast>,
+    origin_row: Option<u16>,
+    alternate_screen_active: bool,
+    drawn_frame: Option<DrawnFrame>,
+    status_hints: Option<&'static str>,
+    pinned_image: Option<usize>,
+    image_restorer: Option<ImageRestorer<G>>,
+    cancellation_check: Option<CancellationCheck<G>>,
+    view_clock: u64,
+    is_terminal: bool,
+    character_ramp: CharacterRamp,
+}
+
+// What the image area showed after the last print, so the next one can send only what changed.
+struct DrawnFrame {
+    origin_row: u16,
+    rows: Vec<Vec<StyledChar>>,
 }
 
 impl<G> Printer<G>
 where
     G: Iterator<Item = PrinterImageData>,
 {
-    pub fn new(image_generator: G, printing_rate_ms: u16) -> Self {
+    const TOAST_DURATION: Duration = Duration::from_secs(2);
+    const FAVOURITE_MARK: char = '★';
+    const COMPARISON_SEPARATOR: [char; 3] = [' ', '│', ' '];
+    const THUMBNAIL_COLUMNS: usize = 20;
+    const THUMBNAIL_ROWS: usize = 8;
+    const GALLERY_GAP: usize = 2;
+    // Used when the terminal does not report its size.
+    const FALLBACK_GALLERY_WIDTH: usize = 80;
+    // The thumbnail, its label and an empty row.
+    const GALLERY_CELL_HEIGHT: usize = Self::THUMBNAIL_ROWS + 2;
+
+    pub fn new(image_generator: G, options: PrinterOptions) -> Self {
         Self {
             image_generator,
             coloured_images: Vec::new(),
             current_image: 0,
-            printing_rate_ms,       
+            options,
+            toast: None,
+            origin_row: None,
+            alternate_screen_active: false,
+            drawn_frame: None,
+            status_hints: None,
+            pinned_image: None,
+            image_restorer: None,
+            cancellation_check: None,
+            view_clock: 0,
+            is_terminal: io::stdout().is_terminal(),
+            character_ramp: CharacterRamp::default(),
+        }
+    }
+
+    pub fn with_status_hints(mut self, status_hints: &'static str) -> Self {
+        self.status_hints = Some(status_hints);
+        self
+    }
+
+    // Background-only cells are copied as the characters the render would have used for them.
+    pub fn with_character_ramp(mut self, character_ramp: CharacterRamp) -> Self {
+        self.character_ramp = character_ramp;
+        self
+    }
+
+    // Without a way to bring images back, none are ever evicted.
+    pub fn with_image_restorer(mut self, image_restorer: ImageRestorer<G>) -> Self {
+        self.image_restorer = Some(image_restorer);
+        self
+    }
+
+    // Lets an empty answer from the generator be told apart: the user cancelled, or the images ran out.
+    pub fn with_cancellation_check(mut self, cancellation_check: CancellationCheck<G>) -> Self {
+        self.cancellation_check = Some(cancellation_check);
+        self
+    }
+
+    fn empty_generator_error(&self, exhausted_error: PrinterError) -> PrinterError {
+        match self.cancellation_check {
+            Some(was_cancelled) if was_cancelled(&self.image_generator) => PrinterError::CancelledError,
+            _ => exhausted_error,
+        }
+    }
+
+    // Once more images are resident than the limit allows, the ones viewed longest ago give up their rows;
+    // the current and the pinned image always stay.
+    fn evict_least_recently_viewed(&mut self) {
+        if self.image_restorer.is_none() || self.options.cache_limit == 0 {
+            return;
+        }
+        let mut resident = self.coloured_images.iter().filter(|image| !image.evicted).count();
+        while resident > self.options.cache_limit {
+            let least_recently_viewed = self.coloured_images
+                .iter()
+                .enumerate()
+                .filter(|(index, image)| !image.evicted && *index != self.current_image && Some(*index) != self.pinned_image)
+                .min_by_key(|(_, image)| image.last_viewed)
+                .map(|(index, _)| index);
+            match least_recently_viewed {
+                Some(index) => self.coloured_images[index].evict(Self::THUMBNAIL_COLUMNS, Self::THUMBNAIL_ROWS),
+                None => return,
+            }
+            resident -= 1;
+        }
+    }
+
+    // The restored image is converted with the current settings, but keeps what the user attached to it.
+    fn set_current_image(&mut self, index: usize) -> Result<(), PrinterError> {
+        if self.coloured_images[index].evicted {
+            let evicted = &self.coloured_images[index];
+            let image_data = self.image_restorer
+                .and_then(|restore| restore(&self.image_generator, evicted.image_name.clone(), evicted.source.clone(), evicted.path.as_deref()))
+                .ok_or(PrinterError::EvictedImageError)?;
+            let mut restored = self.coloured_image_from(image_data, index);
+            let evicted = &self.coloured_images[index];
+            restored.note = evicted.note.clone();
+            restored.favourite = evicted.favourite;
+            restored.aspect_corrected = evicted.aspect_corrected;
+            restored.is_rendered = evicted.is_rendered;
+            restored.pan = evicted.pan;
+            restored.source = restored.source.or_else(|| evicted.source.clone());
+            restored.path = restored.path.or_else(|| evicted.path.clone());
+            self.coloured_images[index] = restored;
         }
+        self.current_image = index;
+        Ok(())
+    }
+
+    fn image_origin_row(&self) -> u16 {
+        self.origin_row.unwrap_or(0) + 1
     }
 
-    pub fn get_current_image_data(&self) -> Result<(&str, &Vec<Vec<String>>), PrinterError> {
+    fn prepare_screen(&mut self, rows_needed: usize) -> Result<u16, PrinterError> {
+        let mut stdout = io::stdout();
+        let origin_row = match self.options.clear_mode {
+            ClearMode::FullScreen => {
+                stdout.queue(Clear(ClearType::All))?;
+                0
+            }
+            ClearMode::AlternateScreen => {
+                if !self.alternate_screen_active {
+                    stdout.queue(EnterAlternateScreen)?;
+                    self.alternate_screen_active = true;
+                }
+                stdout.queue(Clear(ClearType::All))?;
+                0
+            }
+            ClearMode::PreserveScrollback => {
+                let (_, height) = terminal::size()?;
+                let origin_row = match self.origin_row {
+                    Some(origin_row) => origin_row,
+                    None => cursor::position()?.1,
+                };
+                stdout
+                    .queue(cursor::MoveTo(0, origin_row))?
+                    .queue(Clear(ClearType::FromCursorDown))?;
+                let rows_needed = rows_needed.min(height as usize) as u16;
+                let overflow = (origin_row + rows_needed).saturating_sub(height);
+                if overflow > 0 {
+                    stdout.queue(cursor::MoveTo(0, height - 1))?;
+                    for _ in 0..overflow {
+                        stdout.queue(Print('\n'))?;
+                    }
+                }
+                origin_row - overflow
+            }
+        };
+        stdout.queue(cursor::MoveTo(0, origin_row))?.flush()?;
+        self.origin_row = Some(origin_row);
+        Ok(origin_row)
+    }
+
+    pub fn get_current_image_data(&self) -> Result<(&str, &Vec<Vec<StyledChar>>), PrinterError> {
         if self.coloured_images.is_empty() {
             return Err(PrinterError::NoImagesRegisteredError);
         }
@@ -194,28 +1111,373 @@ where
         ))
     }
 
+    // The rows as shown, aspect correction included, rather than as stored.
+    pub fn get_current_displayed_image_data(&self) -> Result<(&str, Vec<&Vec<StyledChar>>), PrinterError> {
+        let current_image = self.coloured_images.get(self.current_image).ok_or(PrinterError::NoImagesRegisteredError)?;
+        Ok((
+            current_image.image_name.as_str(),
+            current_image.corrected_rows_of(&current_image.image_array),
+        ))
+    }
+
+    pub fn get_current_image_note(&self) -> Option<&str> {
+        self.coloured_images.get(self.current_image)?.note.as_deref()
+    }
+
+    pub fn is_current_image_favourite(&self) -> bool {
+        self.coloured_images.get(self.current_image).is_some_and(|image| image.favourite)
+    }
+
+    pub fn toggle_current_image_favourite(&mut self) -> Result<bool, PrinterError> {
+        let image = self.coloured_images.get_mut(self.current_image).ok_or(PrinterError::NoImagesRegisteredError)?;
+        image.favourite = !image.favourite;
+        Ok(image.favourite)
+    }
+
+    pub fn set_current_image_note(&mut self, note: &str) -> Result<&mut Printer<G>, PrinterError> {
+        if self.coloured_images.is_empty() {
+            return Err(PrinterError::NoImagesRegisteredError);
+        }
+        let note = note.trim();
+        self.coloured_images[self.current_image].note = if note.is_empty() { None } else { Some(note.to_string()) };
+        Ok(self)
+    }
+
     pub fn print_current_image(&mut self) -> Result<(), PrinterError>{
         if self.coloured_images.is_empty() {
             if let Some(image_data) = self.image_generator.next() {
                 self.add_image_and_set_current(image_data);
             } else {
-                return Err(PrinterError::NoImagesRegisteredError);
+                return Err(self.empty_generator_error(PrinterError::NoImagesRegisteredError));
             }
         }
-        self.coloured_images[self.current_image].print()
+        // A toast is not part of the drawn frame, so the screen it sits on is no longer known.
+        if self.toast.take().is_some() {
+            self.drawn_frame = None;
+        }
+        self.view_clock += 1;
+        self.coloured_images[self.current_image].last_viewed = self.view_clock;
+        self.evict_least_recently_viewed();
+        let mut stdout = io::stdout();
+        if !self.is_terminal {
+            return self.coloured_images[self.current_image].stream(&mut stdout);
+        }
+        let screen = Screen::terminal();
+        if let Some(pinned_image) = self.compared_image() {
+            return self.print_comparison(&mut stdout, &screen, pinned_image);
+        }
+        let image = &self.coloured_images[self.current_image];
+        let rows_needed = image.displayed_rows(&screen).len() + RESERVED_ROWS;
+        let prints_instantly = image.graphic.is_none() && (image.is_rendered || self.options.printing_rate_ms == 0);
+        let previous_frame = match self.drawn_frame.take() {
+            Some(frame) if prints_instantly && Self::is_frame_intact(&frame, rows_needed)? => Some(frame),
+            _ => None,
+        };
+        let origin_row = match &previous_frame {
+            Some(frame) => {
+                stdout.queue(cursor::MoveTo(0, frame.origin_row))?.flush()?;
+                frame.origin_row
+            }
+            None => self.prepare_screen(rows_needed)?,
+        };
+        let status_bar = self.status_bar();
+        let image = &mut self.coloured_images[self.current_image];
+        image.print(&mut stdout, &screen, origin_row, previous_frame.as_ref().map(|frame| frame.rows.as_slice()), &status_bar)?;
+        self.drawn_frame = image.drawn_rows(&screen).map(|rows| DrawnFrame { origin_row, rows });
+        self.set_printing_rate(self.coloured_images[self.current_image].options.printing_rate_ms);
+        Ok(())
     }
 
-    fn add_image_and_set_current(&mut self, image_data: PrinterImageData) {
-        let new_image_index = self.coloured_images.len();
-        self.coloured_images.push(ColouredImage::new(
+    // The pinned image only takes part once another image is current.
+    fn compared_image(&self) -> Option<usize> {
+        self.pinned_image.filter(|pinned_image| *pinned_image != self.current_image && *pinned_image < self.coloured_images.len())
+    }
+
+    pub fn toggle_pinned_image(&mut self) -> Result<bool, PrinterError> {
+        if self.coloured_images.is_empty() {
+            return Err(PrinterError::NoImagesRegisteredError);
+        }
+        self.pinned_image = match self.pinned_image {
+            Some(_) => None,
+            None => Some(self.current_image),
+        };
+        Ok(self.pinned_image.is_some())
+    }
+
+    // Both images are clipped to half the terminal each and drawn instantly, without panning or reveal.
+    fn comparison_rows(&self, screen: &Screen, pinned_image: usize) -> Vec<Vec<StyledChar>> {
+        let (width, height) = screen.image_area();
+        let half_width = width.saturating_sub(Self::COMPARISON_SEPARATOR.len()) / 2;
+        let clipped_rows = |image: &ColouredImage| -> Vec<Vec<StyledChar>> {
+            image.corrected_rows_of(&image.image_array)
+                .into_iter()
+                .take(height)
+                .map(|row| {
+                    let mut used_width = 0;
+                    row.iter()
+                        .take_while(|styled_char| {
+                            used_width += styled_char.width();
+                            used_width <= half_width
+                        })
+                        .copied()
+                        .collect()
+                })
+                .collect()
+        };
+        let left = clipped_rows(&self.coloured_images[pinned_image]);
+        let right = clipped_rows(&self.coloured_images[self.current_image]);
+        let row_width = |row: &Vec<StyledChar>| row.iter().map(StyledChar::width).sum::<usize>();
+        let left_width = left.iter().map(row_width).max().unwrap_or(0);
+        (0..left.len().max(right.len()))
+            .map(|row_index| {
+                let mut row = left.get(row_index).cloned().unwrap_or_default();
+                let padding = left_width - row_width(&row);
+                row.extend(std::iter::repeat_n(StyledChar::plain(' '), padding));
+                row.extend(Self::COMPARISON_SEPARATOR.map(StyledChar::plain));
+                row.extend(right.get(row_index).into_iter().flatten().copied());
+                row
+            })
+            .collect()
+    }
+
+    fn print_comparison(&mut self, out: &mut impl Write, screen: &Screen, pinned_image: usize) -> Result<(), PrinterError> {
+        let rows = self.comparison_rows(screen, pinned_image);
+        let origin_row = self.prepare_screen(rows.len() + RESERVED_ROWS)?;
+        let header = fill(messages().comparison_header, &[
+            &(self.coloured_images[pinned_image].index + 1),
+            &(self.coloured_images[self.current_image].index + 1),
+        ]);
+        out.queue(Print(header))?.queue(Clear(ClearType::UntilNewLine))?.queue(Print('\n'))?;
+        for row in &rows {
+            out.queue(Print(sgr::render(row)))?.queue(Clear(ClearType::UntilNewLine))?.queue(Print('\n'))?;
+        }
+        out.flush()?;
+        let status_bar = self.status_bar();
+        let image = &mut self.coloured_images[self.current_image];
+        image.print_status_bar(out, screen, origin_row + 1 + rows.len() as u16, &status_bar)?;
+        image.is_rendered = true;
+        self.drawn_frame = None;
+        Ok(())
+    }
+
+    // Images still to come are only counted when the generator knows how many there are.
+    fn status_bar(&self) -> String {
+        let image = &self.coloured_images[self.current_image];
+        let total = match self.image_generator.size_hint() {
+            (_, Some(remaining)) => (self.coloured_images.len() + remaining).to_string(),
+            (_, None) => format!("{}+", self.coloured_images.len()),
+        };
+        let columns = image.image_array.first().map_or(0, Vec::len);
+        let image_name = if image.favourite {
+            format!("{} {}", image.image_name, Self::FAVOURITE_MARK)
+        } else {
+            image.image_name.to_string()
+        };
+        let position = fill(messages().status_bar, &[&(image.index + 1), &total, &image_name, &columns, &image.image_array.len()]);
+        match self.status_hints {
+            Some(hints) => format!(" {}   {} ", position, hints),
+            None => format!(" {} ", position),
+        }
+    }
+
+    // Anything that scrolled the terminal since the last print moved the frame, and the cursor then sits on the bottom row.
+    fn is_frame_intact(frame: &DrawnFrame, rows_needed: usize) -> Result<bool, PrinterError> {
+        let (_, height) = terminal::size()?;
+        // A terminal that does not report the cursor gets a full redraw.
+        let Ok((_, cursor_row)) = cursor::position() else {
+            return Ok(false);
+        };
+        Ok(cursor_row + 1 < height && frame.origin_row as usize + rows_needed <= height as usize)
+    }
+
+    // Fits the current image to a resized terminal; before the first image there is nothing to redraw.
+    pub fn reprint_current_image(&mut self) -> Result<(), PrinterError> {
+        match self.coloured_images.get_mut(self.current_image) {
+            Some(image) if image.is_rendered => {
+                image.pan(&Screen::terminal(), 0, 0);
+                self.drawn_frame = None;
+                self.print_current_image()
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn play_current_animation(&mut self) -> Result<(), PrinterError> {
+        let origin_row = self.image_origin_row();
+        let image = match self.coloured_images.get(self.current_image) {
+            Some(image) if image.is_animated() && self.compared_image().is_none() && self.is_terminal => image,
+            _ => return Ok(()),
+        };
+        self.drawn_frame = None;
+        io::stdout().queue(cursor::Hide)?.flush()?;
+        let result = image.play(&mut io::stdout(), &Screen::terminal(), origin_row);
+        io::stdout().queue(cursor::Show)?.flush()?;
+        result
+    }
+
+    pub fn show_toast(&mut self, message: &str, kind: ToastKind) -> Result<(), PrinterError> {
+        self.clear_toast()?;
+        let screen = Screen::terminal();
+        let image_width = match self.coloured_images.get(self.current_image) {
+            Some(image) if image.is_rendered && self.is_terminal => image.display_width(&screen),
+            _ => {
+                match kind {
+                    ToastKind::Success => Logger::log_success(message),
+                    ToastKind::Info => Logger::log_info(message),
+                    ToastKind::Error => Logger::log_error(message),
+                }
+                return Ok(());
+            }
+        };
+        let image_width = match self.compared_image() {
+            Some(pinned_image) => self.comparison_rows(&screen, pinned_image).first().map_or(0, |row| row.iter().map(StyledChar::width).sum()),
+            None => image_width,
+        };
+        let text = format!(" {} ", message);
+        let width = text.chars().count();
+        let column = image_width.saturating_sub(width);
+        let row = self.image_origin_row();
+        let background = match kind {
+            ToastKind::Success => Color::DarkGreen,
+            ToastKind::Info => Color::DarkYellow,
+            ToastKind::Error => Color::DarkRed,
+        };
+        io::stdout()
+            .queue(cursor::SavePosition)?
+            .queue(cursor::MoveTo(column as u16, row))?
+            .queue(SetForegroundColor(Color::White))?
+            .queue(SetBackgroundColor(background))?
+            .queue(Print(text))?
+            .queue(ResetColor)?
+            .queue(cursor::RestorePosition)?
+            .flush()?;
+        self.toast = Some(Toast {
+            column,
+            width,
+            shown_at: Instant::now(),
+        });
+        Ok(())
+    }
+
+    pub fn expire_toast(&mut self) -> Result<(), PrinterError> {
+        match &self.toast {
+            Some(toast) if toast.shown_at.elapsed() >= Self::TOAST_DURATION => self.clear_toast(),
+            _ => Ok(()),
+        }
+    }
+
+    fn clear_toast(&mut self) -> Result<(), PrinterError> {
+        let toast = match self.toast.take() {
+            Some(toast) => toast,
+            None => return Ok(()),
+        };
+        let image = &self.coloured_images[self.current_image];
+        let screen = Screen::terminal();
+        if let Some(pinned_image) = self.compared_image() {
+            let first_row = self.comparison_rows(&screen, pinned_image).into_iter().next().unwrap_or_default();
+            let padding = (toast.column + toast.width).saturating_sub(first_row.iter().map(StyledChar::width).sum());
+            io::stdout()
+                .queue(cursor::SavePosition)?
+                .queue(cursor::MoveTo(0, self.image_origin_row()))?
+                .queue(Print(sgr::render(&first_row)))?
+                .queue(Print(" ".repeat(padding)))?
+                .queue(cursor::RestorePosition)?
+                .flush()?;
+            return Ok(());
+        }
+        if let Some(graphic) = &image.graphic {
+            let mut stdout = io::stdout();
+            stdout.queue(cursor::SavePosition)?;
+            image.graphic_print(&mut stdout, graphic, self.image_origin_row())?;
+            stdout.queue(cursor::RestorePosition)?.flush()?;
+            return Ok(());
+        }
+        // The whole row is redrawn, as a wide cell cannot be restored from its second column.
+        let first_row = image.displayed_rows(&screen)[0];
+        let padding = (toast.column + toast.width).saturating_sub(image.display_width(&screen));
+        io::stdout()
+            .queue(cursor::SavePosition)?
+            .queue(cursor::MoveTo(0, self.image_origin_row()))?
+            .queue(Print(sgr::render(first_row)))?
+            .queue(Print(" ".repeat(padding)))?
+            .queue(cursor::RestorePosition)?
+            .flush()?;
+        Ok(())
+    }
+
+    fn coloured_image_from(&self, image_data: PrinterImageData, index: usize) -> ColouredImage {
+        let mut coloured_image = ColouredImage::new(
             image_data.image_array,
-            new_image_index,
+            index,
             &image_data.image_name,
-            self.printing_rate_ms
-        ));
+            self.options,
+            image_data.timings,
+        );
+        coloured_image.note = image_data.note;
+        coloured_image.favourite = image_data.favourite;
+        coloured_image.graphic = image_data.graphic;
+        coloured_image.frames = image_data.frames;
+        coloured_image.source = image_data.source;
+        coloured_image.path = image_data.path;
+        coloured_image
+    }
+
+    fn add_image_and_set_current(&mut self, image_data: PrinterImageData) {
+        let new_image_index = self.coloured_images.len();
+        let coloured_image = self.coloured_image_from(image_data, new_image_index);
+        self.coloured_images.push(coloured_image);
         self.current_image = new_image_index; 
     }
 
+    pub fn get_current_image_source(&self) -> Option<(Rc<String>, Bytes)> {
+        let coloured_image = self.coloured_images.get(self.current_image)?;
+        Some((coloured_image.image_name.clone(), coloured_image.source.clone()?))
+    }
+
+    pub fn get_current_image_path(&self) -> Option<&Path> {
+        self.coloured_images.get(self.current_image)?.path.as_deref()
+    }
+
+    // The next image takes the removed one's place, or the previous one when it was the last.
+    pub fn remove_current_image(&mut self) -> Result<&mut Printer<G>, PrinterError> {
+        if self.coloured_images.is_empty() {
+            return Err(PrinterError::NoImagesRegisteredError);
+        }
+        self.coloured_images.remove(self.current_image);
+        for image in &mut self.coloured_images[self.current_image..] {
+            image.index -= 1;
+        }
+        self.pinned_image = match self.pinned_image {
+            Some(pinned_image) if pinned_image == self.current_image => None,
+            Some(pinned_image) if pinned_image > self.current_image => Some(pinned_image - 1),
+            pinned_image => pinned_image,
+        };
+        if self.current_image == self.coloured_images.len() {
+            match self.image_generator.next() {
+                Some(image_data) => self.add_image_and_set_current(image_data),
+                None if self.current_image == 0 => return Err(self.empty_generator_error(PrinterError::NoImagesRegisteredError)),
+                None => self.set_current_image(self.current_image - 1)?,
+            }
+        } else {
+            self.set_current_image(self.current_image)?;
+        }
+        Ok(self)
+    }
+
+    // The note, favourite mark and aspect correction of the replaced image carry over.
+    pub fn replace_current_image(&mut self, image_data: PrinterImageData) -> Result<&mut Printer<G>, PrinterError> {
+        if self.coloured_images.is_empty() {
+            return Err(PrinterError::NoImagesRegisteredError);
+        }
+        let mut coloured_image = self.coloured_image_from(image_data, self.current_image);
+        let replaced = &self.coloured_images[self.current_image];
+        coloured_image.note = replaced.note.clone();
+        coloured_image.favourite = replaced.favourite;
+        coloured_image.aspect_corrected = replaced.aspect_corrected;
+        self.coloured_images[self.current_image] = coloured_image;
+        Ok(self)
+    }
+
 
     pub fn move_to_previous_image(&mut self) -> Result<&mut Printer<G>, PrinterError> {
         if self.coloured_images.is_empty() {
@@ -224,10 +1486,84 @@ where
         if self.current_image == 0 {
             return Err(PrinterError::NoImageLeftError);
         }
-        self.current_image -= 1;
+        self.set_current_image(self.current_image - 1)?;
         Ok(self)
     }
 
+    pub fn move_to_image(&mut self, index: usize) -> Result<&mut Printer<G>, PrinterError> {
+        if self.coloured_images.is_empty() {
+            return Err(PrinterError::NoImagesRegisteredError);
+        }
+        if index >= self.coloured_images.len() {
+            return Err(PrinterError::NoImageLeftError);
+        }
+        self.set_current_image(index)?;
+        Ok(self)
+    }
+
+    pub fn image_generator(&self) -> &G {
+        &self.image_generator
+    }
+
+    pub fn image_generator_mut(&mut self) -> &mut G {
+        &mut self.image_generator
+    }
+
+    pub fn image_count(&self) -> usize {
+        self.coloured_images.len()
+    }
+
+    pub fn current_image_index(&self) -> usize {
+        self.current_image
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        self.is_terminal
+    }
+
+    pub fn gallery_columns(&self) -> usize {
+        let width = terminal::size().map_or(Self::FALLBACK_GALLERY_WIDTH, |(width, _)| width as usize);
+        ((width + Self::GALLERY_GAP) / (Self::THUMBNAIL_COLUMNS + Self::GALLERY_GAP)).max(1)
+    }
+
+    // Shows as many rows of thumbnails as fit, scrolled so the selected one is on screen.
+    pub fn print_gallery(&mut self, selected: usize) -> Result<(), PrinterError> {
+        if self.coloured_images.is_empty() {
+            return Err(PrinterError::NoImagesRegisteredError);
+        }
+        if !self.is_terminal {
+            return Ok(());
+        }
+        let (_, height) = terminal::size()?;
+        let columns = self.gallery_columns();
+        let visible_rows = ((height as usize).saturating_sub(2) / Self::GALLERY_CELL_HEIGHT).max(1);
+        let first_row = (selected / columns).saturating_sub(visible_rows - 1);
+        self.toast = None;
+        self.drawn_frame = None;
+        let origin_row = self.prepare_screen(height as usize)?;
+        let mut stdout = io::stdout();
+        stdout
+            .queue(Print(fill(messages().gallery_header, &[&(selected + 1), &self.coloured_images.len()])))?
+            .queue(Clear(ClearType::UntilNewLine))?;
+        for (slot, image) in self.coloured_images.iter().enumerate().skip(first_row * columns).take(visible_rows * columns) {
+            let top = origin_row + 1 + ((slot / columns - first_row) * Self::GALLERY_CELL_HEIGHT) as u16;
+            let left = ((slot % columns) * (Self::THUMBNAIL_COLUMNS + Self::GALLERY_GAP)) as u16;
+            for (row_index, row) in image.thumbnail(Self::THUMBNAIL_COLUMNS, Self::THUMBNAIL_ROWS).iter().enumerate() {
+                stdout.queue(cursor::MoveTo(left, top + row_index as u16))?.queue(Print(sgr::render(row)))?;
+            }
+            let label: String = format!("{} {}", image.index + 1, image.image_name).chars().take(Self::THUMBNAIL_COLUMNS).collect();
+            stdout.queue(cursor::MoveTo(left, top + Self::THUMBNAIL_ROWS as u16))?;
+            if slot == selected {
+                stdout.queue(SetAttribute(Attribute::Reverse))?;
+            }
+            stdout.queue(Print(label))?.queue(SetAttribute(Attribute::Reset))?;
+        }
+        stdout
+            .queue(cursor::MoveTo(0, origin_row + 1 + (visible_rows * Self::GALLERY_CELL_HEIGHT) as u16))?
+            .flush()?;
+        Ok(())
+    }
+
     pub fn move_to_next_image(&mut self) -> Result<&mut Printer<G>, PrinterError> {
         if self.coloured_images.is_empty() {
             return match self.image_generator.next() {
@@ -235,11 +1571,11 @@ where
                     self.add_image_and_set_current(image_data);
                     Ok(self)
                 }
-                None => Err(PrinterError::NoImagesRegisteredError)
+                None => Err(self.empty_generator_error(PrinterError::NoImagesRegisteredError)),
             }
         }
         if self.current_image < self.coloured_images.len() - 1 {
-            self.current_image += 1;
+            self.set_current_image(self.current_image + 1)?;
             Ok(self)
         } else {
             match self.image_generator.next() {
@@ -247,24 +1583,199 @@ where
                     self.add_image_and_set_current(image_data);
                     Ok(self)
                 }
-                None => Err(PrinterError::NoImageLeftError),
+                None => Err(self.empty_generator_error(PrinterError::NoImageLeftError)),
             }
         }
     }
 
+    // Wraps around to the first image once the generator runs dry, so a slideshow keeps going.
+    pub fn move_to_next_image_looping(&mut self) -> Result<&mut Printer<G>, PrinterError> {
+        match self.move_to_next_image().map(|_| ()) {
+            Ok(()) => Ok(self),
+            Err(PrinterError::NoImageLeftError) => {
+                self.set_current_image(0)?;
+                Ok(self)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn pan_current_image(&mut self, row_step: isize, column_step: isize) -> Result<(), PrinterError> {
+        let compared = self.compared_image().is_some();
+        let image = match self.coloured_images.get_mut(self.current_image) {
+            Some(image) if image.is_rendered && image.graphic.is_none() && !compared && self.is_terminal => image,
+            Some(_) => return Ok(()),
+            None => return Err(PrinterError::NoImagesRegisteredError),
+        };
+        let screen = Screen::terminal();
+        if !image.pan(&screen, row_step, column_step) {
+            return Ok(());
+        }
+        // The redraw wipes the toast along with the old first row.
+        self.toast = None;
+        let image = &self.coloured_images[self.current_image];
+        image.redraw(&mut io::stdout(), &screen, self.image_origin_row())?;
+        if let Some(frame) = &mut self.drawn_frame {
+            frame.rows = image.drawn_rows(&screen).unwrap_or_default();
+        }
+        Ok(())
+    }
+
+    pub fn toggle_aspect_correction(&mut self) -> Result<&mut Printer<G>, PrinterError> {
+        if self.coloured_images.is_empty() {
+            return Err(PrinterError::NoImagesRegisteredError);
+        }
+        let current_image = &mut self.coloured_images[self.current_image];
+        current_image.aspect_corrected = !current_image.aspect_corrected;
+        Ok(self)
+    }
+
+    pub fn is_current_image_aspect_corrected(&self) -> bool {
+        self.coloured_images
+            .get(self.current_image)
+            .is_some_and(|image| image.aspect_corrected)
+    }
+
     pub fn copy_current_image_to_clipboard(&mut self) -> Result<(), PrinterError> {
         if self.coloured_images.is_empty() {
             return Err(PrinterError::NoImagesRegisteredError);
         }
-        let mut clip_ctx = ClipboardContext::new()
-            .map_err(|_| PrinterError::ClipboardError)?;
-        clip_ctx.set_contents(self.coloured_images[self.current_image].get_clipboard_version()?)
-            .map_err(|_| PrinterError::ClipboardError)?;
-        Ok(())
+        Self::set_clipboard(self.coloured_images[self.current_image].get_clipboard_version(&self.character_ramp)?)
+    }
+
+    // Tells whether the colours made it; when they make the copy too large for the terminal, the plain text goes instead.
+    pub fn copy_current_ansi_image_to_clipboard(&mut self) -> Result<bool, PrinterError> {
+        if self.coloured_images.is_empty() {
+            return Err(PrinterError::NoImagesRegisteredError);
+        }
+        let image = &self.coloured_images[self.current_image];
+        match Self::set_clipboard(image.get_ansi_clipboard_version()?) {
+            Err(PrinterError::ClipboardTooLargeError) => {
+                Self::set_clipboard(image.get_clipboard_version(&self.character_ramp)?)?;
+                Ok(false)
+            }
+            result => result.map(|_| true),
+        }
+    }
+
+    // Without a reachable system clipboard, as on headless or SSH machines, the terminal is asked to take the text instead.
+    fn set_clipboard(contents: String) -> Result<(), PrinterError> {
+        let copied = ClipboardContext::new()
+            .ok()
+            .is_some_and(|mut clip_ctx| clip_ctx.set_contents(contents.clone()).is_ok());
+        if copied {
+            return Ok(());
+        }
+        if !Osc52::fits(&contents) {
+            return Err(PrinterError::ClipboardTooLargeError);
+        }
+        Osc52::copy(&contents).map_err(|_| PrinterError::ClipboardError)
     }
     
-    #[allow(dead_code)]
+    // Brings back the user's own screen and scrollback; also runs on drop for exits through errors.
+    pub fn restore_screen(&mut self) -> Result<(), PrinterError> {
+        if self.alternate_screen_active {
+            io::stdout().queue(cursor::Show)?.execute(LeaveAlternateScreen)?;
+            self.alternate_screen_active = false;
+            self.drawn_frame = None;
+        }
+        Ok(())
+    }
+
+    pub fn printing_rate(&self) -> u16 {
+        self.options.printing_rate_ms
+    }
+
+    pub fn step_printing_rate(&mut self, faster: bool) -> u16 {
+        self.set_printing_rate(stepped_printing_rate(self.options.printing_rate_ms, faster));
+        self.options.printing_rate_ms
+    }
+
     pub fn set_printing_rate(&mut self, printing_rate_ms: u16) {
-        self.printing_rate_ms = printing_rate_ms;
+        self.options.printing_rate_ms = printing_rate_ms;
+        for image in &mut self.coloured_images {
+            image.options.printing_rate_ms = printing_rate_ms;
+        }
+    }
+}
+
+impl<G> Drop for Printer<G>
+where
+    G: Iterator<Item = PrinterImageData>,
+{
+    fn drop(&mut self) {
+        let _ = self.restore_screen();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NO_KEYS: KeySource = |_| Ok(None);
+    const SPACE_KEY: KeySource = |_| Ok(Some(KeyCode::Char(' ')));
+
+    fn coloured_image(rows: &[&str], printing_rate_ms: u16, reveal_style: RevealStyle) -> ColouredImage {
+        let image_array = rows.iter().map(|row| row.chars().map(StyledChar::plain).collect()).collect();
+        let options = PrinterOptions {
+            printing_rate_ms,
+            reveal_style,
+            show_timings: false,
+            cell_aspect_ratio: 0.5,
+            clear_mode: ClearMode::default(),
+            cache_limit: 0,
+        };
+        ColouredImage::new(image_array, 0, &Rc::new("test".to_string()), options, ImageTimings::default())
+    }
+
+    fn screen(width: u16, height: u16, keys: KeySource) -> Screen {
+        Screen {
+            size: Some((width, height)),
+            keys,
+        }
+    }
+
+    fn output(buffer: Vec<u8>) -> String {
+        String::from_utf8(buffer).unwrap()
+    }
+
+    #[test]
+    fn evicted_image_keeps_its_thumbnail() {
+        let mut image = coloured_image(&["abcd", "efgh"], 0, RevealStyle::default());
+        let thumbnail = image.thumbnail(4, 2);
+        image.evict(4, 2);
+        assert!(image.image_array.is_empty());
+        assert_eq!(image.thumbnail(4, 2), thumbnail);
+    }
+
+    #[test]
+    fn instant_print_clips_rows_to_the_screen() {
+        let image = coloured_image(&["abcd", "efgh", "ijkl"], 0, RevealStyle::default());
+        let mut buffer = Vec::new();
+        image.instant_print(&mut buffer, &screen(3, 5, NO_KEYS), 1).unwrap();
+        assert_eq!(output(buffer), "\x1b[2;1Habc\nefg\n");
+    }
+
+    #[test]
+    fn slow_print_without_keys_reveals_every_cell() {
+        let image = coloured_image(&["ab", "cd"], 1, RevealStyle::WipeDown);
+        let mut buffer = Vec::new();
+        let printing_rate = image.slow_print(&mut buffer, &screen(80, 24, NO_KEYS), 0).unwrap();
+        let output = output(buffer);
+        assert_eq!(printing_rate, 1);
+        assert!(output.contains("\x1b[1;1Hab") && output.contains("\x1b[2;1Hcd"));
+        assert!(output.contains(&format!("100% {}", "█".repeat(ColouredImage::REVEAL_PROGRESS_BAR_WIDTH))));
+        assert!(output.ends_with("\x1b[?25h"));
+    }
+
+    #[test]
+    fn slow_print_skipped_by_space_ends_with_the_whole_image() {
+        let image = coloured_image(&["ab", "cd"], 50, RevealStyle::RandomCells);
+        let screen = screen(80, 24, SPACE_KEY);
+        let mut whole_image = Vec::new();
+        image.instant_print(&mut whole_image, &screen, 0).unwrap();
+        let mut buffer = Vec::new();
+        image.slow_print(&mut buffer, &screen, 0).unwrap();
+        assert!(output(buffer).contains(&output(whole_image)));
     }
 }
@@ -0,0 +1,41 @@
+use crate::cell_grid::CellGrid;
+use crate::image_storage::{ImageLoadIterator, StorageError};
+use crate::sgr::{self, StyledChar};
+use crossterm::terminal;
+use std::io::{self, Write};
+use std::path::Path;
+
+pub struct Replay;
+
+impl Replay {
+    pub const SUCCESS_EXIT_CODE: i32 = 0;
+    pub const LOAD_FAILURE_EXIT_CODE: i32 = 1;
+    pub const USAGE_EXIT_CODE: i32 = 2;
+
+    pub fn run(image_path: &str) -> Result<(), StorageError> {
+        let image_array = ImageLoadIterator::load_file(Path::new(image_path))?.into_image_array();
+        let image_array = Self::fit_to_terminal(image_array);
+        let mut stdout = io::stdout().lock();
+        for row in &image_array {
+            writeln!(stdout, "{}", sgr::render(row))?;
+        }
+        stdout.flush()?;
+        Ok(())
+    }
+
+    // Only the width is fitted - taller images scroll like any other command output.
+    fn fit_to_terminal(image_array: Vec<Vec<StyledChar>>) -> Vec<Vec<StyledChar>> {
+        let columns = match terminal::size() {
+            Ok((columns, _)) if columns > 0 => columns as usize,
+            _ => return image_array,
+        };
+        let width: usize = image_array.first().map_or(0, |row| row.iter().map(StyledChar::width).sum());
+        if width <= columns {
+            return image_array;
+        }
+        let cell_width = width / image_array[0].len();
+        let grid = CellGrid::new(image_array);
+        let rows = (grid.height() * columns).div_ceil(width);
+        grid.thumbnail(columns / cell_width, rows)
+    }
+}
@@ -0,0 +1,142 @@
+use crate::image_source::{self, ImageSource};
+use crate::logger::Logger;
+use crate::messages::{fill, messages};
+use bytes::Bytes;
+use image::{DynamicImage, RgbaImage};
+use screenshots::Screen;
+use std::fmt;
+use std::rc::Rc;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub enum ScreenCaptureError {
+    NoScreenError,
+    InvalidRegionError,
+    CaptureError(String),
+    EncodingError,
+}
+
+impl fmt::Display for ScreenCaptureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScreenCaptureError::NoScreenError => write!(f, "{}", messages().no_screen),
+            ScreenCaptureError::InvalidRegionError => write!(f, "{}", messages().invalid_region),
+            ScreenCaptureError::CaptureError(e) => write!(f, "{}", fill(messages().capture_failed, &[e])),
+            ScreenCaptureError::EncodingError => write!(f, "{}", messages().frame_encoding_error),
+        }
+    }
+}
+
+impl std::error::Error for ScreenCaptureError {}
+
+pub struct CaptureRegion {
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+}
+
+impl FromStr for CaptureRegion {
+    type Err = ScreenCaptureError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(',').map(str::trim).collect();
+        if parts.len() != 4 {
+            return Err(ScreenCaptureError::InvalidRegionError);
+        }
+        let parse_error = |_| ScreenCaptureError::InvalidRegionError;
+        let region = Self {
+            x: parts[0].parse().map_err(parse_error)?,
+            y: parts[1].parse().map_err(parse_error)?,
+            width: parts[2].parse().map_err(parse_error)?,
+            height: parts[3].parse().map_err(parse_error)?,
+        };
+        if region.width == 0 || region.height == 0 {
+            return Err(ScreenCaptureError::InvalidRegionError);
+        }
+        Ok(region)
+    }
+}
+
+pub struct ScreenCapture {
+    screen: Screen,
+    region: Option<CaptureRegion>,
+    image_name: Rc<String>,
+    last_capture_time: Option<Duration>,
+}
+
+impl ScreenCapture {
+    pub fn new(screen_index: usize, region: Option<CaptureRegion>) -> Result<Self, ScreenCaptureError> {
+        let screen = Self::all_screens()?
+            .into_iter()
+            .nth(screen_index)
+            .ok_or(ScreenCaptureError::NoScreenError)?;
+        Ok(Self {
+            screen,
+            region,
+            image_name: Rc::new(format!("screen{}", screen_index + 1)),
+            last_capture_time: None,
+        })
+    }
+
+    pub fn screen_descriptions() -> Result<Vec<String>, ScreenCaptureError> {
+        Ok(Self::all_screens()?
+            .iter()
+            .enumerate()
+            .map(|(index, screen)| {
+                let info = &screen.display_info;
+                let primary_suffix = if info.is_primary { messages().screen_primary } else { "" };
+                fill(messages().screen_description, &[&(index + 1), &info.width, &info.height, &primary_suffix])
+            })
+            .collect())
+    }
+
+    fn all_screens() -> Result<Vec<Screen>, ScreenCaptureError> {
+        let screens = Screen::all().map_err(|e| ScreenCaptureError::CaptureError(e.to_string()))?;
+        if screens.is_empty() {
+            return Err(ScreenCaptureError::NoScreenError);
+        }
+        Ok(screens)
+    }
+
+    fn capture(&self) -> Result<Bytes, ScreenCaptureError> {
+        let captured = match &self.region {
+            Some(region) => self.screen.capture_area(region.x, region.y, region.width, region.height),
+            None => self.screen.capture(),
+        }
+        .map_err(|e| ScreenCaptureError::CaptureError(e.to_string()))?;
+        let (width, height) = captured.dimensions();
+        RgbaImage::from_raw(width, height, captured.into_raw())
+            .and_then(|frame| image_source::encode_frame(DynamicImage::ImageRgba8(frame)))
+            .ok_or(ScreenCaptureError::EncodingError)
+    }
+}
+
+impl Iterator for ScreenCapture {
+    type Item = (Rc<String>, Bytes);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let capture_start = Instant::now();
+        match self.capture() {
+            Ok(bytes) => {
+                self.last_capture_time = Some(capture_start.elapsed());
+                Some((self.image_name.clone(), bytes))
+            }
+            Err(e) => {
+                Logger::log_error(e.to_string().as_str());
+                None
+            }
+        }
+    }
+}
+
+impl ImageSource for ScreenCapture {
+    fn last_fetch_time(&self) -> Option<Duration> {
+        self.last_capture_time
+    }
+
+    fn is_live(&self) -> bool {
+        true
+    }
+}
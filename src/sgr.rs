@@ -0,0 +1,405 @@
+use crate::messages::{fill, messages};
+use std::fmt;
+use std::fmt::Write;
+use std::iter::Peekable;
+use std::str::Chars;
+
+#[derive(Debug)]
+pub enum SgrError {
+    UnterminatedSequenceError,
+    InvalidParameterError(String),
+}
+
+impl fmt::Display for SgrError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SgrError::UnterminatedSequenceError => write!(f, "{}", messages().unterminated_sequence),
+            SgrError::InvalidParameterError(param) => write!(f, "{}", fill(messages().invalid_sgr_parameter, &[param])),
+        }
+    }
+}
+
+impl std::error::Error for SgrError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Rgb(u8, u8, u8),
+    Indexed(u8),
+}
+
+impl Colour {
+    const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    const FADE_DIM: f32 = 0.4;
+    const BASIC_PALETTE: [(u8, u8, u8); 16] = [
+        (0, 0, 0), (205, 0, 0), (0, 205, 0), (205, 205, 0),
+        (0, 0, 238), (205, 0, 205), (0, 205, 205), (229, 229, 229),
+        (127, 127, 127), (255, 0, 0), (0, 255, 0), (255, 255, 0),
+        (92, 92, 255), (255, 0, 255), (0, 255, 255), (255, 255, 255),
+    ];
+
+    fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+        let dr = r1.abs_diff(r2) as u32;
+        let dg = g1.abs_diff(g2) as u32;
+        let db = b1.abs_diff(b2) as u32;
+        dr * dr + dg * dg + db * db
+    }
+
+    fn nearest_cube_level(channel: u8) -> usize {
+        (0..Self::CUBE_LEVELS.len())
+            .min_by_key(|&level| Self::CUBE_LEVELS[level].abs_diff(channel))
+            .expect("The cube has levels")
+    }
+
+    pub fn ansi_256(r: u8, g: u8, b: u8) -> Self {
+        let (ri, gi, bi) = (Self::nearest_cube_level(r), Self::nearest_cube_level(g), Self::nearest_cube_level(b));
+        let cube_colour = (Self::CUBE_LEVELS[ri], Self::CUBE_LEVELS[gi], Self::CUBE_LEVELS[bi]);
+        let cube_index = 16 + 36 * ri + 6 * gi + bi;
+        let grey_step = ((r as u32 + g as u32 + b as u32) / 3).saturating_sub(3) / 10;
+        let grey_step = grey_step.min(23) as u8;
+        let grey_level = 8 + grey_step * 10;
+        let index = if Self::distance((r, g, b), (grey_level, grey_level, grey_level)) < Self::distance((r, g, b), cube_colour) {
+            232 + grey_step as usize
+        } else {
+            cube_index
+        };
+        Colour::Indexed(index as u8)
+    }
+
+    pub fn rgb(&self) -> (u8, u8, u8) {
+        match *self {
+            Colour::Rgb(r, g, b) => (r, g, b),
+            Colour::Indexed(index) if index < 16 => Self::BASIC_PALETTE[index as usize],
+            Colour::Indexed(index) if index < 232 => {
+                let cube_index = (index - 16) as usize;
+                (
+                    Self::CUBE_LEVELS[cube_index / 36],
+                    Self::CUBE_LEVELS[cube_index / 6 % 6],
+                    Self::CUBE_LEVELS[cube_index % 6],
+                )
+            }
+            Colour::Indexed(index) => {
+                let grey_level = 8 + (index - 232) * 10;
+                (grey_level, grey_level, grey_level)
+            }
+        }
+    }
+
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim().trim_start_matches('#');
+        if hex.len() != 6 || !hex.is_ascii() {
+            return None;
+        }
+        let channel = |index: usize| u8::from_str_radix(&hex[index..index + 2], 16).ok();
+        Some(Colour::Rgb(channel(0)?, channel(2)?, channel(4)?))
+    }
+
+    pub fn hex(&self) -> String {
+        let (r, g, b) = self.rgb();
+        format!("#{:02x}{:02x}{:02x}", r, g, b)
+    }
+
+    // Blends from a dim grey of the same brightness (strength 0) to the colour itself (strength 1),
+    // staying in the palette the colour came from so 16 and 256 colour terminals keep working.
+    pub fn faded(&self, strength: f32) -> Self {
+        // Palette colours do not always survive the round trip through RGB.
+        if strength >= 1.0 {
+            return *self;
+        }
+        let (r, g, b) = self.rgb();
+        let grey = (299 * r as u32 + 587 * g as u32 + 114 * b as u32) as f32 / 1000.0 * Self::FADE_DIM;
+        let mix = |channel: u8| (grey + (channel as f32 - grey) * strength.max(0.0)).round() as u8;
+        let (r, g, b) = (mix(r), mix(g), mix(b));
+        match self {
+            Colour::Rgb(..) => Colour::Rgb(r, g, b),
+            Colour::Indexed(index) if *index < 16 => Self::ansi_16(r, g, b),
+            Colour::Indexed(_) => Self::ansi_256(r, g, b),
+        }
+    }
+
+    pub fn ansi_16(r: u8, g: u8, b: u8) -> Self {
+        let index = (0..Self::BASIC_PALETTE.len())
+            .min_by_key(|&index| Self::distance((r, g, b), Self::BASIC_PALETTE[index]))
+            .expect("The palette has colours");
+        Colour::Indexed(index as u8)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StyledChar {
+    pub ch: char,
+    pub foreground: Option<Colour>,
+    pub background: Option<Colour>,
+}
+
+impl StyledChar {
+    pub fn plain(ch: char) -> Self {
+        Self {
+            ch,
+            foreground: None,
+            background: None,
+        }
+    }
+
+    const RESET: &'static str = "\x1B[0m";
+
+    pub fn faded(&self, strength: f32) -> Self {
+        Self {
+            ch: self.ch,
+            foreground: self.foreground.map(|colour| colour.faded(strength)),
+            background: self.background.map(|colour| colour.faded(strength)),
+        }
+    }
+
+    // Emoji and fullwidth forms take up two terminal columns, everything else the converter produces takes one.
+    pub fn width(&self) -> usize {
+        match self.ch {
+            '\u{1F300}'..='\u{1FAFF}' | '\u{2B1B}' | '\u{2B1C}' | '\u{3000}' | '\u{FF01}'..='\u{FF5E}' => 2,
+            _ => 1,
+        }
+    }
+
+    fn write_colour(f: &mut impl Write, colour: Colour, is_background: bool) -> fmt::Result {
+        let (basic_offset, bright_offset, extended) = if is_background { (40, 100, 48) } else { (30, 90, 38) };
+        match colour {
+            Colour::Rgb(r, g, b) => write!(f, "\x1B[{};2;{};{};{}m", extended, r, g, b),
+            Colour::Indexed(index) if index < 8 => write!(f, "\x1B[{}m", basic_offset + index as u16),
+            Colour::Indexed(index) if index < 16 => write!(f, "\x1B[{}m", bright_offset + index as u16 - 8),
+            Colour::Indexed(index) => write!(f, "\x1B[{};5;{}m", extended, index),
+        }
+    }
+}
+
+impl fmt::Display for StyledChar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(colour) = self.foreground {
+            Self::write_colour(f, colour, false)?;
+        }
+        if let Some(colour) = self.background {
+            Self::write_colour(f, colour, true)?;
+        }
+        write!(f, "{}", self.ch)?;
+        if self.foreground.is_some() || self.background.is_some() {
+            write!(f, "{}", Self::RESET)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct SgrState {
+    foreground: Option<Colour>,
+    background: Option<Colour>,
+}
+
+impl SgrState {
+    fn parse_number(param: &str) -> Result<u16, SgrError> {
+        if param.is_empty() {
+            return Ok(0);
+        }
+        param
+            .parse::<u16>()
+            .map_err(|_| SgrError::InvalidParameterError(param.to_string()))
+    }
+
+    fn parse_component(param: Option<&&str>) -> Result<u8, SgrError> {
+        let param = param.ok_or_else(|| SgrError::InvalidParameterError(String::new()))?;
+        u8::try_from(Self::parse_number(param)?)
+            .map_err(|_| SgrError::InvalidParameterError(param.to_string()))
+    }
+
+    // The colon form keeps a colour in one parameter: 38:5:n, or 38:2:id:r:g:b with an often empty colour-space id.
+    fn parse_colon_colour(sub_params: &[&str]) -> Result<Colour, SgrError> {
+        match sub_params.first().copied().unwrap_or("") {
+            "5" => Ok(Colour::Indexed(Self::parse_component(sub_params.get(1))?)),
+            "2" => {
+                // Some programs leave the colour-space id out altogether.
+                let channels = if sub_params.len() > 4 { &sub_params[2..] } else { &sub_params[1..] };
+                Ok(Colour::Rgb(
+                    Self::parse_component(channels.first())?,
+                    Self::parse_component(channels.get(1))?,
+                    Self::parse_component(channels.get(2))?,
+                ))
+            }
+            mode => Err(SgrError::InvalidParameterError(mode.to_string())),
+        }
+    }
+
+    fn extended_colour(params: &[&str], sub_params: &[&str], index: &mut usize) -> Result<Colour, SgrError> {
+        if sub_params.len() > 1 {
+            Self::parse_colon_colour(&sub_params[1..])
+        } else {
+            Self::parse_extended_colour(params, index)
+        }
+    }
+
+    fn parse_extended_colour(params: &[&str], index: &mut usize) -> Result<Colour, SgrError> {
+        let mode = params.get(*index + 1).copied().unwrap_or("");
+        match mode {
+            "5" => {
+                let colour = Colour::Indexed(Self::parse_component(params.get(*index + 2))?);
+                *index += 2;
+                Ok(colour)
+            }
+            "2" => {
+                let colour = Colour::Rgb(
+                    Self::parse_component(params.get(*index + 2))?,
+                    Self::parse_component(params.get(*index + 3))?,
+                    Self::parse_component(params.get(*index + 4))?,
+                );
+                *index += 4;
+                Ok(colour)
+            }
+            _ => Err(SgrError::InvalidParameterError(mode.to_string())),
+        }
+    }
+
+    fn apply(&mut self, sequence: &str) -> Result<(), SgrError> {
+        let params: Vec<&str> = sequence.split(';').collect();
+        let mut index = 0;
+        while index < params.len() {
+            let sub_params: Vec<&str> = params[index].split(':').collect();
+            match Self::parse_number(sub_params[0])? {
+                0 => *self = Self::default(),
+                code @ 30..=37 => self.foreground = Some(Colour::Indexed((code - 30) as u8)),
+                code @ 90..=97 => self.foreground = Some(Colour::Indexed((code - 90 + 8) as u8)),
+                38 => self.foreground = Some(Self::extended_colour(&params, &sub_params, &mut index)?),
+                39 => self.foreground = None,
+                code @ 40..=47 => self.background = Some(Colour::Indexed((code - 40) as u8)),
+                code @ 100..=107 => self.background = Some(Colour::Indexed((code - 100 + 8) as u8)),
+                48 => self.background = Some(Self::extended_colour(&params, &sub_params, &mut index)?),
+                49 => self.background = None,
+                _ => {}
+            }
+            index += 1;
+        }
+        Ok(())
+    }
+
+    // OSC titles and hyperlinks, DCS and the other string sequences run until BEL or the string terminator ESC \.
+    fn skip_string_sequence(chars: &mut Peekable<Chars>) -> Result<(), SgrError> {
+        loop {
+            match chars.next() {
+                Some('\x07') | Some('\u{9C}') => return Ok(()),
+                Some('\x1B') if chars.next_if_eq(&'\\').is_some() => return Ok(()),
+                Some(_) => {}
+                None => return Err(SgrError::UnterminatedSequenceError),
+            }
+        }
+    }
+
+    fn consume_escape(&mut self, chars: &mut Peekable<Chars>) -> Result<(), SgrError> {
+        match chars.next() {
+            Some('[') => {}
+            Some(']' | 'P' | 'X' | '^' | '_') => return Self::skip_string_sequence(chars),
+            // Any intermediate bytes, as in the charset selection ESC ( B, come before the final one.
+            Some(mut c) => {
+                while ('\x20'..='\x2F').contains(&c) {
+                    c = chars.next().ok_or(SgrError::UnterminatedSequenceError)?;
+                }
+                return Ok(());
+            }
+            None => return Err(SgrError::UnterminatedSequenceError),
+        }
+        let mut sequence = String::new();
+        loop {
+            match chars.next() {
+                Some('m') => return self.apply(&sequence),
+                Some('\x40'..='\x7E') => return Ok(()),
+                Some(c) => sequence.push(c),
+                None => return Err(SgrError::UnterminatedSequenceError),
+            }
+        }
+    }
+}
+
+pub fn parse(text: &str) -> Result<Vec<StyledChar>, SgrError> {
+    let mut state = SgrState::default();
+    let mut result = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\x1B' => state.consume_escape(&mut chars)?,
+            c if c.is_control() => {}
+            c => result.push(StyledChar {
+                ch: c,
+                foreground: state.foreground,
+                background: state.background,
+            }),
+        }
+    }
+    Ok(result)
+}
+
+// Colour codes are only emitted where they change, so runs of equal colours share one prefix.
+pub fn render(styled_chars: &[StyledChar]) -> String {
+    let mut result = String::with_capacity(styled_chars.len() * 4);
+    let mut current = StyledChar::plain(' ');
+    for styled_char in styled_chars {
+        let dropped_colour = (current.foreground.is_some() && styled_char.foreground.is_none())
+            || (current.background.is_some() && styled_char.background.is_none());
+        if dropped_colour {
+            result.push_str(StyledChar::RESET);
+            current = StyledChar::plain(' ');
+        }
+        if let Some(colour) = styled_char.foreground.filter(|colour| current.foreground != Some(*colour)) {
+            StyledChar::write_colour(&mut result, colour, false).expect("Writing to String should not fail");
+        }
+        if let Some(colour) = styled_char.background.filter(|colour| current.background != Some(*colour)) {
+            StyledChar::write_colour(&mut result, colour, true).expect("Writing to String should not fail");
+        }
+        result.push(styled_char.ch);
+        current = *styled_char;
+    }
+    if current.foreground.is_some() || current.background.is_some() {
+        result.push_str(StyledChar::RESET);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coloured(ch: char, foreground: Option<Colour>, background: Option<Colour>) -> StyledChar {
+        StyledChar { ch, foreground, background }
+    }
+
+    #[test]
+    fn parse_reads_back_what_render_wrote() {
+        let styled_chars = vec![
+            coloured('a', Some(Colour::Rgb(10, 20, 30)), None),
+            coloured('b', Some(Colour::Rgb(10, 20, 30)), Some(Colour::Indexed(4))),
+            coloured('c', Some(Colour::Indexed(12)), Some(Colour::Indexed(200))),
+            StyledChar::plain('d'),
+            coloured('e', None, Some(Colour::Rgb(255, 0, 128))),
+        ];
+        assert_eq!(parse(&render(&styled_chars)).unwrap(), styled_chars);
+    }
+
+    #[test]
+    fn parse_reads_the_colon_form_with_and_without_colour_space() {
+        let expected = vec![coloured('x', Some(Colour::Rgb(10, 20, 30)), Some(Colour::Indexed(42)))];
+        assert_eq!(parse("\x1B[38:2::10:20:30;48:5:42mx").unwrap(), expected);
+        assert_eq!(parse("\x1B[38:2:10:20:30;48:5:42mx").unwrap(), expected);
+        assert_eq!(parse("\x1B[38:2:0:10:20:30;48:5:42mx").unwrap(), expected);
+    }
+
+    #[test]
+    fn parse_skips_non_sgr_csi_sequences() {
+        assert_eq!(parse("\x1B[2J\x1B[1;1Hab\x1B[?25l").unwrap(), vec![StyledChar::plain('a'), StyledChar::plain('b')]);
+    }
+
+    #[test]
+    fn parse_skips_osc_and_other_escapes() {
+        let expected = vec![StyledChar::plain('a'), StyledChar::plain('b')];
+        assert_eq!(parse("\x1B]0;title\x07ab").unwrap(), expected);
+        assert_eq!(parse("\x1B]8;;https://example.com\x1B\\a\x1B]8;;\x1B\\b").unwrap(), expected);
+        assert_eq!(parse("\x1B(Ba\x1B7b").unwrap(), expected);
+    }
+
+    #[test]
+    fn parse_reports_unterminated_sequences() {
+        assert!(matches!(parse("a\x1B]0;title"), Err(SgrError::UnterminatedSequenceError)));
+        assert!(matches!(parse("a\x1B[38;2"), Err(SgrError::UnterminatedSequenceError)));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::graphics::GraphicsProtocol;
+use image::{ImageError, Rgb, RgbImage};
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::{self, Write};
+
+pub struct Sixel;
+
+impl Sixel {
+    const SUPPORTING_TERMS: [&'static str; 2] = ["foot", "mlterm"];
+    const SUPPORTING_TERM_PROGRAMS: [&'static str; 2] = ["WezTerm", "mlterm"];
+    const START: &'static str = "\x1BPq";
+    const END: &'static str = "\x1B\\";
+    const LEVELS: u32 = 6;
+    const BAND_HEIGHT: u32 = 6;
+    const SIXEL_OFFSET: u8 = 0x3F;
+    const MIN_REPEAT: usize = 4;
+
+    fn register(pixel: &Rgb<u8>) -> u32 {
+        let [r, g, b] = pixel.0.map(|channel| (channel as u32 * (Self::LEVELS - 1) + 127) / 255);
+        (r * Self::LEVELS + g) * Self::LEVELS + b
+    }
+
+    fn write_palette(sixel: &mut String) -> fmt::Result {
+        let percent = |level: u32| level * 100 / (Self::LEVELS - 1);
+        for register in 0..Self::LEVELS.pow(3) {
+            let (r, g, b) = (register / (Self::LEVELS * Self::LEVELS), register / Self::LEVELS % Self::LEVELS, register % Self::LEVELS);
+            write!(sixel, "#{};2;{};{};{}", register, percent(r), percent(g), percent(b))?;
+        }
+        Ok(())
+    }
+
+    fn write_image(sixel: &mut String, image: &RgbImage) -> fmt::Result {
+        let (width, height) = image.dimensions();
+        write!(sixel, "{}\"1;1;{};{}", Self::START, width, height)?;
+        Self::write_palette(sixel)?;
+        let registers: Vec<u32> = image.pixels().map(Self::register).collect();
+        for band_top in (0..height).step_by(Self::BAND_HEIGHT as usize) {
+            // Every colour present in the band gets its own pass over the band, joined by '$'.
+            let mut band: BTreeMap<u32, Vec<u8>> = BTreeMap::new();
+            for dy in 0..(height - band_top).min(Self::BAND_HEIGHT) {
+                let row = ((band_top + dy) * width) as usize;
+                for x in 0..width as usize {
+                    band.entry(registers[row + x]).or_insert_with(|| vec![0; width as usize])[x] |= 1 << dy;
+                }
+            }
+            for (index, (register, sixels)) in band.iter().enumerate() {
+                if index > 0 {
+                    sixel.push('$');
+                }
+                write!(sixel, "#{}", register)?;
+                Self::write_run_length(sixel, sixels)?;
+            }
+            sixel.push('-');
+        }
+        sixel.push_str(Self::END);
+        Ok(())
+    }
+
+    fn write_run_length(sixel: &mut String, sixels: &[u8]) -> fmt::Result {
+        let length = sixels.iter().rposition(|bits| *bits != 0).map_or(0, |last| last + 1);
+        let mut index = 0;
+        while index < length {
+            let bits = sixels[index];
+            let run = sixels[index..length].iter().take_while(|other| **other == bits).count();
+            let ch = (Self::SIXEL_OFFSET + bits) as char;
+            if run >= Self::MIN_REPEAT {
+                write!(sixel, "!{}{}", run, ch)?;
+            } else {
+                (0..run).for_each(|_| sixel.push(ch));
+            }
+            index += run;
+        }
+        Ok(())
+    }
+}
+
+impl GraphicsProtocol for Sixel {
+    const OVERRIDE_VARIABLE: &'static str = "COLOURFUL_WORDS_SIXEL";
+
+    // Plain xterm only draws Sixel when built and started for it, which its TERM then usually says (xterm-sixel).
+    fn is_detected() -> bool {
+        env::var("TERM").is_ok_and(|term| term.contains("sixel") || Self::SUPPORTING_TERMS.iter().any(|supporting| term.starts_with(supporting)))
+            || env::var("TERM_PROGRAM").is_ok_and(|program| Self::SUPPORTING_TERM_PROGRAMS.contains(&program.as_str()))
+    }
+
+    // Sixel draws the image pixel for pixel, so the cell size is left to the image itself.
+    fn encode(image: &RgbImage, _columns: u32, _rows: u32) -> Result<String, ImageError> {
+        let mut sixel = String::new();
+        Self::write_image(&mut sixel, image).expect("Writing to String should not fail");
+        Ok(sixel)
+    }
+}
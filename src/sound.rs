@@ -0,0 +1,116 @@
+use crate::logger::Logger;
+use crate::messages::{fill, messages};
+use std::io::Write;
+use std::process::{self, Stdio};
+use std::time::Duration;
+use std::{fmt, io, thread};
+
+#[derive(Clone, Copy)]
+pub enum SoundEvent {
+    Save,
+    Copy,
+    Error,
+}
+
+impl SoundEvent {
+    fn name(&self) -> &'static str {
+        match self {
+            SoundEvent::Save => "save",
+            SoundEvent::Copy => "copy",
+            SoundEvent::Error => "error",
+        }
+    }
+
+    fn bell_count(&self) -> usize {
+        match self {
+            SoundEvent::Save => 1,
+            SoundEvent::Copy => 2,
+            SoundEvent::Error => 3,
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub enum SoundFeedback {
+    #[default]
+    Off,
+    Bell,
+    Command(String),
+}
+
+impl SoundFeedback {
+    const BELL_GAP: Duration = Duration::from_millis(150);
+
+    pub fn code(&self) -> &'static str {
+        match self {
+            SoundFeedback::Off => "off",
+            SoundFeedback::Bell => "bell",
+            SoundFeedback::Command(_) => "command",
+        }
+    }
+
+    pub fn command(&self) -> &str {
+        match self {
+            SoundFeedback::Command(command) => command,
+            _ => "",
+        }
+    }
+
+    pub fn from_code(code: &str, command: Option<&str>) -> Option<SoundFeedback> {
+        match code.trim() {
+            "off" => Some(SoundFeedback::Off),
+            "bell" => Some(SoundFeedback::Bell),
+            "command" => command
+                .map(str::trim)
+                .filter(|command| !command.is_empty())
+                .map(|command| SoundFeedback::Command(command.to_string())),
+            _ => None,
+        }
+    }
+
+    pub fn play(&self, event: SoundEvent) {
+        match self {
+            SoundFeedback::Off => {}
+            SoundFeedback::Bell => {
+                let bell_count = event.bell_count();
+                thread::spawn(move || {
+                    for _ in 0..bell_count {
+                        let mut stdout = io::stdout();
+                        let _ = stdout.write_all(b"\x07").and_then(|_| stdout.flush());
+                        thread::sleep(Self::BELL_GAP);
+                    }
+                });
+            }
+            SoundFeedback::Command(command) => {
+                let mut parts = command.split_whitespace();
+                let program = match parts.next() {
+                    Some(program) => program,
+                    None => return,
+                };
+                let child = process::Command::new(program)
+                    .args(parts)
+                    .arg(event.name())
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .spawn();
+                match child {
+                    Ok(mut child) => {
+                        thread::spawn(move || child.wait());
+                    }
+                    Err(e) => Logger::log_error(&fill(messages().sound_command_failed, &[command, &e])),
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for SoundFeedback {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SoundFeedback::Off => write!(f, "{}", messages().sound_off),
+            SoundFeedback::Bell => write!(f, "{}", messages().sound_bell),
+            SoundFeedback::Command(command) => write!(f, "{}", fill(messages().sound_command, &[command])),
+        }
+    }
+}
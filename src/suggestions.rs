@@ -0,0 +1,99 @@
+use dialoguer::Completion;
+use reqwest::blocking;
+use std::cell::RefCell;
+use std::time::Duration;
+
+struct SuggestionCycle {
+    candidates: Vec<String>,
+    index: usize,
+}
+
+pub struct KeywordSuggestions {
+    history: Vec<String>,
+    client: Option<blocking::Client>,
+    cycle: RefCell<Option<SuggestionCycle>>,
+}
+
+impl KeywordSuggestions {
+    const SUGGESTION_URL: &'static str = "https://api.bing.com/osjson.aspx";
+    const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+    pub fn new(history: &[String], online: bool) -> Self {
+        let client = if online {
+            blocking::Client::builder()
+                .timeout(Self::REQUEST_TIMEOUT)
+                .build()
+                .ok()
+        } else {
+            None
+        };
+        Self {
+            history: history.to_vec(),
+            client,
+            cycle: RefCell::new(None),
+        }
+    }
+
+    fn fetch_online(&self, prefix: &str) -> Vec<String> {
+        let client = match &self.client {
+            Some(client) => client,
+            None => return Vec::new(),
+        };
+        let response = client
+            .get(Self::SUGGESTION_URL)
+            .query(&[("query", prefix)])
+            .send()
+            .and_then(|res| res.error_for_status())
+            .and_then(|res| res.text());
+        match response.map(|body| serde_json::from_str::<serde_json::Value>(&body)) {
+            Ok(Ok(value)) => value
+                .get(1)
+                .and_then(|suggestions| suggestions.as_array())
+                .map(|suggestions| {
+                    suggestions
+                        .iter()
+                        .filter_map(|suggestion| suggestion.as_str())
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn candidates(&self, input: &str) -> Vec<String> {
+        let prefix = input.trim().to_lowercase();
+        let mut candidates: Vec<String> = self
+            .history
+            .iter()
+            .filter(|keyword| keyword.to_lowercase().starts_with(&prefix))
+            .cloned()
+            .collect();
+        if !prefix.is_empty() {
+            candidates.extend(self.fetch_online(&prefix));
+        }
+        let mut unique_candidates: Vec<String> = Vec::new();
+        for candidate in candidates {
+            if candidate != input && !unique_candidates.contains(&candidate) {
+                unique_candidates.push(candidate);
+            }
+        }
+        unique_candidates
+    }
+}
+
+impl Completion for KeywordSuggestions {
+    fn get(&self, input: &str) -> Option<String> {
+        let mut cycle = self.cycle.borrow_mut();
+        if let Some(cycle) = cycle.as_mut() {
+            if cycle.candidates.get(cycle.index).is_some_and(|current| current == input) {
+                cycle.index = (cycle.index + 1) % cycle.candidates.len();
+                return Some(cycle.candidates[cycle.index].clone());
+            }
+        }
+        let candidates = self.candidates(input);
+        let first_candidate = candidates.first()?.clone();
+        *cycle = Some(SuggestionCycle { candidates, index: 0 });
+        Some(first_candidate)
+    }
+}
@@ -0,0 +1,46 @@
+use crate::logger::Logger;
+use crate::messages::{fill, messages};
+use crossterm::style::ResetColor;
+use crossterm::terminal::LeaveAlternateScreen;
+use crossterm::{cursor, QueueableCommand};
+use std::io::{self, Write};
+use std::panic;
+use std::process::exit;
+
+// Puts the terminal back the way the user had it, however the program ends: on return, panic or Ctrl+C.
+pub struct TerminalGuard;
+
+impl TerminalGuard {
+    const INTERRUPTED_EXIT_CODE: i32 = 130;
+
+    pub fn install() -> Self {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            Self::restore();
+            default_hook(info);
+        }));
+        if let Err(e) = ctrlc::set_handler(|| {
+            Self::restore();
+            exit(Self::INTERRUPTED_EXIT_CODE);
+        }) {
+            Logger::log_error(&fill(messages().interrupt_handler_failed, &[&e]));
+        }
+        Self
+    }
+
+    // Leaving the alternate screen while on the main one does nothing, so this is safe to run at any point.
+    fn restore() {
+        let mut stdout = io::stdout();
+        let _ = stdout
+            .queue(ResetColor)
+            .and_then(|stdout| stdout.queue(cursor::Show))
+            .and_then(|stdout| stdout.queue(LeaveAlternateScreen))
+            .and_then(|stdout| stdout.flush());
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        Self::restore();
+    }
+}
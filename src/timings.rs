@@ -0,0 +1,67 @@
+use crate::messages::messages;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+pub enum TimingStage {
+    Download,
+    Decode,
+    Resize,
+    Colorize,
+    Render,
+}
+
+impl TimingStage {
+    const ALL: [TimingStage; 5] = [
+        TimingStage::Download,
+        TimingStage::Decode,
+        TimingStage::Resize,
+        TimingStage::Colorize,
+        TimingStage::Render,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            TimingStage::Download => messages().timing_download,
+            TimingStage::Decode => messages().timing_decode,
+            TimingStage::Resize => messages().timing_resize,
+            TimingStage::Colorize => messages().timing_colorize,
+            TimingStage::Render => messages().timing_render,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImageTimings {
+    stages: [Option<Duration>; 5],
+}
+
+impl ImageTimings {
+    pub fn record(&mut self, stage: TimingStage, duration: Duration) {
+        self.stages[stage as usize] = Some(duration);
+    }
+
+    pub fn measure<T>(&mut self, stage: TimingStage, action: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let result = action();
+        self.record(stage, start.elapsed());
+        result
+    }
+}
+
+impl fmt::Display for ImageTimings {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let recorded: Vec<String> = TimingStage::ALL
+            .iter()
+            .filter_map(|stage| {
+                self.stages[*stage as usize]
+                    .map(|duration| format!("{} {} ms", stage.name(), duration.as_millis()))
+            })
+            .collect();
+        if recorded.is_empty() {
+            write!(f, "{}", messages().timings_empty)
+        } else {
+            write!(f, "{}", recorded.join(", "))
+        }
+    }
+}
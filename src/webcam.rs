@@ -0,0 +1,114 @@
+use crate::image_source::{self, ImageSource};
+use crate::logger::Logger;
+use crate::messages::{fill, messages};
+use bytes::Bytes;
+use image::{DynamicImage, RgbImage};
+use nokhwa::pixel_format::RgbFormat;
+use nokhwa::utils::{ApiBackend, CameraIndex, RequestedFormat, RequestedFormatType};
+use nokhwa::Camera;
+use std::fmt;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+#[derive(Debug)]
+pub enum WebcamError {
+    NoCameraError,
+    CameraError(String),
+    EncodingError,
+}
+
+impl fmt::Display for WebcamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WebcamError::NoCameraError => write!(f, "{}", messages().no_camera),
+            WebcamError::CameraError(e) => write!(f, "{}", fill(messages().camera_error, &[e])),
+            WebcamError::EncodingError => write!(f, "{}", messages().frame_encoding_error),
+        }
+    }
+}
+
+impl std::error::Error for WebcamError {}
+
+pub struct Webcam {
+    camera: Camera,
+    image_name: Rc<String>,
+    last_capture_time: Option<Duration>,
+}
+
+impl Webcam {
+    pub fn new(camera_index: u32) -> Result<Self, WebcamError> {
+        let requested_format = RequestedFormat::new::<RgbFormat>(RequestedFormatType::AbsoluteHighestResolution);
+        let mut camera = Camera::new(CameraIndex::Index(camera_index), requested_format)
+            .map_err(|e| WebcamError::CameraError(e.to_string()))?;
+        camera
+            .open_stream()
+            .map_err(|e| WebcamError::CameraError(e.to_string()))?;
+        Ok(Self {
+            camera,
+            image_name: Rc::new("webcam".to_string()),
+            last_capture_time: None,
+        })
+    }
+
+    pub fn camera_descriptions() -> Result<Vec<(u32, String)>, WebcamError> {
+        let cameras = nokhwa::query(ApiBackend::Auto).map_err(|e| WebcamError::CameraError(e.to_string()))?;
+        if cameras.is_empty() {
+            return Err(WebcamError::NoCameraError);
+        }
+        Ok(cameras
+            .iter()
+            .filter_map(|info| match info.index() {
+                CameraIndex::Index(index) => Some((*index, info.human_name())),
+                CameraIndex::String(_) => None,
+            })
+            .collect())
+    }
+
+    fn capture(&mut self) -> Result<Bytes, WebcamError> {
+        let frame = self
+            .camera
+            .frame()
+            .map_err(|e| WebcamError::CameraError(e.to_string()))?;
+        let decoded = frame
+            .decode_image::<RgbFormat>()
+            .map_err(|e| WebcamError::CameraError(e.to_string()))?;
+        let (width, height) = decoded.dimensions();
+        RgbImage::from_raw(width, height, decoded.into_raw())
+            .and_then(|frame| image_source::encode_frame(DynamicImage::ImageRgb8(frame)))
+            .ok_or(WebcamError::EncodingError)
+    }
+}
+
+impl Iterator for Webcam {
+    type Item = (Rc<String>, Bytes);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let capture_start = Instant::now();
+        match self.capture() {
+            Ok(bytes) => {
+                self.last_capture_time = Some(capture_start.elapsed());
+                Some((self.image_name.clone(), bytes))
+            }
+            Err(e) => {
+                Logger::log_error(e.to_string().as_str());
+                None
+            }
+        }
+    }
+}
+
+impl ImageSource for Webcam {
+    fn last_fetch_time(&self) -> Option<Duration> {
+        self.last_capture_time
+    }
+
+    fn is_live(&self) -> bool {
+        true
+    }
+}
+
+impl Drop for Webcam {
+    fn drop(&mut self) {
+        let _ = self.camera.stop_stream();
+    }
+}
@@ -0,0 +1,153 @@
+use portable_pty::{native_pty_system, CommandBuilder, PtySize};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const ROWS: u16 = 48;
+const COLUMNS: u16 = 120;
+const IMAGE_WIDTH: u16 = 30;
+const IMAGE_HEIGHT: u16 = 10;
+const DEFAULT_RAMP: &str = "@#S%&?*=+-:,.";
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+struct VirtualTerminal {
+    parser: vt100::Parser,
+    output: Receiver<Vec<u8>>,
+    input: Box<dyn Write + Send>,
+    child: Box<dyn portable_pty::Child + Send + Sync>,
+    config_dir: PathBuf,
+}
+
+impl VirtualTerminal {
+    fn spawn() -> Self {
+        let pair = native_pty_system()
+            .openpty(PtySize {
+                rows: ROWS,
+                cols: COLUMNS,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .expect("Failed to open a pty");
+        let config_dir = std::env::temp_dir().join(format!("colourful_words_pty_{}", std::process::id()));
+        // An existing config file skips the first-run setup wizard
+        std::fs::create_dir_all(&config_dir).expect("Failed to create the config directory");
+        std::fs::write(config_dir.join("config.ini"), "language = en\n").expect("Failed to write the config file");
+        let mut command = CommandBuilder::new(env!("CARGO_BIN_EXE_ColourfulWords"));
+        command.env("COLOURFUL_WORDS_MOCK_SOURCE", "1");
+        command.env("COLOURFUL_WORDS_CONFIG", config_dir.join("config.ini"));
+        command.env("LC_ALL", "C");
+        command.env("LANG", "C");
+        command.env("TERM", "xterm-256color");
+        command.env("COLORTERM", "truecolor");
+        let child = pair.slave.spawn_command(command).expect("Failed to spawn the application");
+        drop(pair.slave);
+        let mut reader = pair.master.try_clone_reader().expect("Failed to read from the pty");
+        let input = pair.master.take_writer().expect("Failed to write to the pty");
+        let (sender, output) = mpsc::channel();
+        thread::spawn(move || {
+            let mut buffer = [0u8; 4096];
+            while let Ok(read) = reader.read(&mut buffer) {
+                if read == 0 || sender.send(buffer[..read].to_vec()).is_err() {
+                    break;
+                }
+            }
+        });
+        Self {
+            parser: vt100::Parser::new(ROWS, COLUMNS, 0),
+            output,
+            input,
+            child,
+            config_dir,
+        }
+    }
+
+    fn send(&mut self, keys: &str) {
+        self.input.write_all(keys.as_bytes()).expect("Failed to send keys");
+        self.input.flush().expect("Failed to send keys");
+    }
+
+    fn wait_until(&mut self, description: &str, condition: impl Fn(&vt100::Screen) -> bool) {
+        let start = Instant::now();
+        while !condition(self.parser.screen()) {
+            let remaining = TIMEOUT.checked_sub(start.elapsed()).unwrap_or_else(|| {
+                panic!("Timed out waiting for {}. Screen:\n{}", description, self.parser.screen().contents())
+            });
+            if let Ok(bytes) = self.output.recv_timeout(remaining.min(Duration::from_millis(100))) {
+                self.parser.process(&bytes);
+            }
+        }
+    }
+
+    fn wait_for_text(&mut self, text: &str) {
+        self.wait_until(text, |screen| screen.contents().contains(text));
+    }
+}
+
+impl Drop for VirtualTerminal {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = std::fs::remove_dir_all(&self.config_dir);
+    }
+}
+
+fn header_row(screen: &vt100::Screen) -> Option<u16> {
+    screen
+        .rows(0, COLUMNS)
+        .position(|row| row.starts_with("Image 1"))
+        .map(|row| row as u16)
+}
+
+fn is_image_rendered(screen: &vt100::Screen) -> bool {
+    let header = match header_row(screen) {
+        Some(header) => header,
+        None => return false,
+    };
+    (header + 1..=header + IMAGE_HEIGHT).all(|row| {
+        (0..IMAGE_WIDTH).all(|column| {
+            screen
+                .cell(row, column)
+                .and_then(|cell| cell.contents().chars().next())
+                .is_some_and(|ch| DEFAULT_RAMP.contains(ch))
+        })
+    })
+}
+
+fn rgb(screen: &vt100::Screen, row: u16, column: u16) -> (u8, u8, u8) {
+    match screen.cell(row, column).map(|cell| cell.fgcolor()) {
+        Some(vt100::Color::Rgb(r, g, b)) => (r, g, b),
+        other => panic!("Expected a true colour cell at {},{}, got {:?}", row, column, other),
+    }
+}
+
+#[test]
+fn printer_renders_mock_image_grid() {
+    let mut terminal = VirtualTerminal::spawn();
+    terminal.wait_for_text("Generator mode");
+    terminal.send("\r");
+    terminal.wait_for_text("Enter image width");
+    terminal.send(&format!("{}\r", IMAGE_WIDTH));
+    terminal.wait_for_text("Crop before converting");
+    terminal.send("\r");
+    terminal.wait_for_text("Press 'Q'");
+    terminal.send("n\r");
+    terminal.wait_until("the first sample image", is_image_rendered);
+    terminal.wait_for_text("Image 1/");
+
+    let screen = terminal.parser.screen();
+    let header = header_row(screen).expect("The image header should be visible");
+    let row_after_image = screen.rows(0, COLUMNS).nth((header + IMAGE_HEIGHT + 1) as usize).unwrap_or_default();
+    assert!(
+        row_after_image.trim_start().starts_with("Image 1/"),
+        "The image should be exactly {} rows tall and followed by the status bar",
+        IMAGE_HEIGHT
+    );
+
+    let (top_r, top_g, top_b) = rgb(screen, header + 1, 0);
+    assert!(top_b > top_r && top_b > top_g, "The gradient should start blue");
+    let (bottom_r, bottom_g, bottom_b) = rgb(screen, header + IMAGE_HEIGHT, IMAGE_WIDTH - 1);
+    assert!(bottom_r > bottom_b && bottom_g > bottom_b, "The gradient should end yellow");
+
+    terminal.send("q\r");
+}